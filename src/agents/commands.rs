@@ -1,13 +1,48 @@
 //! Agents domain commands
 
-use crate::agents::compiler::{compile_agent, Platform};
+use crate::agents::compiler::{agent_filename, compile_agent, CompiledAgent, Platform};
 use crate::agents::diff::{AgentDiffer, AgentStatus};
 use crate::agents::discovery::AgentDiscovery;
-use crate::agents::parser::parse_agent_md;
-use crate::agents::validator::validate_agent;
+use crate::agents::parser::{parse_agent_md, parse_agent_md_collecting, ParsedAgent};
+use crate::agents::sarif::{sarif_report, AgentFindings, ValidationFormat};
+use crate::agents::validator::{collect_agent_errors, validate_name, ValidationError};
+use crate::core::confirm::confirm;
 use crate::core::infra::{Checksum, FileSystem, Logger};
-use crate::core::types::Result;
-use std::path::Path;
+use crate::core::types::{Error, Result, ToolDirs};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Note at debug level when `platform` could not express an agent's triggers
+fn log_ignored_triggers<L: Logger>(logger: &L, compiled: &CompiledAgent, platform: Platform) {
+    if !compiled.ignored_triggers.is_empty() {
+        logger.debug(&format!(
+            "Ignoring triggers for {} ({} does not support them): {}",
+            compiled.name,
+            platform,
+            compiled.ignored_triggers.join(", ")
+        ));
+    }
+}
+
+/// Read, parse and validate an agent, returning every problem found with it
+async fn check_agent<F: FileSystem>(
+    fs: &F,
+    agent_md_path: &Path,
+    skills_dir: &Path,
+) -> std::result::Result<ParsedAgent, Vec<ValidationError>> {
+    let content = fs.read(agent_md_path).await.map_err(|e| {
+        vec![ValidationError::file(format!("Failed to read AGENT.md: {}", e))]
+    })?;
+    let (parsed, mut result) = parse_agent_md_collecting(&content, agent_md_path.to_path_buf())
+        .map_err(|e| vec![ValidationError::file(e.to_string())])?;
+
+    result.merge(collect_agent_errors(&parsed.spec, agent_md_path, skills_dir, fs).await);
+    if result.is_valid() {
+        Ok(parsed)
+    } else {
+        Err(result.errors)
+    }
+}
 
 /// Command to list available agents
 pub struct AgentsListCommand;
@@ -38,12 +73,13 @@ impl AgentsListCommand {
                     for agent_info in &agents {
                         // Read and parse agent to get description
                         if let Ok(content) = fs.read(&agent_info.agent_md_path).await {
-                            if let Ok(parsed) =
-                                parse_agent_md(&content, agent_info.agent_md_path.clone())
-                            {
-                                logger.info(&format!("  {} - {}", agent_info.name, parsed.spec.description));
-                            } else {
-                                logger.info(&format!("  {} (parse error)", agent_info.name));
+                            match parse_agent_md(&content, agent_info.agent_md_path.clone()) {
+                                Ok(parsed) => {
+                                    logger.info(&format!("  {} - {}", agent_info.name, parsed.spec.description));
+                                }
+                                Err(e) => {
+                                    logger.warn(&format!("  {} (invalid: {})", agent_info.name, e));
+                                }
                             }
                         } else {
                             logger.info(&format!("  {}", agent_info.name));
@@ -61,11 +97,100 @@ impl AgentsListCommand {
     }
 }
 
-/// Command to set up (compile and install) agents for a platform
+/// Command to validate every agent without compiling or writing anything
+pub struct AgentsValidateCommand {
+    /// `Sarif` prints a SARIF document instead of log lines
+    pub format: ValidationFormat,
+}
+
+impl AgentsValidateCommand {
+    pub async fn execute<F: FileSystem, L: Logger>(
+        &self,
+        fs: &F,
+        logger: &L,
+        project_path: &Path,
+    ) -> Result<()> {
+        let discovery = AgentDiscovery::new(fs, project_path);
+        let skills_dir = discovery.skills_source_dir();
+
+        let agents = discovery.discover_agents().await?;
+
+        if agents.is_empty() {
+            logger.warn("No agents found in .aiassisted/agents/");
+            logger.info("Run 'aiassisted install' to install content first");
+            return Ok(());
+        }
+
+        let sarif = self.format == ValidationFormat::Sarif;
+        if !sarif {
+            logger.info(&format!("Validating {} agent(s)", agents.len()));
+            logger.info("");
+        }
+
+        let mut findings = Vec::new();
+
+        for agent_info in &agents {
+            match check_agent(fs, &agent_info.agent_md_path, &skills_dir).await {
+                Ok(_) if !sarif => logger.info(&format!("  PASS {}", agent_info.name)),
+                Ok(_) => {}
+                Err(errors) => {
+                    if !sarif {
+                        logger.error(&format!("  FAIL {}", agent_info.name));
+                        for error in &errors {
+                            logger.error(&format!("       - {}", error));
+                        }
+                    }
+                    findings.push(AgentFindings {
+                        agent_md_path: agent_info.agent_md_path.clone(),
+                        content: fs.read(&agent_info.agent_md_path).await.ok(),
+                        errors,
+                    });
+                }
+            }
+        }
+
+        if sarif {
+            let report = sarif_report(project_path, &findings);
+            let output = serde_json::to_string_pretty(&report)
+                .map_err(|e| Error::json("SARIF output", &e))?;
+            println!("{}", output);
+        } else {
+            logger.info("");
+        }
+
+        if !findings.is_empty() {
+            return Err(Error::Parse(format!(
+                "{} of {} agent(s) failed validation",
+                findings.len(),
+                agents.len()
+            )));
+        }
+
+        if sarif {
+            return Ok(());
+        }
+        logger.success(&format!("All {} agent(s) are valid", agents.len()));
+        Ok(())
+    }
+}
+
+/// Command to set up (compile and install) agents for one or more platforms
 pub struct AgentsSetupCommand {
-    pub platform: Platform,
+    pub platforms: Vec<Platform>,
     pub dry_run: bool,
     pub force: bool,
+    /// Per-tool output directory overrides from config
+    pub output_dirs: BTreeMap<String, ToolDirs>,
+    /// Only compile the agent with this name
+    pub name: Option<String>,
+}
+
+/// Per-platform outcome of an agents setup run
+#[derive(Debug, Clone, Copy, Default)]
+struct SetupCounts {
+    compiled: usize,
+    skipped: usize,
+    errors: usize,
 }
 
 impl AgentsSetupCommand {
@@ -75,24 +200,69 @@ impl AgentsSetupCommand {
         logger: &L,
         project_path: &Path,
     ) -> Result<()> {
-        let discovery = AgentDiscovery::new(fs, project_path);
+        let discovery =
+            AgentDiscovery::new(fs, project_path).with_output_dirs(self.output_dirs.clone());
+
+        let mut results = Vec::new();
+        for (index, platform) in self.platforms.iter().enumerate() {
+            logger.checkpoint()?;
+            if index > 0 {
+                logger.info("");
+            }
+            let counts = self.setup_platform(fs, logger, &discovery, *platform).await?;
+            results.push((*platform, counts));
+        }
+
+        // Aggregated summary across platforms
+        if results.len() > 1 {
+            logger.info("");
+            logger.info("Summary by platform:");
+            for (platform, counts) in &results {
+                logger.info(&format!(
+                    "  {}: {} compiled, {} skipped, {} errors",
+                    platform, counts.compiled, counts.skipped, counts.errors
+                ));
+            }
+        }
 
-        logger.info(&format!("Setting up agents for {}", self.platform));
+        Ok(())
+    }
+
+    /// Compile and install agents for a single platform
+    async fn setup_platform<F: FileSystem, L: Logger>(
+        &self,
+        fs: &F,
+        logger: &L,
+        discovery: &AgentDiscovery<'_, F>,
+        platform: Platform,
+    ) -> Result<SetupCounts> {
+        logger.info(&format!("Setting up agents for {}", platform));
 
         let source_dir = discovery.agents_source_dir();
         let skills_dir = discovery.skills_source_dir();
-        let target_dir = discovery.agents_target_dir(self.platform);
+        let target_dir = discovery.agents_target_dir(platform);
 
         logger.info(&format!("Source: {}", source_dir.display()));
         logger.info(&format!("Target: {}", target_dir.display()));
 
         // Discover agents
-        let agents = discovery.discover_agents().await?;
+        let mut agents = discovery.discover_agents().await?;
+
+        if let Some(name) = &self.name {
+            agents.retain(|agent| &agent.name == name);
+            if agents.is_empty() {
+                return Err(Error::NotFound(format!(
+                    "No agent named '{}' in {}",
+                    name,
+                    source_dir.display()
+                )));
+            }
+        }
 
         if agents.is_empty() {
             logger.warn("No agents found in .aiassisted/agents/");
             logger.info("Run 'aiassisted install' to install content first");
-            return Ok(());
+            return Ok(SetupCounts::default());
         }
 
         logger.info(&format!("Found {} agent(s)", agents.len()));
@@ -103,39 +273,31 @@ impl AgentsSetupCommand {
         }
 
         // Process each agent
-        let mut compiled_count = 0;
-        let mut skipped_count = 0;
-        let mut error_count = 0;
+        let mut counts = SetupCounts::default();
 
         for agent_info in &agents {
-            // Read and parse agent
-            let content = match fs.read(&agent_info.agent_md_path).await {
-                Ok(c) => c,
-                Err(e) => {
-                    logger.error(&format!("Failed to read {}: {}", agent_info.name, e));
-                    error_count += 1;
-                    continue;
-                }
-            };
+            logger.checkpoint()?;
 
-            let parsed = match parse_agent_md(&content, agent_info.agent_md_path.clone()) {
-                Ok(p) => p,
-                Err(e) => {
-                    logger.error(&format!("Failed to parse {}: {}", agent_info.name, e));
-                    error_count += 1;
+            // Read, parse and validate agent
+            let parsed = match check_agent(fs, &agent_info.agent_md_path, &skills_dir).await {
+                Ok(parsed) => parsed,
+                Err(errors) => {
+                    logger.error(&format!(
+                        "Validation failed for {} ({} problem(s)):",
+                        agent_info.name,
+                        errors.len()
+                    ));
+                    for error in &errors {
+                        logger.error(&format!("  - {}", error));
+                    }
+                    counts.errors += 1;
                     continue;
                 }
             };
 
-            // Validate agent
-            if let Err(e) = validate_agent(&parsed.spec, &agent_info.agent_md_path, &skills_dir, fs).await {
-                logger.error(&format!("Validation failed for {}: {}", agent_info.name, e));
-                error_count += 1;
-                continue;
-            }
-
             // Compile agent
-            let compiled = compile_agent(&parsed, self.platform);
+            let compiled = compile_agent(&parsed, platform);
+            log_ignored_triggers(logger, &compiled, platform);
 
             // Check if already exists
             let agent_target_file = target_dir.join(&compiled.filename);
@@ -145,7 +307,7 @@ impl AgentsSetupCommand {
                 } else {
                     logger.warn(&format!("Skipped (exists): {}", agent_info.name));
                 }
-                skipped_count += 1;
+                counts.skipped += 1;
                 continue;
             }
 
@@ -155,13 +317,13 @@ impl AgentsSetupCommand {
                     agent_info.name,
                     agent_target_file.display()
                 ));
-                compiled_count += 1;
+                counts.compiled += 1;
             } else {
                 // Write single markdown file
                 fs.write(&agent_target_file, &compiled.content).await?;
 
                 logger.success(&format!("Compiled: {}", agent_info.name));
-                compiled_count += 1;
+                counts.compiled += 1;
             }
         }
 
@@ -170,20 +332,20 @@ impl AgentsSetupCommand {
         if self.dry_run {
             logger.info(&format!(
                 "Dry run: {} agent(s) would be compiled, {} skipped, {} errors",
-                compiled_count, skipped_count, error_count
+                counts.compiled, counts.skipped, counts.errors
             ));
         } else {
             logger.success(&format!(
                 "Setup complete: {} compiled, {} skipped, {} errors",
-                compiled_count, skipped_count, error_count
+                counts.compiled, counts.skipped, counts.errors
             ));
 
-            if skipped_count > 0 {
+            if counts.skipped > 0 {
                 logger.info("Use --force to overwrite existing agents");
             }
         }
 
-        Ok(())
+        Ok(counts)
     }
 }
 
@@ -192,6 +354,12 @@ pub struct AgentsUpdateCommand {
     pub platform: Platform,
     pub dry_run: bool,
     pub force: bool,
+    /// Only update the agent with this name
+    pub name: Option<String>,
+    /// Re-hash written agents afterwards and fail if any differ from the compiled source
+    pub verify: bool,
+    /// Per-tool output directory overrides from config
+    pub output_dirs: BTreeMap<String, ToolDirs>,
 }
 
 impl AgentsUpdateCommand {
@@ -202,7 +370,8 @@ impl AgentsUpdateCommand {
         logger: &L,
         project_path: &Path,
     ) -> Result<()> {
-        let discovery = AgentDiscovery::new(fs, project_path);
+        let discovery =
+            AgentDiscovery::new(fs, project_path).with_output_dirs(self.output_dirs.clone());
         let differ = AgentDiffer::new(fs, checksum);
 
         let source_dir = discovery.agents_source_dir();
@@ -232,7 +401,18 @@ impl AgentsUpdateCommand {
         logger.info("Analyzing agents...");
 
         // Compute diff
-        let diff = differ.compute_diff(&source_dir, &target_dir, self.platform).await?;
+        let mut diff = differ.compute_diff(&source_dir, &target_dir, self.platform).await?;
+
+        if let Some(name) = &self.name {
+            diff.agents.retain(|agent| &agent.name == name);
+            if diff.agents.is_empty() {
+                return Err(Error::NotFound(format!(
+                    "No agent named '{}' in {}",
+                    name,
+                    source_dir.display()
+                )));
+            }
+        }
 
         // Summary
         logger.info(&format!(
@@ -309,9 +489,11 @@ impl AgentsUpdateCommand {
 
             let mut updated = 0;
             for agent in &agents_to_update {
+                logger.checkpoint()?;
                 if let Some(source_path) = &agent.source_path {
                     // Compile and write
                     let compiled = differ.compile_from_source(source_path, self.platform).await?;
+                    log_ignored_triggers(logger, &compiled, self.platform);
 
                     // Write single markdown file
                     fs.write(&agent.target_path, &compiled.content).await?;
@@ -322,6 +504,37 @@ impl AgentsUpdateCommand {
 
             logger.success(&format!("Updated {} agent(s)", updated));
 
+            if self.verify {
+                let mut mismatches = Vec::new();
+                for agent in &agents_to_update {
+                    let Some(source_path) = &agent.source_path else {
+                        continue;
+                    };
+                    let result = differ
+                        .verify_agent(source_path, &agent.target_path, self.platform)
+                        .await?;
+                    if let Some(mismatch) = result {
+                        logger.error(&format!(
+                            "  ! {} (expected {}, actual {})",
+                            agent.target_path.display(),
+                            mismatch.0,
+                            mismatch.1
+                        ));
+                        mismatches.push(mismatch);
+                    }
+                }
+
+                if let Some((expected, actual)) = mismatches.first().cloned() {
+                    logger.error(&format!(
+                        "{} of {} agent(s) failed verification after update",
+                        mismatches.len(),
+                        updated
+                    ));
+                    return Err(Error::ChecksumMismatch { expected, actual });
+                }
+                logger.success(&format!("Verified {} agent(s)", updated));
+            }
+
             // Note about removed agents
             if diff.removed_agents_count() > 0 {
                 logger.info(&format!(
@@ -334,3 +547,539 @@ impl AgentsUpdateCommand {
         Ok(())
     }
 }
+
+/// Command to remove compiled agents from a platform's target directory
+pub struct AgentsRemoveCommand {
+    pub platform: Platform,
+    /// Agent to remove (ignored with `all`)
+    pub name: Option<String>,
+    /// Remove every agent compiled from `.aiassisted/agents/`
+    pub all: bool,
+    pub dry_run: bool,
+    /// Remove without asking (`--yes`)
+    pub yes: bool,
+    /// Per-tool output directory overrides from config
+    pub output_dirs: BTreeMap<String, ToolDirs>,
+}
+
+impl AgentsRemoveCommand {
+    pub async fn execute<F: FileSystem, L: Logger>(
+        &self,
+        fs: &F,
+        logger: &L,
+        project_path: &Path,
+    ) -> Result<()> {
+        let discovery =
+            AgentDiscovery::new(fs, project_path).with_output_dirs(self.output_dirs.clone());
+        let target_dir = discovery.agents_target_dir(self.platform);
+
+        logger.info(&format!("Removing agents for {}", self.platform));
+        logger.info(&format!("Target: {}", target_dir.display()));
+
+        let files = self.files_to_remove(fs, &discovery, &target_dir).await?;
+
+        if files.is_empty() {
+            logger.info("No compiled agents installed");
+            return Ok(());
+        }
+
+        let question = format!("Remove {} agent(s) from {}?", files.len(), target_dir.display());
+        if !self.dry_run && !confirm(&question, self.yes)? {
+            logger.info("Remove cancelled");
+            return Ok(());
+        }
+
+        for file in &files {
+            // Never delete anything outside the target directory
+            if file.parent() != Some(target_dir.as_path()) || !fs.is_file(file) {
+                return Err(Error::Config(format!(
+                    "Refusing to remove {}: not a compiled agent in {}",
+                    file.display(),
+                    target_dir.display()
+                )));
+            }
+
+            if self.dry_run {
+                logger.info(&format!("Would remove: {}", file.display()));
+            } else {
+                fs.remove_file(file).await?;
+                logger.success(&format!("Removed: {}", file.display()));
+            }
+        }
+
+        logger.info("");
+        if self.dry_run {
+            logger.info(&format!("Dry run: {} agent(s) would be removed", files.len()));
+        } else {
+            logger.success(&format!("Removed {} agent(s)", files.len()));
+        }
+
+        Ok(())
+    }
+
+    /// Compiled agent files selected by `name` or `all`
+    ///
+    /// `all` only covers agents with a source in `.aiassisted/agents/`, the
+    /// ones `agents setup` writes, so agents the user wrote by hand survive.
+    async fn files_to_remove<F: FileSystem>(
+        &self,
+        fs: &F,
+        discovery: &AgentDiscovery<'_, F>,
+        target_dir: &Path,
+    ) -> Result<Vec<PathBuf>> {
+        if self.all {
+            let mut files = Vec::new();
+            for agent in discovery.discover_agents().await? {
+                let file = target_dir.join(agent_filename(&agent.name));
+                if fs.is_file(&file) {
+                    files.push(file);
+                }
+            }
+            return Ok(files);
+        }
+
+        let name = self.name.as_deref().ok_or_else(|| {
+            Error::Config("Specify an agent name or use --all".to_string())
+        })?;
+
+        // A valid name cannot contain path separators or `..`
+        let validation = validate_name(name);
+        if let Some(error) = validation.errors.first() {
+            return Err(Error::Parse(format!(
+                "Invalid agent name '{}': {}",
+                name, error.message
+            )));
+        }
+
+        let file = target_dir.join(agent_filename(name));
+        if !fs.exists(&file) {
+            return Err(Error::NotFound(format!(
+                "Agent '{}' is not installed in {}",
+                name,
+                target_dir.display()
+            )));
+        }
+
+        Ok(vec![file])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::{Sha2Checksum, StdFileSystem};
+    use tempfile::TempDir;
+
+    struct NullLogger;
+
+    impl Logger for NullLogger {
+        fn info(&self, _msg: &str) {}
+        fn warn(&self, _msg: &str) {}
+        fn error(&self, _msg: &str) {}
+        fn debug(&self, _msg: &str) {}
+        fn success(&self, _msg: &str) {}
+    }
+
+    /// Logger that keeps error messages for assertions
+    #[derive(Default)]
+    struct RecordingLogger {
+        errors: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl Logger for RecordingLogger {
+        fn info(&self, _msg: &str) {}
+        fn warn(&self, _msg: &str) {}
+        fn error(&self, msg: &str) {
+            self.errors.lock().unwrap().push(msg.to_string());
+        }
+        fn debug(&self, _msg: &str) {}
+        fn success(&self, _msg: &str) {}
+    }
+
+    async fn write_agent<F: FileSystem>(fs: &F, project: &Path, name: &str) {
+        let content = format!(
+            "---\nname: {}\ndescription: Test agent\n---\n\nYou are {}.\n",
+            name, name
+        );
+        fs.write(
+            &project.join(".aiassisted/agents").join(name).join("AGENT.md"),
+            &content,
+        )
+        .await
+        .unwrap();
+    }
+
+    fn setup_command(name: Option<&str>) -> AgentsSetupCommand {
+        AgentsSetupCommand {
+            platforms: vec![Platform::ClaudeCode],
+            dry_run: false,
+            force: false,
+            output_dirs: BTreeMap::new(),
+            name: name.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_setup_with_name_compiles_only_that_agent() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        write_agent(&fs, temp_dir.path(), "commit-helper").await;
+        write_agent(&fs, temp_dir.path(), "reviewer").await;
+
+        setup_command(Some("commit-helper"))
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await
+            .unwrap();
+
+        let target_dir =
+            AgentDiscovery::new(&fs, temp_dir.path()).agents_target_dir(Platform::ClaudeCode);
+        let installed = fs.list_dir(&target_dir).await.unwrap();
+        assert_eq!(installed.len(), 1);
+        assert!(installed[0].ends_with("commit-helper.md"));
+    }
+
+    #[tokio::test]
+    async fn test_setup_all_platforms_writes_each_agents_dir() {
+        let fs = crate::infra::MemFileSystem::new();
+        let project = Path::new("/project");
+        write_agent(&fs, project, "reviewer").await;
+
+        let setup = |dry_run| AgentsSetupCommand {
+            platforms: Platform::ALL.to_vec(),
+            dry_run,
+            force: false,
+            output_dirs: BTreeMap::new(),
+            name: None,
+        };
+        let targets = [".claude/agents/reviewer.md", ".opencode/agents/reviewer.md"];
+
+        setup(true).execute(&fs, &NullLogger, project).await.unwrap();
+        for target in targets {
+            assert!(!fs.exists(&project.join(target)), "dry run wrote {}", target);
+        }
+        assert!(!fs.exists(&project.join(".claude")));
+        assert!(!fs.exists(&project.join(".opencode")));
+
+        setup(false).execute(&fs, &NullLogger, project).await.unwrap();
+        for target in targets {
+            let compiled = fs.read(&project.join(target)).await.unwrap();
+            assert!(compiled.contains("You are reviewer."), "{}: {}", target, compiled);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_setup_with_unknown_name_fails() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        write_agent(&fs, temp_dir.path(), "reviewer").await;
+
+        let result = setup_command(Some("missing"))
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await;
+
+        assert!(matches!(result, Err(Error::NotFound(msg)) if msg.contains("missing")));
+    }
+
+    #[tokio::test]
+    async fn test_setup_reports_unknown_model_tier_and_continues() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        write_agent(&fs, temp_dir.path(), "reviewer").await;
+        fs.write(
+            &temp_dir.path().join(".aiassisted/agents/planner/AGENT.md"),
+            "---\nname: planner\ndescription: Plans work\nmodel-tier: turbo\n---\n\nYou plan.\n",
+        )
+        .await
+        .unwrap();
+
+        let logger = RecordingLogger::default();
+        setup_command(None)
+            .execute(&fs, &logger, temp_dir.path())
+            .await
+            .unwrap();
+
+        let target_dir =
+            AgentDiscovery::new(&fs, temp_dir.path()).agents_target_dir(Platform::ClaudeCode);
+        assert!(fs.exists(&target_dir.join("reviewer.md")));
+        assert!(!fs.exists(&target_dir.join("planner.md")));
+
+        let errors = logger.errors.lock().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("planner"));
+        assert!(errors[1].contains("Unknown model tier 'turbo' (allowed: fast, balanced, capable)"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_reports_every_problem_at_once() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        fs.write(
+            &temp_dir.path().join(".aiassisted/agents/planner/AGENT.md"),
+            "---\nname: Planner\ndescription: Plans\nmodel-tier: turbo\n\
+             skills: [missing-skill]\ntriggers: ['']\n---\n\nYou plan.\n",
+        )
+        .await
+        .unwrap();
+
+        let logger = RecordingLogger::default();
+        let result = AgentsValidateCommand { format: ValidationFormat::Text }
+            .execute(&fs, &logger, temp_dir.path())
+            .await;
+        assert!(matches!(result, Err(Error::Parse(_))));
+
+        let errors = logger.errors.lock().unwrap();
+        assert!(errors[0].contains("FAIL planner"));
+        for expected in [
+            "model-tier: Unknown model tier 'turbo'",
+            "triggers: Trigger patterns must be non-empty",
+            "name: Name must contain only lowercase",
+            "does not match directory name 'planner'",
+            "description: Description is 5 characters",
+            "Referenced skill 'missing-skill' not found",
+        ] {
+            assert!(errors.iter().any(|e| e.contains(expected)), "{}: {:?}", expected, errors);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sarif_has_one_result_per_violation() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let agent_md = temp_dir.path().join(".aiassisted/agents/planner/AGENT.md");
+        let content = "---\nname: planner\ndescription: Plans\nmodel-tier: turbo\n\
+                       skills: [missing-skill]\n---\n\nYou plan.\n";
+        fs.write(&agent_md, content).await.unwrap();
+
+        let skills_dir = AgentDiscovery::new(&fs, temp_dir.path()).skills_source_dir();
+        let errors = check_agent(&fs, &agent_md, &skills_dir).await.unwrap_err();
+        assert_eq!(errors.len(), 3);
+
+        let findings = [AgentFindings {
+            agent_md_path: agent_md.clone(),
+            content: Some(content.to_string()),
+            errors,
+        }];
+        let output = serde_json::to_string(&sarif_report(temp_dir.path(), &findings)).unwrap();
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        let line = |rule: &str| {
+            let result = results.iter().find(|r| r["ruleId"] == rule).unwrap();
+            let location = &result["locations"][0]["physicalLocation"];
+            assert_eq!(location["artifactLocation"]["uri"], ".aiassisted/agents/planner/AGENT.md");
+            location["region"]["startLine"].as_u64()
+        };
+        assert_eq!(line("agent/model-tier"), Some(4));
+        assert_eq!(line("agent/description"), Some(3));
+        assert_eq!(line("agent/skills"), Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_update_with_name_filters_agents() {
+        let fs = StdFileSystem::new();
+        let checksum = Sha2Checksum::new();
+        let temp_dir = TempDir::new().unwrap();
+        write_agent(&fs, temp_dir.path(), "commit-helper").await;
+        setup_command(None)
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await
+            .unwrap();
+        write_agent(&fs, temp_dir.path(), "reviewer").await;
+
+        let update = |name: &str| AgentsUpdateCommand {
+            platform: Platform::ClaudeCode,
+            dry_run: false,
+            force: false,
+            name: Some(name.to_string()),
+            verify: false,
+            output_dirs: BTreeMap::new(),
+        };
+
+        // The unchanged agent is selected, so the new one is not installed
+        update("commit-helper")
+            .execute(&fs, &checksum, &NullLogger, temp_dir.path())
+            .await
+            .unwrap();
+        let target_dir =
+            AgentDiscovery::new(&fs, temp_dir.path()).agents_target_dir(Platform::ClaudeCode);
+        assert!(!fs.exists(&target_dir.join("reviewer.md")));
+
+        let result = update("missing")
+            .execute(&fs, &checksum, &NullLogger, temp_dir.path())
+            .await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_uses_output_dir_override() {
+        let fs = StdFileSystem::new();
+        let checksum = Sha2Checksum::new();
+        let temp_dir = TempDir::new().unwrap();
+        let mut output_dirs = BTreeMap::new();
+        output_dirs.insert(
+            "claude".to_string(),
+            ToolDirs {
+                agents: Some(PathBuf::from("custom/agents")),
+                ..Default::default()
+            },
+        );
+        write_agent(&fs, temp_dir.path(), "commit-helper").await;
+        AgentsSetupCommand {
+            output_dirs: output_dirs.clone(),
+            ..setup_command(None)
+        }
+        .execute(&fs, &NullLogger, temp_dir.path())
+        .await
+        .unwrap();
+        write_agent(&fs, temp_dir.path(), "reviewer").await;
+
+        AgentsUpdateCommand {
+            platform: Platform::ClaudeCode,
+            dry_run: false,
+            force: false,
+            name: None,
+            verify: true,
+            output_dirs,
+        }
+        .execute(&fs, &checksum, &NullLogger, temp_dir.path())
+        .await
+        .unwrap();
+
+        let target_dir = temp_dir.path().join("custom/agents");
+        assert!(fs.exists(&target_dir.join("commit-helper.md")));
+        assert!(fs.exists(&target_dir.join("reviewer.md")));
+        assert!(!fs.exists(&temp_dir.path().join(".claude")));
+    }
+
+    #[tokio::test]
+    async fn test_update_with_verify_checks_written_agents() {
+        let fs = StdFileSystem::new();
+        let checksum = Sha2Checksum::new();
+        let temp_dir = TempDir::new().unwrap();
+        write_agent(&fs, temp_dir.path(), "commit-helper").await;
+        setup_command(None)
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await
+            .unwrap();
+        write_agent(&fs, temp_dir.path(), "reviewer").await;
+
+        let logger = RecordingLogger::default();
+        AgentsUpdateCommand {
+            platform: Platform::ClaudeCode,
+            dry_run: false,
+            force: false,
+            name: None,
+            verify: true,
+            output_dirs: BTreeMap::new(),
+        }
+        .execute(&fs, &checksum, &logger, temp_dir.path())
+        .await
+        .unwrap();
+        assert!(logger.errors.lock().unwrap().is_empty());
+
+        // A target that no longer matches its source is reported
+        let discovery = AgentDiscovery::new(&fs, temp_dir.path());
+        let source_path = discovery.agents_source_dir().join("reviewer");
+        let target_path = discovery.agents_target_dir(Platform::ClaudeCode).join("reviewer.md");
+        let differ = AgentDiffer::new(&fs, &checksum);
+        assert_eq!(
+            differ.verify_agent(&source_path, &target_path, Platform::ClaudeCode).await.unwrap(),
+            None
+        );
+        fs.write(&target_path, "truncated").await.unwrap();
+        let (_, actual) = differ
+            .verify_agent(&source_path, &target_path, Platform::ClaudeCode)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(actual, checksum.sha256(b"truncated"));
+    }
+
+    fn remove_command(name: Option<&str>, all: bool) -> AgentsRemoveCommand {
+        AgentsRemoveCommand {
+            platform: Platform::ClaudeCode,
+            name: name.map(str::to_string),
+            all,
+            dry_run: false,
+            yes: true,
+            output_dirs: BTreeMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_single_agent() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        write_agent(&fs, temp_dir.path(), "commit-helper").await;
+        write_agent(&fs, temp_dir.path(), "reviewer").await;
+        setup_command(None)
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await
+            .unwrap();
+        let target_dir =
+            AgentDiscovery::new(&fs, temp_dir.path()).agents_target_dir(Platform::ClaudeCode);
+
+        let dry_run = AgentsRemoveCommand {
+            dry_run: true,
+            ..remove_command(Some("reviewer"), false)
+        };
+        dry_run.execute(&fs, &NullLogger, temp_dir.path()).await.unwrap();
+        assert!(fs.exists(&target_dir.join("reviewer.md")));
+
+        remove_command(Some("reviewer"), false)
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await
+            .unwrap();
+        assert!(!fs.exists(&target_dir.join("reviewer.md")));
+        assert!(fs.exists(&target_dir.join("commit-helper.md")));
+
+        let result = remove_command(Some("reviewer"), false)
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_remove_all_keeps_other_files() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        write_agent(&fs, temp_dir.path(), "commit-helper").await;
+        write_agent(&fs, temp_dir.path(), "reviewer").await;
+        setup_command(None)
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await
+            .unwrap();
+        let target_dir =
+            AgentDiscovery::new(&fs, temp_dir.path()).agents_target_dir(Platform::ClaudeCode);
+        fs.write(&target_dir.join("notes.txt"), "keep me").await.unwrap();
+        // Written by hand, with no source in .aiassisted/agents/
+        fs.write(&target_dir.join("my-own.md"), "---\nname: my-own\n---\n").await.unwrap();
+
+        remove_command(None, true)
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await
+            .unwrap();
+
+        let mut remaining = fs.list_dir(&target_dir).await.unwrap();
+        remaining.sort();
+        assert_eq!(remaining, vec![target_dir.join("my-own.md"), target_dir.join("notes.txt")]);
+    }
+
+    #[tokio::test]
+    async fn test_remove_rejects_path_traversal() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        fs.write(&temp_dir.path().join(".claude/secret.md"), "keep me")
+            .await
+            .unwrap();
+
+        let result = remove_command(Some("../secret"), false)
+            .execute(&fs, &NullLogger, temp_dir.path())
+            .await;
+
+        assert!(matches!(result, Err(Error::Parse(_))));
+        assert!(fs.exists(&temp_dir.path().join(".claude/secret.md")));
+    }
+}
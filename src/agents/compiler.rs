@@ -1,6 +1,7 @@
 //! Agent compilation to platform-specific formats
 
 use crate::agents::parser::{Capabilities, ModelTier, ParsedAgent};
+use crate::core::types::ToolType;
 
 /// Target platform for agent compilation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +12,42 @@ pub enum Platform {
     OpenCode,
 }
 
+impl Platform {
+    /// All concrete platforms agents can be compiled for
+    pub const ALL: [Platform; 2] = [Platform::ClaudeCode, Platform::OpenCode];
+
+    /// The tool this platform targets
+    pub fn tool_type(&self) -> ToolType {
+        match self {
+            Platform::ClaudeCode => ToolType::Claude,
+            Platform::OpenCode => ToolType::OpenCode,
+        }
+    }
+
+    /// Whether the platform can activate agents on file patterns
+    ///
+    /// Neither Claude Code nor OpenCode has such a frontmatter field yet, so
+    /// `triggers` are dropped for both.
+    pub fn supports_triggers(&self) -> bool {
+        match self {
+            Platform::ClaudeCode => false,
+            Platform::OpenCode => false,
+        }
+    }
+
+    /// Where the platform looks for project agents, relative to the project root
+    ///
+    /// No catch-all arm: a new platform must declare its directory here.
+    pub fn agents_dir(&self) -> &'static str {
+        match self {
+            // Claude Code loads project subagents from .claude/agents/*.md
+            Platform::ClaudeCode => ".claude/agents",
+            // OpenCode loads project agents from .opencode/agents/*.md
+            Platform::OpenCode => ".opencode/agents",
+        }
+    }
+}
+
 impl std::fmt::Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -29,15 +66,40 @@ pub struct CompiledAgent {
     pub content: String,
     /// Output filename (e.g., "code-reviewer.md")
     pub filename: String,
+    /// Triggers left out because the platform cannot express them
+    pub ignored_triggers: Vec<String>,
+}
+
+/// Tools withheld from an agent for a given capability set.
+///
+/// Each platform renders the same list in its own syntax:
+///
+/// | Capabilities | Claude Code | OpenCode |
+/// |--------------|-------------|----------|
+/// | `read-only`  | `disallowedTools: Write, Edit` | `tools:` with `write: false`, `edit: false` |
+/// | `read-write` | omitted (all tools) | omitted (all tools) |
+pub fn denied_tools(capabilities: Capabilities) -> &'static [&'static str] {
+    match capabilities {
+        Capabilities::ReadOnly => &["Write", "Edit"],
+        Capabilities::ReadWrite => &[],
+    }
 }
 
+/// Filename of a compiled agent (e.g., "code-reviewer.md"), the same on every platform
+pub fn agent_filename(name: &str) -> String {
+    format!("{}.md", name)
+}
 
 /// Compile an agent to a platform-specific format
 pub fn compile_agent(agent: &ParsedAgent, platform: Platform) -> CompiledAgent {
-    match platform {
+    let mut compiled = match platform {
         Platform::ClaudeCode => compile_for_claude_code(agent),
         Platform::OpenCode => compile_for_opencode(agent),
+    };
+    if !platform.supports_triggers() {
+        compiled.ignored_triggers = agent.spec.triggers.clone();
     }
+    compiled
 }
 
 /// Compile agent for Claude Code format
@@ -63,11 +125,9 @@ fn compile_for_claude_code(agent: &ParsedAgent) -> CompiledAgent {
     frontmatter_lines.push(format!("description: {}", agent.spec.description));
 
     // Tool restrictions (before model per guideline example)
-    match agent.spec.capabilities {
-        Capabilities::ReadOnly => {
-            frontmatter_lines.push("disallowedTools: Write, Edit".to_string());
-        }
-        Capabilities::ReadWrite => {} // Default, omit
+    let denied = denied_tools(agent.spec.capabilities);
+    if !denied.is_empty() {
+        frontmatter_lines.push(format!("disallowedTools: {}", denied.join(", ")));
     }
 
     // Model mapping per agent-spec.guideline.md
@@ -92,7 +152,8 @@ fn compile_for_claude_code(agent: &ParsedAgent) -> CompiledAgent {
     CompiledAgent {
         name: agent.spec.name.clone(),
         content,
-        filename: format!("{}.md", agent.spec.name),
+        filename: agent_filename(&agent.spec.name),
+        ignored_triggers: Vec::new(),
     }
 }
 
@@ -126,14 +187,13 @@ fn compile_for_opencode(agent: &ParsedAgent) -> CompiledAgent {
     };
     frontmatter_lines.push(format!("model: {}", model));
 
-    // Tool restrictions (nested YAML for read-only)
-    match agent.spec.capabilities {
-        Capabilities::ReadOnly => {
-            frontmatter_lines.push("tools:".to_string());
-            frontmatter_lines.push("  write: false".to_string());
-            frontmatter_lines.push("  edit: false".to_string());
+    // Tool restrictions (nested YAML, lowercase tool names)
+    let denied = denied_tools(agent.spec.capabilities);
+    if !denied.is_empty() {
+        frontmatter_lines.push("tools:".to_string());
+        for tool in denied {
+            frontmatter_lines.push(format!("  {}: false", tool.to_lowercase()));
         }
-        Capabilities::ReadWrite => {} // Default, omit
     }
 
     let frontmatter = frontmatter_lines.join("\n");
@@ -142,7 +202,8 @@ fn compile_for_opencode(agent: &ParsedAgent) -> CompiledAgent {
     CompiledAgent {
         name: agent.spec.name.clone(),
         content,
-        filename: format!("{}.md", agent.spec.name),
+        filename: agent_filename(&agent.spec.name),
+        ignored_triggers: Vec::new(),
     }
 }
 
@@ -160,6 +221,7 @@ mod tests {
                 capabilities,
                 model_tier,
                 skills,
+                triggers: vec![],
             },
             system_prompt: "You are a test agent.".to_string(),
             source_path: PathBuf::from("/test/AGENT.md"),
@@ -258,6 +320,22 @@ mod tests {
         assert!(!compiled.content.contains("some-skill"));
     }
 
+    #[test]
+    fn test_triggers_dropped_on_unsupported_platforms() {
+        let plain = make_test_agent(Capabilities::ReadWrite, ModelTier::Balanced, vec![]);
+        let mut triggered = plain.clone();
+        triggered.spec.triggers = vec!["**/*.rs".to_string()];
+
+        for platform in Platform::ALL {
+            assert!(!platform.supports_triggers());
+            let compiled = compile_agent(&triggered, platform);
+            // Output is byte-for-byte what an agent without triggers compiles to
+            assert_eq!(compiled.content, compile_agent(&plain, platform).content);
+            assert_eq!(compiled.ignored_triggers, vec!["**/*.rs"]);
+            assert!(compile_agent(&plain, platform).ignored_triggers.is_empty());
+        }
+    }
+
     #[test]
     fn test_prompt_content_preserved() {
         let agent = ParsedAgent {
@@ -267,6 +345,7 @@ mod tests {
                 capabilities: Capabilities::ReadWrite,
                 model_tier: ModelTier::Balanced,
                 skills: vec![],
+                triggers: vec![],
             },
             system_prompt: "Custom system prompt\n\nWith multiple lines.".to_string(),
             source_path: PathBuf::from("/test/AGENT.md"),
@@ -279,6 +358,36 @@ mod tests {
         assert!(opencode_compiled.content.contains("Custom system prompt\n\nWith multiple lines."));
     }
 
+    #[test]
+    fn test_denied_tools() {
+        assert_eq!(denied_tools(Capabilities::ReadOnly), &["Write", "Edit"]);
+        assert!(denied_tools(Capabilities::ReadWrite).is_empty());
+    }
+
+    #[test]
+    fn test_read_only_restricted_on_every_platform() {
+        let agent = make_test_agent(Capabilities::ReadOnly, ModelTier::Balanced, vec![]);
+
+        for platform in Platform::ALL {
+            let compiled = compile_agent(&agent, platform);
+            let restricted = match platform {
+                Platform::ClaudeCode => compiled.content.contains("disallowedTools: Write, Edit"),
+                Platform::OpenCode => {
+                    compiled.content.contains("  write: false")
+                        && compiled.content.contains("  edit: false")
+                }
+            };
+            assert!(restricted, "{} output is not restricted", platform);
+        }
+    }
+
+    #[test]
+    fn test_platform_all() {
+        assert_eq!(Platform::ALL.len(), 2);
+        assert!(Platform::ALL.contains(&Platform::ClaudeCode));
+        assert!(Platform::ALL.contains(&Platform::OpenCode));
+    }
+
     #[test]
     fn test_platform_display() {
         assert_eq!(Platform::ClaudeCode.to_string(), "claude-code");
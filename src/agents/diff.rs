@@ -244,6 +244,25 @@ impl<'a, F: FileSystem, C: Checksum> AgentDiffer<'a, F, C> {
         }
     }
 
+    /// Check that an installed agent matches what its source compiles to
+    /// Returns the expected and actual checksums if they differ ("missing" if not written)
+    pub async fn verify_agent(
+        &self,
+        source_path: &Path,
+        target_path: &Path,
+        platform: Platform,
+    ) -> Result<Option<(String, String)>> {
+        let compiled = self.compile_from_source(source_path, platform).await?;
+        let expected = self.checksum.sha256(compiled.content.as_bytes());
+        let actual = if self.fs.exists(target_path) {
+            self.checksum.sha256_file(target_path)?
+        } else {
+            "missing".to_string()
+        };
+
+        Ok((expected != actual).then_some((expected, actual)))
+    }
+
     /// Compile an agent from source
     pub async fn compile_from_source(
         &self,
@@ -2,7 +2,8 @@
 
 use crate::agents::compiler::Platform;
 use crate::core::infra::FileSystem;
-use crate::core::types::Result;
+use crate::core::types::{Result, ToolDirs};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// Information about a discovered agent
@@ -20,6 +21,7 @@ pub struct AgentInfo {
 pub struct AgentDiscovery<'a, F: FileSystem> {
     fs: &'a F,
     project_path: PathBuf,
+    output_dirs: BTreeMap<String, ToolDirs>,
 }
 
 impl<'a, F: FileSystem> AgentDiscovery<'a, F> {
@@ -27,9 +29,16 @@ impl<'a, F: FileSystem> AgentDiscovery<'a, F> {
         Self {
             fs,
             project_path: project_path.to_path_buf(),
+            output_dirs: BTreeMap::new(),
         }
     }
 
+    /// Use per-tool output directory overrides from config
+    pub fn with_output_dirs(mut self, output_dirs: BTreeMap<String, ToolDirs>) -> Self {
+        self.output_dirs = output_dirs;
+        self
+    }
+
     /// Get the agents source directory
     pub fn agents_source_dir(&self) -> PathBuf {
         self.project_path.join(".aiassisted/agents")
@@ -41,11 +50,18 @@ impl<'a, F: FileSystem> AgentDiscovery<'a, F> {
     }
 
     /// Get the target directory for compiled agents
+    ///
+    /// A config override wins; otherwise [`Platform::agents_dir`] decides.
     pub fn agents_target_dir(&self, platform: Platform) -> PathBuf {
-        match platform {
-            Platform::ClaudeCode => self.project_path.join(".claude/agents"),
-            Platform::OpenCode => self.project_path.join(".opencode/agents"),
+        let override_dir = self
+            .output_dirs
+            .get(&platform.tool_type().to_string())
+            .and_then(|dirs| dirs.agents.as_ref());
+        if let Some(dir) = override_dir {
+            return self.project_path.join(dir);
         }
+
+        self.project_path.join(platform.agents_dir())
     }
 
     /// Discover all agents in the source directory
@@ -181,6 +197,18 @@ mod tests {
         async fn copy(&self, _from: &Path, _to: &Path) -> Result<()> {
             Ok(())
         }
+
+        async fn remove_file(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+
+        async fn remove_dir_all(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+
+        async fn rename(&self, _from: &Path, _to: &Path) -> Result<()> {
+            Ok(())
+        }
     }
 
     #[test]
@@ -210,11 +238,40 @@ mod tests {
         let fs = MockFs::new();
         let discovery = AgentDiscovery::new(&fs, Path::new("/project"));
 
+        for platform in Platform::ALL {
+            // Exhaustive so a new platform needs an expected path here too
+            let expected = match platform {
+                Platform::ClaudeCode => "/project/.claude/agents",
+                Platform::OpenCode => "/project/.opencode/agents",
+            };
+            assert_eq!(
+                discovery.agents_target_dir(platform),
+                PathBuf::from(expected),
+                "{}",
+                platform
+            );
+        }
+    }
+
+    #[test]
+    fn test_agents_target_dir_override() {
+        let fs = MockFs::new();
+        let mut output_dirs = BTreeMap::new();
+        output_dirs.insert(
+            "claude".to_string(),
+            ToolDirs {
+                agents: Some(PathBuf::from("custom/agents")),
+                ..Default::default()
+            },
+        );
+        let discovery =
+            AgentDiscovery::new(&fs, Path::new("/project")).with_output_dirs(output_dirs);
+
+        // Override wins for Claude Code, default is kept for OpenCode
         assert_eq!(
             discovery.agents_target_dir(Platform::ClaudeCode),
-            PathBuf::from("/project/.claude/agents")
+            PathBuf::from("/project/custom/agents")
         );
-
         assert_eq!(
             discovery.agents_target_dir(Platform::OpenCode),
             PathBuf::from("/project/.opencode/agents")
@@ -5,11 +5,22 @@ mod compiler;
 mod diff;
 mod discovery;
 mod parser;
+mod sarif;
 mod validator;
 
-pub use commands::{AgentsListCommand, AgentsSetupCommand, AgentsUpdateCommand};
-pub use compiler::{compile_agent, CompiledAgent, Platform};
+pub use commands::{
+    AgentsListCommand, AgentsRemoveCommand, AgentsSetupCommand, AgentsUpdateCommand,
+    AgentsValidateCommand,
+};
+pub use compiler::{agent_filename, compile_agent, denied_tools, CompiledAgent, Platform};
 pub use diff::{AgentDiff, AgentDiffer, AgentStatus, AgentsUpdateDiff};
 pub use discovery::{AgentDiscovery, AgentInfo};
-pub use parser::{parse_agent_md, AgentSpec, Capabilities, ModelTier, ParsedAgent};
-pub use validator::{validate_agent, validate_description, validate_name, ValidationError, ValidationResult};
+pub use parser::{
+    frontmatter_field_line, parse_agent_md, parse_agent_md_collecting, AgentSpec, Capabilities,
+    ModelTier, ParsedAgent,
+};
+pub use sarif::{sarif_report, AgentFindings, ValidationFormat};
+pub use validator::{
+    collect_agent_errors, validate_agent, validate_description, validate_name, ValidationError,
+    ValidationErrorKind, ValidationResult,
+};
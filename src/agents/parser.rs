@@ -1,8 +1,10 @@
 //! AGENT.md parsing - YAML frontmatter and markdown body extraction
 
+use crate::agents::validator::{ValidationError, ValidationResult};
 use crate::core::types::{Error, Result};
 use serde::Deserialize;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 /// Agent capabilities - determines which tools are available
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -43,21 +45,38 @@ pub enum ModelTier {
     Capable,
 }
 
+impl ModelTier {
+    /// Every tier, in order from cheapest to most capable
+    pub const ALL: [ModelTier; 3] = [ModelTier::Fast, ModelTier::Balanced, ModelTier::Capable];
+
+    /// The frontmatter spelling of this tier
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelTier::Fast => "fast",
+            ModelTier::Balanced => "balanced",
+            ModelTier::Capable => "capable",
+        }
+    }
+}
+
+impl FromStr for ModelTier {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        ModelTier::ALL
+            .into_iter()
+            .find(|tier| tier.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| ValidationError::unknown_model_tier(s))
+    }
+}
+
 impl<'de> Deserialize<'de> for ModelTier {
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        match s.to_lowercase().as_str() {
-            "fast" => Ok(ModelTier::Fast),
-            "balanced" => Ok(ModelTier::Balanced),
-            "capable" => Ok(ModelTier::Capable),
-            _ => Err(serde::de::Error::custom(format!(
-                "Invalid model-tier: {}. Expected 'fast', 'balanced', or 'capable'",
-                s
-            ))),
-        }
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -68,10 +87,13 @@ struct RawFrontmatter {
     description: String,
     #[serde(default)]
     capabilities: Option<Capabilities>,
+    // Kept as a string so an unknown tier gets a dedicated error
     #[serde(rename = "model-tier", default)]
-    model_tier: Option<ModelTier>,
+    model_tier: Option<String>,
     #[serde(default)]
     skills: Option<Vec<String>>,
+    #[serde(default, alias = "when")]
+    triggers: Option<Vec<String>>,
 }
 
 /// Parsed agent specification from YAML frontmatter
@@ -87,6 +109,8 @@ pub struct AgentSpec {
     pub model_tier: ModelTier,
     /// Skills the agent can use
     pub skills: Vec<String>,
+    /// File patterns that activate the agent (`triggers:` or `when:`)
+    pub triggers: Vec<String>,
 }
 
 /// Complete parsed agent with spec and system prompt
@@ -102,13 +126,45 @@ pub struct ParsedAgent {
 
 /// Parse AGENT.md content into structured data
 pub fn parse_agent_md(content: &str, source_path: PathBuf) -> Result<ParsedAgent> {
+    let (parsed, result) = parse_agent_md_collecting(content, source_path)?;
+    if result.is_valid() {
+        return Ok(parsed);
+    }
+
+    let messages: Vec<String> = result
+        .errors
+        .iter()
+        .map(|e| format!("{} in {}", e, parsed.source_path.display()))
+        .collect();
+    Err(Error::Parse(messages.join("\n")))
+}
+
+/// Parse AGENT.md content, collecting invalid frontmatter values instead of failing
+///
+/// Only a file that cannot be read as frontmatter plus body is an error. An
+/// unknown `model-tier` or a blank trigger is reported in the returned
+/// [`ValidationResult`], with the spec falling back to the default tier and the
+/// remaining triggers, so validation can report it alongside every other rule.
+pub fn parse_agent_md_collecting(
+    content: &str,
+    source_path: PathBuf,
+) -> Result<(ParsedAgent, ValidationResult)> {
+    if content.trim().is_empty() {
+        return Err(Error::Parse(format!(
+            "AGENT.md is empty or missing frontmatter: {}",
+            source_path.display()
+        )));
+    }
+
     // Split content by --- delimiters
     let parts: Vec<&str> = content.splitn(3, "---").collect();
 
-    if parts.len() < 3 {
-        return Err(Error::Parse(
-            "AGENT.md must have YAML frontmatter delimited by ---".to_string(),
-        ));
+    // Frontmatter must come first; a later --- is just a markdown rule
+    if parts.len() < 3 || !parts[0].trim().is_empty() {
+        return Err(Error::Parse(format!(
+            "AGENT.md must start with YAML frontmatter delimited by ---: {}",
+            source_path.display()
+        )));
     }
 
     // parts[0] is empty (before first ---)
@@ -118,23 +174,71 @@ pub fn parse_agent_md(content: &str, source_path: PathBuf) -> Result<ParsedAgent
     let yaml_content = parts[1].trim();
     let markdown_body = parts[2].trim();
 
+    if markdown_body.is_empty() {
+        return Err(Error::Parse(format!(
+            "AGENT.md has no system prompt after the frontmatter: {}",
+            source_path.display()
+        )));
+    }
+
     // Parse YAML frontmatter
-    let raw: RawFrontmatter =
-        serde_yaml::from_str(yaml_content).map_err(|e| Error::Parse(format!("YAML parse error: {}", e)))?;
+    let raw: RawFrontmatter = serde_yaml::from_str(yaml_content).map_err(|e| {
+        Error::Parse(format!("YAML parse error in {}: {}", source_path.display(), e))
+    })?;
+
+    let mut result = ValidationResult::new();
+
+    let model_tier = match raw.model_tier.map(|value| value.parse::<ModelTier>()) {
+        Some(Ok(tier)) => tier,
+        Some(Err(e)) => {
+            result.errors.push(e);
+            ModelTier::default()
+        }
+        None => ModelTier::default(),
+    };
+
+    let mut triggers = raw.triggers.unwrap_or_default();
+    if triggers.iter().any(|pattern| pattern.trim().is_empty()) {
+        result.add_error("triggers", "Trigger patterns must be non-empty strings");
+        triggers.retain(|pattern| !pattern.trim().is_empty());
+    }
 
     let spec = AgentSpec {
         name: raw.name,
         description: raw.description,
         capabilities: raw.capabilities.unwrap_or_default(),
-        model_tier: raw.model_tier.unwrap_or_default(),
+        model_tier,
         skills: raw.skills.unwrap_or_default(),
+        triggers,
     };
 
-    Ok(ParsedAgent {
+    let parsed = ParsedAgent {
         spec,
         system_prompt: markdown_body.to_string(),
         source_path,
-    })
+    };
+    Ok((parsed, result))
+}
+
+/// 1-based line of a top-level frontmatter key in AGENT.md content
+///
+/// Only keys at the start of a line inside the leading `---` block count,
+/// so nested keys and the markdown body are never matched.
+pub fn frontmatter_field_line(content: &str, field: &str) -> Option<usize> {
+    let mut lines = content.lines().enumerate();
+    lines.by_ref().find(|(_, line)| !line.trim().is_empty())?;
+
+    for (index, line) in lines {
+        if line.trim_end() == "---" {
+            return None;
+        }
+        if let Some(rest) = line.strip_prefix(field)
+            && rest.trim_start().starts_with(':')
+        {
+            return Some(index + 1);
+        }
+    }
+    None
 }
 
 #[cfg(test)]
@@ -188,6 +292,22 @@ System prompt here.
         assert!(result.spec.skills.is_empty());
     }
 
+    #[test]
+    fn test_parse_triggers() {
+        let content = "---\nname: a\ndescription: d\ntriggers:\n  - \"**/*.rs\"\n---\nPrompt";
+        let parsed = parse_agent_md(content, PathBuf::from("/test/AGENT.md")).unwrap();
+        assert_eq!(parsed.spec.triggers, vec!["**/*.rs"]);
+
+        // `when:` is accepted as an alias
+        let content = "---\nname: a\ndescription: d\nwhen: [Cargo.toml]\n---\nPrompt";
+        let parsed = parse_agent_md(content, PathBuf::from("/test/AGENT.md")).unwrap();
+        assert_eq!(parsed.spec.triggers, vec!["Cargo.toml"]);
+
+        let content = "---\nname: a\ndescription: d\ntriggers: [\"  \"]\n---\nPrompt";
+        let result = parse_agent_md(content, PathBuf::from("/test/AGENT.md"));
+        assert!(matches!(result, Err(Error::Parse(msg)) if msg.contains("non-empty")));
+    }
+
     #[test]
     fn test_parse_missing_frontmatter() {
         let content = "Just markdown without frontmatter";
@@ -197,6 +317,35 @@ System prompt here.
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_empty_file() {
+        for content in ["", "  \n\t\n"] {
+            let err = parse_agent_md(content, PathBuf::from("/test/empty/AGENT.md")).unwrap_err();
+            let message = err.to_string();
+            assert!(message.contains("empty or missing frontmatter"), "{}", message);
+            assert!(message.contains("/test/empty/AGENT.md"), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_parse_frontmatter_only() {
+        let content = "---\nname: test\ndescription: test\n---\n";
+
+        let err = parse_agent_md(content, PathBuf::from("/test/AGENT.md")).unwrap_err();
+        assert!(err.to_string().contains("no system prompt"));
+    }
+
+    #[test]
+    fn test_parse_body_only_with_rule() {
+        // A markdown horizontal rule must not be mistaken for frontmatter
+        let content = "# Agent\n\n---\n\nSome text\n---\nMore";
+
+        let err = parse_agent_md(content, PathBuf::from("/test/AGENT.md")).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("must start with YAML frontmatter"), "{}", message);
+        assert!(message.contains("/test/AGENT.md"), "{}", message);
+    }
+
     #[test]
     fn test_parse_invalid_yaml() {
         let content = r#"---
@@ -241,7 +390,26 @@ Body
 
         let result = parse_agent_md(content, PathBuf::from("/test/AGENT.md"));
 
-        assert!(result.is_err());
+        match result {
+            Err(Error::Parse(msg)) => {
+                assert!(msg.contains("'ultra'"), "{}", msg);
+                assert!(msg.contains("fast, balanced, capable"), "{}", msg);
+                assert!(msg.contains("/test/AGENT.md"), "{}", msg);
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_model_tier_from_str() {
+        assert_eq!("Capable".parse::<ModelTier>().unwrap(), ModelTier::Capable);
+
+        let error = "turbo".parse::<ModelTier>().unwrap_err();
+        assert_eq!(error.field, "model-tier");
+        assert_eq!(
+            error.message,
+            "Unknown model tier 'turbo' (allowed: fast, balanced, capable)"
+        );
     }
 
     #[test]
@@ -301,4 +469,16 @@ Body
             assert_eq!(result.spec.model_tier, expected);
         }
     }
+
+    #[test]
+    fn test_frontmatter_field_line() {
+        let content = "\n---\nname: test\nskills:\n  - name: nested\nwhen: ['*.rs']\n---\n\n\
+                       description: in the body\n";
+
+        assert_eq!(frontmatter_field_line(content, "name"), Some(3));
+        assert_eq!(frontmatter_field_line(content, "skills"), Some(4));
+        assert_eq!(frontmatter_field_line(content, "when"), Some(6));
+        assert_eq!(frontmatter_field_line(content, "description"), None);
+        assert_eq!(frontmatter_field_line("", "name"), None);
+    }
 }
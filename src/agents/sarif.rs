@@ -0,0 +1,103 @@
+//! SARIF output for `agents validate`, for code-scanning annotations in CI
+
+use crate::agents::parser::frontmatter_field_line;
+use crate::agents::validator::ValidationError;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+/// SARIF version emitted
+const SARIF_VERSION: &str = "2.1.0";
+/// Schema of the emitted SARIF version
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// How `agents validate` reports its findings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationFormat {
+    /// PASS/FAIL log lines
+    #[default]
+    Text,
+    /// A single SARIF 2.1.0 document on stdout
+    Sarif,
+}
+
+/// Validation errors for one AGENT.md
+#[derive(Debug, Clone)]
+pub struct AgentFindings {
+    /// Path to the AGENT.md file
+    pub agent_md_path: PathBuf,
+    /// File contents, used to locate frontmatter fields (if readable)
+    pub content: Option<String>,
+    pub errors: Vec<ValidationError>,
+}
+
+/// Build a SARIF log with one result per validation error
+///
+/// File paths are relative to `project_path` so code scanning can match
+/// them to the repository. Errors on a frontmatter field point at the line
+/// that sets it; anything else points at the file.
+pub fn sarif_report(project_path: &Path, findings: &[AgentFindings]) -> Value {
+    let mut rule_ids = BTreeSet::new();
+    let mut results = Vec::new();
+
+    for finding in findings {
+        let uri = relative_uri(project_path, &finding.agent_md_path);
+        for error in &finding.errors {
+            let rule_id = format!("agent/{}", error.field);
+            let line = finding
+                .content
+                .as_deref()
+                .and_then(|content| field_line(content, &error.field));
+
+            let mut location = json!({ "artifactLocation": { "uri": uri } });
+            if let Some(line) = line {
+                location["region"] = json!({ "startLine": line });
+            }
+
+            results.push(json!({
+                "ruleId": rule_id,
+                "level": "error",
+                "message": { "text": error.message },
+                "locations": [{ "physicalLocation": location }],
+            }));
+            rule_ids.insert(rule_id);
+        }
+    }
+
+    let rules: Vec<Value> = rule_ids.iter().map(|id| json!({ "id": id })).collect();
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Line of the frontmatter key an error is about, counting `when:` for triggers
+fn field_line(content: &str, field: &str) -> Option<usize> {
+    frontmatter_field_line(content, field).or_else(|| match field {
+        "triggers" => frontmatter_field_line(content, "when"),
+        _ => None,
+    })
+}
+
+/// `path` relative to `project_path`, joined with `/` as SARIF URIs require
+fn relative_uri(project_path: &Path, path: &Path) -> String {
+    path.strip_prefix(project_path)
+        .unwrap_or(path)
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
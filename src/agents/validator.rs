@@ -1,20 +1,107 @@
 //! Agent validation rules
 
-use crate::agents::parser::AgentSpec;
+use crate::agents::parser::{AgentSpec, ModelTier};
 use crate::core::infra::FileSystem;
 use crate::core::types::{Error, Result};
 use std::path::Path;
 
 /// Maximum allowed name length
 const MAX_NAME_LENGTH: usize = 64;
-/// Maximum allowed description length
+/// Minimum description length (platforms use it to decide when to invoke the agent)
+const MIN_DESCRIPTION_LENGTH: usize = 10;
+/// Maximum allowed description length (longer descriptions get truncated)
 const MAX_DESCRIPTION_LENGTH: usize = 1024;
 
+/// What kind of rule a validation error broke
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationErrorKind {
+    /// Any rule without a dedicated kind
+    #[default]
+    Other,
+    /// Description is empty or only whitespace
+    DescriptionEmpty,
+    /// Description is shorter than the minimum length
+    DescriptionTooShort,
+    /// Description is longer than the maximum length
+    DescriptionTooLong,
+    /// Description has surrounding whitespace or line breaks
+    DescriptionFormat,
+}
+
 /// Validation error details
 #[derive(Debug, Clone)]
 pub struct ValidationError {
     pub field: String,
     pub message: String,
+    pub kind: ValidationErrorKind,
+}
+
+impl ValidationError {
+    fn description(kind: ValidationErrorKind, message: String) -> Self {
+        Self {
+            field: "description".to_string(),
+            message,
+            kind,
+        }
+    }
+
+    /// An empty or whitespace-only description
+    pub fn description_empty() -> Self {
+        Self::description(
+            ValidationErrorKind::DescriptionEmpty,
+            "Description cannot be empty".to_string(),
+        )
+    }
+
+    /// A description of `len` characters, below the minimum
+    pub fn description_too_short(len: usize) -> Self {
+        Self::description(
+            ValidationErrorKind::DescriptionTooShort,
+            format!(
+                "Description is {} characters; at least {} are needed to say when to use the agent",
+                len, MIN_DESCRIPTION_LENGTH
+            ),
+        )
+    }
+
+    /// A description of `len` characters, above the maximum
+    pub fn description_too_long(len: usize) -> Self {
+        Self::description(
+            ValidationErrorKind::DescriptionTooLong,
+            format!(
+                "Description is {} characters and exceeds maximum length of {} characters",
+                len, MAX_DESCRIPTION_LENGTH
+            ),
+        )
+    }
+
+    /// A description that would break the compiled frontmatter
+    pub fn description_format(reason: &str) -> Self {
+        Self::description(ValidationErrorKind::DescriptionFormat, reason.to_string())
+    }
+
+    /// An AGENT.md that could not be read or parsed at all
+    pub fn file(message: String) -> Self {
+        Self {
+            field: "file".to_string(),
+            message,
+            kind: ValidationErrorKind::Other,
+        }
+    }
+
+    /// A `model-tier` value that is not one of the known tiers
+    pub fn unknown_model_tier(value: &str) -> Self {
+        let allowed: Vec<&str> = ModelTier::ALL.iter().map(ModelTier::as_str).collect();
+        Self {
+            field: "model-tier".to_string(),
+            message: format!(
+                "Unknown model tier '{}' (allowed: {})",
+                value,
+                allowed.join(", ")
+            ),
+            kind: ValidationErrorKind::Other,
+        }
+    }
 }
 
 impl std::fmt::Display for ValidationError {
@@ -38,10 +125,16 @@ impl ValidationResult {
         self.errors.is_empty()
     }
 
+    /// Append every error from `other`
+    pub fn merge(&mut self, other: ValidationResult) {
+        self.errors.extend(other.errors);
+    }
+
     pub fn add_error(&mut self, field: &str, message: &str) {
         self.errors.push(ValidationError {
             field: field.to_string(),
             message: message.to_string(),
+            kind: ValidationErrorKind::Other,
         });
     }
 }
@@ -120,21 +213,33 @@ pub fn validate_name_matches_directory(name: &str, source_path: &Path) -> Valida
 }
 
 /// Validate agent description
+/// - Not empty or whitespace-only
+/// - 10-1024 characters
+/// - A single line without leading/trailing whitespace
 pub fn validate_description(description: &str) -> ValidationResult {
     let mut result = ValidationResult::new();
 
-    if description.is_empty() {
-        result.add_error("description", "Description cannot be empty");
+    if description.trim().is_empty() {
+        result.errors.push(ValidationError::description_empty());
+        return result;
     }
 
-    if description.len() > MAX_DESCRIPTION_LENGTH {
-        result.add_error(
-            "description",
-            &format!(
-                "Description exceeds maximum length of {} characters",
-                MAX_DESCRIPTION_LENGTH
-            ),
-        );
+    let len = description.chars().count();
+    if len < MIN_DESCRIPTION_LENGTH {
+        result.errors.push(ValidationError::description_too_short(len));
+    }
+    if len > MAX_DESCRIPTION_LENGTH {
+        result.errors.push(ValidationError::description_too_long(len));
+    }
+
+    if description.contains(['\n', '\r']) {
+        result.errors.push(ValidationError::description_format(
+            "Description must be a single line",
+        ));
+    } else if description.trim() != description {
+        result.errors.push(ValidationError::description_format(
+            "Description cannot start or end with whitespace",
+        ));
     }
 
     result
@@ -168,33 +273,41 @@ pub async fn validate_agent<F: FileSystem>(
     skills_dir: &Path,
     fs: &F,
 ) -> Result<()> {
-    let mut all_errors = Vec::new();
+    let result = collect_agent_errors(spec, source_path, skills_dir, fs).await;
+
+    if result.is_valid() {
+        Ok(())
+    } else {
+        let error_messages: Vec<String> = result.errors.iter().map(|e| e.to_string()).collect();
+        Err(Error::Parse(format!(
+            "Agent validation failed:\n  - {}",
+            error_messages.join("\n  - ")
+        )))
+    }
+}
+
+/// Collect every validation error for an agent specification
+pub async fn collect_agent_errors<F: FileSystem>(
+    spec: &AgentSpec,
+    source_path: &Path,
+    skills_dir: &Path,
+    fs: &F,
+) -> ValidationResult {
+    let mut result = ValidationResult::new();
 
     // Validate name
-    let name_result = validate_name(&spec.name);
-    all_errors.extend(name_result.errors);
+    result.merge(validate_name(&spec.name));
 
     // Validate name matches directory
-    let dir_result = validate_name_matches_directory(&spec.name, source_path);
-    all_errors.extend(dir_result.errors);
+    result.merge(validate_name_matches_directory(&spec.name, source_path));
 
     // Validate description
-    let desc_result = validate_description(&spec.description);
-    all_errors.extend(desc_result.errors);
+    result.merge(validate_description(&spec.description));
 
     // Validate skills
-    let skills_result = validate_skills(&spec.skills, skills_dir, fs).await;
-    all_errors.extend(skills_result.errors);
+    result.merge(validate_skills(&spec.skills, skills_dir, fs).await);
 
-    if all_errors.is_empty() {
-        Ok(())
-    } else {
-        let error_messages: Vec<String> = all_errors.iter().map(|e| e.to_string()).collect();
-        Err(Error::Parse(format!(
-            "Agent validation failed:\n  - {}",
-            error_messages.join("\n  - ")
-        )))
-    }
+    result
 }
 
 #[cfg(test)]
@@ -297,9 +410,65 @@ mod tests {
         assert!(result.errors.iter().any(|e| e.message.contains("maximum length")));
     }
 
+    #[test]
+    fn test_validate_description_boundaries() {
+        use ValidationErrorKind::*;
+
+        let cases: Vec<(String, Vec<ValidationErrorKind>)> = vec![
+            (String::new(), vec![DescriptionEmpty]),
+            ("   ".to_string(), vec![DescriptionEmpty]),
+            ("a".to_string(), vec![DescriptionTooShort]),
+            ("a".repeat(MIN_DESCRIPTION_LENGTH - 1), vec![DescriptionTooShort]),
+            ("a".repeat(MIN_DESCRIPTION_LENGTH), vec![]),
+            ("a".repeat(MAX_DESCRIPTION_LENGTH), vec![]),
+            ("a".repeat(MAX_DESCRIPTION_LENGTH + 1), vec![DescriptionTooLong]),
+            // Length counts characters, not bytes
+            ("é".repeat(MAX_DESCRIPTION_LENGTH), vec![]),
+            (" Reviews code.".to_string(), vec![DescriptionFormat]),
+            ("Reviews code.\n".to_string(), vec![DescriptionFormat]),
+            ("Reviews\ncode changes".to_string(), vec![DescriptionFormat]),
+        ];
+
+        for (description, expected) in cases {
+            let kinds: Vec<ValidationErrorKind> = validate_description(&description)
+                .errors
+                .iter()
+                .map(|e| e.kind)
+                .collect();
+            assert_eq!(kinds, expected, "description {:?}", description);
+        }
+    }
+
     #[test]
     fn test_validation_result_default() {
         let result = ValidationResult::default();
         assert!(result.is_valid());
     }
+
+    #[tokio::test]
+    async fn test_collect_agent_errors_reports_all() {
+        use crate::agents::parser::{Capabilities, ModelTier};
+        use crate::infra::StdFileSystem;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let spec = AgentSpec {
+            name: "Bad--Name".to_string(),
+            description: String::new(),
+            capabilities: Capabilities::default(),
+            model_tier: ModelTier::default(),
+            skills: vec!["missing-skill".to_string()],
+            triggers: vec![],
+        };
+        let source_path = temp_dir.path().join("other-dir/AGENT.md");
+
+        let errors =
+            collect_agent_errors(&spec, &source_path, temp_dir.path(), &StdFileSystem::new())
+                .await
+                .errors;
+
+        assert!(errors.iter().any(|e| e.message.contains("consecutive hyphens")));
+        assert!(errors.iter().any(|e| e.message.contains("does not match directory")));
+        assert!(errors.iter().any(|e| e.field == "description"));
+        assert!(errors.iter().any(|e| e.message.contains("missing-skill")));
+    }
 }
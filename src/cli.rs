@@ -1,11 +1,19 @@
 //! CLI definitions using Clap.
 
+use std::io::Write;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 
-use aiassisted::agents::Platform;
+use aiassisted::agents::{Platform, ValidationFormat};
+use aiassisted::content::backup::DEFAULT_KEEP_BACKUPS;
+use aiassisted::content::github::DEFAULT_CONCURRENCY;
+use aiassisted::core::types::OutputFormat;
 use aiassisted::core::ToolType;
+use aiassisted::infra::{ColorChoice, StrictMode};
+use aiassisted::migration::DEFAULT_RETAIN_BACKUPS;
 
 /// CLI tool for embedding AI assistant guidelines and templates into projects.
 #[derive(Parser, Debug)]
@@ -17,6 +25,67 @@ pub struct Cli {
     #[arg(short, long, action = clap::ArgAction::Count, global = true)]
     pub verbose: u8,
 
+    /// Append all log output (at full verbosity) to this file
+    #[arg(long, global = true, env = "AIASSISTED_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// When to use colored output
+    #[arg(long, global = true, value_enum, default_value_t = CliColorChoice::Auto)]
+    pub color: CliColorChoice,
+
+    /// Never access the network; commands that need remote content fail
+    #[arg(long, global = true, env = "AIASSISTED_OFFLINE")]
+    pub offline: bool,
+
+    /// Cap download speed, e.g. 500K or 1MB (per second)
+    #[arg(long, global = true, env = "AIASSISTED_RATE_LIMIT", value_parser = parse_rate_limit)]
+    pub rate_limit: Option<u64>,
+
+    /// Use this configuration file instead of ~/.aiassisted/config.toml
+    #[arg(long, global = true, env = "AIASSISTED_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Maximum number of files downloaded at once (clamped to 1-32)
+    #[arg(long, global = true, env = "AIASSISTED_CONCURRENCY", default_value_t = DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
+
+    /// Keep downloading after a file fails, then list every failure
+    #[arg(long, global = true)]
+    pub keep_going: bool,
+
+    /// Download the manifest from this URL instead of GitHub
+    #[arg(long, global = true, env = "AIASSISTED_MANIFEST_URL")]
+    pub manifest_url: Option<String>,
+
+    /// Read the manifest from this file, or `-` for stdin, instead of downloading it
+    #[arg(long, global = true, value_name = "FILE")]
+    pub manifest: Option<PathBuf>,
+
+    /// Download content files from this base URL instead of GitHub
+    #[arg(long, global = true, env = "AIASSISTED_CONTENT_BASE")]
+    pub content_base: Option<String>,
+
+    /// Always download content instead of reusing ~/.aiassisted/cache
+    #[arg(long, global = true, env = "AIASSISTED_NO_CACHE")]
+    pub no_cache: bool,
+
+    /// Re-detect the AI tool instead of reusing .aiassisted/.tool-cache
+    #[arg(long, global = true)]
+    pub refresh_detection: bool,
+
+    /// Fail with exit code 8 if anything warns, after the command finishes
+    /// or (`--strict=immediate`) at the next safe point after the first warning
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "at-end",
+        value_name = "WHEN"
+    )]
+    pub strict: Option<CliStrictMode>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -33,6 +102,18 @@ pub enum Commands {
     /// Check if updates are available
     Check(CheckArgs),
 
+    /// Re-download installed files that fail checksum verification
+    Repair(RepairArgs),
+
+    /// List files changed upstream since a manifest version
+    Changelog(ChangelogArgs),
+
+    /// Inspect installed .aiassisted content
+    Content(ContentArgs),
+
+    /// Maintain content manifests
+    Manifest(ManifestArgs),
+
     /// Set up AI skills (copy to tool directory)
     SetupSkills(SetupSkillsArgs),
 
@@ -46,21 +127,48 @@ pub enum Commands {
     Config(ConfigArgs),
 
     /// Update the CLI binary itself
-    SelfUpdate,
+    SelfUpdate {
+        /// Download and extract here instead of the temp directory
+        /// (default: temp_dir from config, then $TMPDIR)
+        #[arg(long, env = "AIASSISTED_TEMP_DIR", value_name = "DIR")]
+        temp_dir: Option<PathBuf>,
+    },
 
     /// Migrate from old shell-based version
-    Migrate,
+    Migrate {
+        /// Number of ~/.aiassisted/source.backup.* directories to keep (at least 1)
+        #[arg(long, default_value_t = DEFAULT_RETAIN_BACKUPS)]
+        retain: usize,
+    },
 
-    /// Show version information
+    /// Generate a shell completion script on stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// Show version information (--verbose adds commit, build and platform details)
     Version,
 }
 
+/// Write the completion script for `shell` to `out`.
+pub fn write_completions(shell: Shell, out: &mut dyn Write) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
 /// Arguments for the install command.
 #[derive(Parser, Debug)]
 pub struct InstallArgs {
     /// Target directory path
     #[arg(short, long, default_value = ".")]
     pub path: PathBuf,
+
+    /// Show which files would be downloaded without downloading them
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 /// Arguments for the update command.
@@ -70,6 +178,30 @@ pub struct UpdateArgs {
     #[arg(short, long)]
     pub force: bool,
 
+    /// Show which files would be downloaded without downloading them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Delete installed files that are no longer in the remote manifest
+    #[arg(long)]
+    pub prune: bool,
+
+    /// Do not back up the current content before updating
+    #[arg(long)]
+    pub no_backup: bool,
+
+    /// Number of backups to keep
+    #[arg(long, default_value_t = DEFAULT_KEEP_BACKUPS)]
+    pub keep_backups: usize,
+
+    /// Restore the most recent backup instead of updating
+    #[arg(long, conflicts_with_all = ["force", "no_backup", "dry_run", "prune"])]
+    pub rollback: bool,
+
+    /// Roll back without a confirmation prompt (required when not running in a terminal)
+    #[arg(short, long, requires = "rollback")]
+    pub yes: bool,
+
     /// Target directory path
     #[arg(short, long, default_value = ".")]
     pub path: PathBuf,
@@ -81,13 +213,101 @@ pub struct CheckArgs {
     /// Target directory path
     #[arg(short, long, default_value = ".")]
     pub path: PathBuf,
+
+    /// Output format (json or yaml prints the new/modified/unchanged file lists)
+    #[arg(long, value_enum, default_value_t = CliOutputFormat::Text)]
+    pub format: CliOutputFormat,
+
+    /// Print nothing; exit 0 only if every file verifies and nothing changed upstream
+    #[arg(short, long, alias = "check", conflicts_with = "format")]
+    pub quiet: bool,
+
+    /// Keep re-verifying local files until interrupted (Ctrl-C)
+    #[arg(long, conflicts_with_all = ["format", "quiet"])]
+    pub watch: bool,
+
+    /// Time between verification rounds with --watch (e.g. 30s, 5m, 1h)
+    #[arg(long, requires = "watch", default_value = "60s", value_parser = parse_interval)]
+    pub interval: Duration,
+
+    /// With --watch, exit non-zero on the first file that changes
+    #[arg(long, requires = "watch")]
+    pub fail_on_change: bool,
+}
+
+/// Arguments for the repair command.
+#[derive(Parser, Debug)]
+pub struct RepairArgs {
+    /// Target directory path
+    #[arg(short, long, default_value = ".")]
+    pub path: PathBuf,
+}
+
+/// Arguments for the changelog command.
+#[derive(Parser, Debug)]
+pub struct ChangelogArgs {
+    /// Manifest version (git commit) to compare against
+    #[arg(long)]
+    pub since: String,
+
+    /// Target directory path
+    #[arg(short, long, default_value = ".")]
+    pub path: PathBuf,
+}
+
+/// Arguments for the content command.
+#[derive(Parser, Debug)]
+pub struct ContentArgs {
+    #[command(subcommand)]
+    pub command: ContentCommands,
+}
+
+/// Content subcommands.
+#[derive(Subcommand, Debug)]
+pub enum ContentCommands {
+    /// List managed files and stray files in .aiassisted
+    List {
+        /// Target directory path
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Re-hash each managed file and show its status
+        #[arg(long)]
+        verify: bool,
+
+        /// Output format (json or yaml prints the managed and stray file lists)
+        #[arg(long, value_enum, default_value_t = CliOutputFormat::Text)]
+        format: CliOutputFormat,
+    },
+}
+
+/// Arguments for the manifest command.
+#[derive(Parser, Debug)]
+pub struct ManifestArgs {
+    #[command(subcommand)]
+    pub command: ManifestCommands,
+}
+
+/// Manifest subcommands.
+#[derive(Subcommand, Debug)]
+pub enum ManifestCommands {
+    /// Generate manifest.json for a .aiassisted directory
+    Generate {
+        /// Version string to record (e.g. a git commit hash)
+        #[arg(long)]
+        manifest_version: String,
+
+        /// The .aiassisted directory to index
+        #[arg(short, long, default_value = ".aiassisted")]
+        path: PathBuf,
+    },
 }
 
 /// Arguments for the setup-skills command.
 #[derive(Parser, Debug)]
 pub struct SetupSkillsArgs {
-    /// AI tool to generate skills for
-    #[arg(short, long, value_enum, default_value = "auto")]
+    /// AI tool to generate skills for (auto, claude, opencode)
+    #[arg(short, long, value_parser = parse_tool_type, default_value = "auto")]
     pub tool: CliToolType,
 
     /// Show what would be created without creating
@@ -111,9 +331,9 @@ pub struct SkillsArgs {
 pub enum SkillsCommands {
     /// Set up AI skills (copy to tool directory)
     Setup {
-        /// AI tool to generate skills for
-        #[arg(short, long, value_enum, default_value = "auto")]
-        tool: CliToolType,
+        /// AI tool to generate skills for (auto, claude, opencode, or all)
+        #[arg(short, long, value_parser = parse_tool_selection, default_value = "auto")]
+        tool: CliToolSelection,
 
         /// Show what would be created without creating
         #[arg(long)]
@@ -122,19 +342,23 @@ pub enum SkillsCommands {
         /// Overwrite existing skills
         #[arg(long)]
         force: bool,
+
+        /// Follow symlinks inside skills (loops are skipped)
+        #[arg(long)]
+        follow_symlinks: bool,
     },
 
     /// List available skills
     List {
-        /// AI tool to list skills for
-        #[arg(short, long, value_enum, default_value = "auto")]
+        /// AI tool to list skills for (auto, claude, opencode)
+        #[arg(short, long, value_parser = parse_tool_type, default_value = "auto")]
         tool: CliToolType,
     },
 
     /// Update installed skills (sync changes from source)
     Update {
-        /// AI tool to update skills for
-        #[arg(short, long, value_enum, default_value = "auto")]
+        /// AI tool to update skills for (auto, claude, opencode)
+        #[arg(short, long, value_parser = parse_tool_type, default_value = "auto")]
         tool: CliToolType,
 
         /// Show what would be updated without updating
@@ -144,6 +368,64 @@ pub enum SkillsCommands {
         /// Force update all files (ignore checksums)
         #[arg(long)]
         force: bool,
+
+        /// Re-hash updated files against the source and fail on any mismatch
+        #[arg(long)]
+        verify: bool,
+
+        /// Follow symlinks inside skills (loops are skipped)
+        #[arg(long)]
+        follow_symlinks: bool,
+    },
+
+    /// Preview differences between source and installed skills
+    Diff {
+        /// AI tool to compare skills for (auto, claude, opencode)
+        #[arg(short, long, value_parser = parse_tool_type, default_value = "auto")]
+        tool: CliToolType,
+
+        /// Show per-file status for each skill
+        #[arg(long)]
+        detailed: bool,
+
+        /// Follow symlinks inside skills (loops are skipped)
+        #[arg(long)]
+        follow_symlinks: bool,
+    },
+
+    /// Enable a skill for setup and update (.aiassisted/skills.toml)
+    Enable {
+        /// Skill name (directory under .aiassisted/skills/)
+        name: String,
+    },
+
+    /// Disable a skill for setup and update (.aiassisted/skills.toml)
+    Disable {
+        /// Skill name (directory under .aiassisted/skills/)
+        name: String,
+    },
+
+    /// Bundle one skill from .aiassisted/skills/ into a tar.gz archive
+    Export {
+        /// Skill name (directory under .aiassisted/skills/)
+        name: String,
+
+        /// Archive to write, e.g. git-commit.tar.gz
+        archive: PathBuf,
+    },
+
+    /// Unpack a skill archive into .aiassisted/skills/
+    Import {
+        /// Archive written by `skills export`
+        archive: PathBuf,
+
+        /// Replace an existing skill of the same name
+        #[arg(long)]
+        force: bool,
+
+        /// Replace without a confirmation prompt (required when not running in a terminal)
+        #[arg(short, long, requires = "force")]
+        yes: bool,
     },
 }
 
@@ -159,9 +441,9 @@ pub struct AgentsArgs {
 pub enum AgentsCommands {
     /// Compile and install agents for a platform
     Setup {
-        /// Target platform for agent compilation
+        /// Target platform for agent compilation ("all" for every platform)
         #[arg(short, long, value_enum)]
-        platform: CliPlatform,
+        platform: CliPlatformSelection,
 
         /// Show what would be created without creating
         #[arg(long)]
@@ -170,6 +452,17 @@ pub enum AgentsCommands {
         /// Overwrite existing agents
         #[arg(long)]
         force: bool,
+
+        /// Only compile the agent with this name
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Validate all agents without compiling them
+    Validate {
+        /// Output format (sarif prints a SARIF 2.1.0 log for code scanning)
+        #[arg(long, value_enum, default_value_t = CliValidationFormat::Text)]
+        format: CliValidationFormat,
     },
 
     /// Update installed agents (sync changes from source)
@@ -185,6 +478,38 @@ pub enum AgentsCommands {
         /// Force update all agents (ignore checksums)
         #[arg(long)]
         force: bool,
+
+        /// Only update the agent with this name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Re-hash updated agents against the compiled source and fail on any mismatch
+        #[arg(long)]
+        verify: bool,
+    },
+
+    /// Remove compiled agents from a platform's agents directory
+    Remove {
+        /// Name of the agent to remove
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        name: Option<String>,
+
+        /// Target platform to remove agents from
+        #[arg(short, long, value_enum)]
+        platform: CliPlatform,
+
+        /// Remove every agent compiled from .aiassisted/agents/ (hand-written
+        /// agents are left alone)
+        #[arg(long)]
+        all: bool,
+
+        /// Show what would be removed without removing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Remove without a confirmation prompt (required when not running in a terminal)
+        #[arg(short, long)]
+        yes: bool,
     },
 }
 
@@ -206,6 +531,27 @@ impl From<CliPlatform> for Platform {
     }
 }
 
+/// CLI platform selection for agents setup (a single platform or all of them).
+#[derive(ValueEnum, Clone, Debug)]
+pub enum CliPlatformSelection {
+    #[value(name = "claude-code")]
+    ClaudeCode,
+    #[value(name = "opencode")]
+    OpenCode,
+    #[value(name = "all")]
+    All,
+}
+
+impl From<CliPlatformSelection> for Vec<Platform> {
+    fn from(cli: CliPlatformSelection) -> Self {
+        match cli {
+            CliPlatformSelection::ClaudeCode => vec![Platform::ClaudeCode],
+            CliPlatformSelection::OpenCode => vec![Platform::OpenCode],
+            CliPlatformSelection::All => Platform::ALL.to_vec(),
+        }
+    }
+}
+
 /// Arguments for the config command.
 #[derive(Parser, Debug)]
 pub struct ConfigArgs {
@@ -225,22 +571,96 @@ pub enum ConfigCommands {
         key: String,
     },
 
-    /// Edit configuration in $EDITOR
+    /// Edit configuration in $VISUAL/$EDITOR (validated before it is kept)
     Edit,
 
     /// Reset configuration to defaults
     Reset {
-        /// Skip confirmation prompt
-        #[arg(short, long)]
+        /// Skip confirmation prompt (required when not running in a terminal)
+        #[arg(short, long, visible_alias = "yes", short_alias = 'y')]
         force: bool,
     },
 
     /// Show configuration file path
     Path,
+
+    /// Check the configuration file for invalid values
+    Validate,
+}
+
+/// CLI color choice enum for Clap.
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum CliColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl From<CliColorChoice> for ColorChoice {
+    fn from(cli: CliColorChoice) -> Self {
+        match cli {
+            CliColorChoice::Auto => ColorChoice::Auto,
+            CliColorChoice::Always => ColorChoice::Always,
+            CliColorChoice::Never => ColorChoice::Never,
+        }
+    }
+}
+
+/// CLI strict mode enum for Clap.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CliStrictMode {
+    AtEnd,
+    Immediate,
+}
+
+impl From<CliStrictMode> for StrictMode {
+    fn from(cli: CliStrictMode) -> Self {
+        match cli {
+            CliStrictMode::AtEnd => StrictMode::AtEnd,
+            CliStrictMode::Immediate => StrictMode::Immediate,
+        }
+    }
 }
 
-/// CLI tool type enum for Clap.
-#[derive(ValueEnum, Clone, Debug, Default)]
+/// CLI validation output format enum for Clap.
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum CliValidationFormat {
+    #[default]
+    Text,
+    Sarif,
+}
+
+impl From<CliValidationFormat> for ValidationFormat {
+    fn from(cli: CliValidationFormat) -> Self {
+        match cli {
+            CliValidationFormat::Text => ValidationFormat::Text,
+            CliValidationFormat::Sarif => ValidationFormat::Sarif,
+        }
+    }
+}
+
+/// CLI output format enum for Clap.
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum CliOutputFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
+
+impl From<CliOutputFormat> for OutputFormat {
+    fn from(cli: CliOutputFormat) -> Self {
+        match cli {
+            CliOutputFormat::Text => OutputFormat::Text,
+            CliOutputFormat::Json => OutputFormat::Json,
+            CliOutputFormat::Yaml => OutputFormat::Yaml,
+        }
+    }
+}
+
+/// CLI tool type for `--tool`, parsed by [`parse_tool_type`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum CliToolType {
     #[default]
     Auto,
@@ -248,6 +668,99 @@ pub enum CliToolType {
     Claude,
 }
 
+/// CLI tool selection for `skills setup --tool`, which also accepts `all`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CliToolSelection {
+    Tool(CliToolType),
+    All,
+}
+
+impl From<CliToolSelection> for Vec<ToolType> {
+    fn from(cli: CliToolSelection) -> Self {
+        match cli {
+            CliToolSelection::Tool(tool) => vec![tool.into()],
+            CliToolSelection::All => ToolType::ALL.to_vec(),
+        }
+    }
+}
+
+/// Parse a `--rate-limit` value such as `500K`, `1MB` or `2M/s` into bytes per second.
+///
+/// Suffixes are binary multiples (`K` = 1024 bytes); a bare number is bytes.
+fn parse_rate_limit(value: &str) -> Result<u64, String> {
+    let normalized = value.trim().to_ascii_uppercase();
+    let normalized = normalized.strip_suffix("/S").unwrap_or(&normalized);
+    let normalized = normalized.strip_suffix('B').unwrap_or(normalized);
+
+    let (number, multiplier) = match normalized.chars().last() {
+        Some('K') => (&normalized[..normalized.len() - 1], 1024),
+        Some('M') => (&normalized[..normalized.len() - 1], 1024 * 1024),
+        Some('G') => (&normalized[..normalized.len() - 1], 1024 * 1024 * 1024),
+        _ => (normalized, 1),
+    };
+
+    match number.trim().parse::<u64>().ok().and_then(|n| n.checked_mul(multiplier)) {
+        Some(rate) if rate > 0 => Ok(rate),
+        _ => Err(format!(
+            "invalid rate '{}' (expected a positive size such as 500K or 1MB)",
+            value
+        )),
+    }
+}
+
+/// Parse a `--interval` value such as `30s`, `5m` or `1h`; a bare number is seconds.
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let normalized = value.trim().to_ascii_lowercase();
+    let (number, multiplier) = match normalized.chars().last() {
+        Some('s') => (&normalized[..normalized.len() - 1], 1),
+        Some('m') => (&normalized[..normalized.len() - 1], 60),
+        Some('h') => (&normalized[..normalized.len() - 1], 60 * 60),
+        _ => (normalized.as_str(), 1),
+    };
+
+    match number.trim().parse::<u64>().ok().and_then(|n| n.checked_mul(multiplier)) {
+        Some(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds)),
+        _ => Err(format!(
+            "invalid interval '{}' (expected a positive duration such as 30s, 5m or 1h)",
+            value
+        )),
+    }
+}
+
+/// Accepted `--tool` spellings, canonical name first for each tool.
+const TOOL_NAMES: &[(&str, CliToolType)] = &[
+    ("auto", CliToolType::Auto),
+    ("claude", CliToolType::Claude),
+    ("anthropic", CliToolType::Claude),
+    ("claude-code", CliToolType::Claude),
+    ("opencode", CliToolType::OpenCode),
+    ("open-code", CliToolType::OpenCode),
+];
+
+/// Parse a `--tool` value case-insensitively, accepting aliases.
+fn parse_tool_type(value: &str) -> Result<CliToolType, String> {
+    TOOL_NAMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(value.trim()))
+        .map(|(_, tool)| tool.clone())
+        .ok_or_else(|| {
+            format!(
+                "unknown tool '{}' (valid: auto, claude, opencode; aliases: anthropic, claude-code for claude, open-code for opencode)",
+                value
+            )
+        })
+}
+
+/// Parse a `skills setup --tool` value: `all` or anything [`parse_tool_type`] accepts.
+fn parse_tool_selection(value: &str) -> Result<CliToolSelection, String> {
+    if value.trim().eq_ignore_ascii_case("all") {
+        return Ok(CliToolSelection::All);
+    }
+    parse_tool_type(value)
+        .map(CliToolSelection::Tool)
+        .map_err(|e| format!("{}; use 'all' for every tool", e))
+}
+
 impl From<CliToolType> for ToolType {
     fn from(cli: CliToolType) -> Self {
         match cli {
@@ -257,3 +770,175 @@ impl From<CliToolType> for ToolType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_cli_tool(value: &str) -> Result<CliToolType, clap::Error> {
+        let cli = Cli::try_parse_from(["aiassisted", "skills", "list", "--tool", value])?;
+        match cli.command {
+            Commands::Skills(SkillsArgs {
+                command: SkillsCommands::List { tool },
+            }) => Ok(tool),
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_config_flag_is_global() {
+        let cli = Cli::try_parse_from(["aiassisted", "config", "path", "--config", "/tmp/alt.toml"])
+            .unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("/tmp/alt.toml")));
+    }
+
+    #[test]
+    fn test_config_reset_accepts_yes() {
+        for flag in ["--yes", "-y", "--force", "-f"] {
+            let cli = Cli::try_parse_from(["aiassisted", "config", "reset", flag]).unwrap();
+            assert!(
+                matches!(
+                    cli.command,
+                    Commands::Config(ConfigArgs {
+                        command: ConfigCommands::Reset { force: true }
+                    })
+                ),
+                "flag {}",
+                flag
+            );
+        }
+    }
+
+    #[test]
+    fn test_destructive_commands_accept_yes() {
+        let parse = |args: &[&str]| {
+            Cli::try_parse_from(std::iter::once("aiassisted").chain(args.iter().copied()))
+        };
+
+        let cli = parse(&["update", "--rollback", "-y"]).unwrap();
+        assert!(matches!(cli.command, Commands::Update(UpdateArgs { yes: true, .. })));
+        // Plain updates never prompt, so --yes would mean nothing there
+        assert!(parse(&["update", "--yes"]).is_err());
+
+        let cli = parse(&["agents", "remove", "--all", "-p", "claude-code", "--yes"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Agents(AgentsArgs {
+                command: Some(AgentsCommands::Remove { yes: true, .. })
+            })
+        ));
+
+        assert!(parse(&["skills", "import", "skill.tar.gz", "--force", "-y"]).is_ok());
+        assert!(parse(&["skills", "import", "skill.tar.gz", "--yes"]).is_err());
+    }
+
+    #[test]
+    fn test_agents_remove_requires_name_or_all() {
+        let parse = |args: &[&str]| {
+            Cli::try_parse_from(
+                ["aiassisted", "agents", "remove", "--platform", "claude-code"]
+                    .iter()
+                    .chain(args),
+            )
+        };
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["reviewer", "--all"]).is_err());
+        assert!(parse(&["--all"]).is_ok());
+        assert!(parse(&["reviewer"]).is_ok());
+    }
+
+    #[test]
+    fn test_skills_setup_accepts_all() {
+        let parse = |value: &str| -> Result<CliToolSelection, clap::Error> {
+            let cli = Cli::try_parse_from(["aiassisted", "skills", "setup", "--tool", value])?;
+            match cli.command {
+                Commands::Skills(SkillsArgs {
+                    command: SkillsCommands::Setup { tool, .. },
+                }) => Ok(tool),
+                other => panic!("unexpected command: {:?}", other),
+            }
+        };
+
+        assert_eq!(parse("ALL").unwrap(), CliToolSelection::All);
+        assert_eq!(parse("claude-code").unwrap(), CliToolSelection::Tool(CliToolType::Claude));
+        assert!(parse("vscode").is_err());
+        // Only setup takes `all`
+        assert!(parse_cli_tool("all").is_err());
+    }
+
+    #[test]
+    fn test_tool_aliases() {
+        for (alias, expected) in TOOL_NAMES {
+            assert_eq!(&parse_tool_type(alias).unwrap(), expected, "alias {}", alias);
+        }
+    }
+
+    #[test]
+    fn test_tool_aliases_ignore_case() {
+        for (alias, expected) in TOOL_NAMES {
+            let upper = alias.to_uppercase();
+            assert_eq!(&parse_tool_type(&upper).unwrap(), expected, "alias {}", upper);
+        }
+    }
+
+    #[test]
+    fn test_unknown_tool_lists_valid_values_and_aliases() {
+        let err = parse_tool_type("vscode").unwrap_err();
+        assert!(err.contains("'vscode'"));
+        for (name, _) in TOOL_NAMES {
+            assert!(err.contains(name), "missing {}", name);
+        }
+    }
+
+    #[test]
+    fn test_completions_for_every_shell() {
+        for shell in Shell::value_variants() {
+            let mut out = Vec::new();
+            write_completions(*shell, &mut out);
+
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains("aiassisted"), "{} script", shell);
+            assert!(script.contains("skills"), "{} script", shell);
+        }
+    }
+
+    #[test]
+    fn test_completions_unknown_shell() {
+        let err = Cli::try_parse_from(["aiassisted", "completions", "tcsh"]).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("tcsh"));
+        assert!(message.contains("bash"));
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(parse_rate_limit("2048").unwrap(), 2048);
+        assert_eq!(parse_rate_limit("500K").unwrap(), 500 * 1024);
+        assert_eq!(parse_rate_limit("1MB").unwrap(), 1024 * 1024);
+        assert_eq!(parse_rate_limit("1mb/s").unwrap(), 1024 * 1024);
+        assert_eq!(parse_rate_limit("2G").unwrap(), 2 * 1024 * 1024 * 1024);
+        assert!(parse_rate_limit("0").is_err());
+        assert!(parse_rate_limit("fast").is_err());
+        assert!(parse_rate_limit("").is_err());
+        assert!(parse_rate_limit("99999999999999G").is_err());
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("45").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_interval("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_interval("5M").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_interval("1h").unwrap(), Duration::from_secs(3600));
+        assert!(parse_interval("0s").is_err());
+        assert!(parse_interval("soon").is_err());
+        assert!(parse_interval("").is_err());
+    }
+
+    #[test]
+    fn test_tool_flag_uses_alias_parser() {
+        assert_eq!(parse_cli_tool("Claude-Code").unwrap(), CliToolType::Claude);
+        assert_eq!(parse_cli_tool("auto").unwrap(), CliToolType::Auto);
+        assert!(parse_cli_tool("vscode").is_err());
+    }
+}
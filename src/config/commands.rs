@@ -1,12 +1,15 @@
 //! Command implementations for the config domain.
 
-use std::io::Write;
-use std::process::{Command, Stdio};
+use std::path::Path;
+use std::process::Command;
 
 use crate::core::config::ConfigStore;
-use crate::core::infra::Logger;
+use crate::core::confirm::confirm;
+use crate::core::infra::{FileSystem, Logger};
 use crate::core::types::{Error, Result};
 
+use super::settings;
+
 /// Show command - displays all configuration values.
 pub struct ShowCommand;
 
@@ -25,6 +28,17 @@ impl ShowCommand {
         println!("  verbosity         = {}", config.verbosity);
         println!("  auto_update       = {}", config.auto_update);
         println!("  prefer_project    = {}", config.prefer_project);
+        for (tool, dirs) in &config.output_dirs {
+            if let Some(skills) = &dirs.skills {
+                println!("  output_dirs.{}.skills = {}", tool, skills.display());
+            }
+            if let Some(agents) = &dirs.agents {
+                println!("  output_dirs.{}.agents = {}", tool, agents.display());
+            }
+        }
+        if let Some(temp_dir) = &config.temp_dir {
+            println!("  temp_dir          = {}", temp_dir.display());
+        }
         println!();
         logger.info(&format!(
             "Configuration file: {}",
@@ -64,73 +78,143 @@ impl GetCommand {
 }
 
 /// Edit command - opens configuration file in editor.
+///
+/// The edited file is validated before it is kept; an invalid edit can be
+/// reopened, and is otherwise rolled back to the previous contents.
 pub struct EditCommand;
 
 impl EditCommand {
     /// Execute the edit command.
-    pub async fn execute<C, L>(&self, config_store: &C, logger: &L) -> Result<()>
+    pub async fn execute<F, C, L>(&self, fs: &F, config_store: &C, logger: &L) -> Result<()>
+    where
+        F: FileSystem,
+        C: ConfigStore,
+        L: Logger,
+    {
+        let editors =
+            editor_candidates(std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok());
+        // Without a terminal there is nobody to ask, so treat it as a no
+        let reopen = || confirm("Reopen the editor?", false).unwrap_or(false);
+        self.edit_with(fs, config_store, logger, &editors, reopen).await
+    }
+
+    /// [`Self::execute`] with the editors and reopen prompt supplied by the caller.
+    async fn edit_with<F, C, L, R>(
+        &self,
+        fs: &F,
+        config_store: &C,
+        logger: &L,
+        editors: &[String],
+        mut reopen: R,
+    ) -> Result<()>
     where
+        F: FileSystem,
         C: ConfigStore,
         L: Logger,
+        R: FnMut() -> bool,
     {
         // Ensure config file exists with defaults
         let config = config_store.load().await?;
         config_store.save(&config).await?;
 
         let config_path = config_store.config_path();
+        let original = fs.read(&config_path).await?;
 
-        // Determine editor
-        let editor = std::env::var("EDITOR")
-            .or_else(|_| std::env::var("VISUAL"))
-            .unwrap_or_else(|_| {
-                if cfg!(windows) {
-                    "notepad".to_string()
-                } else {
-                    // Try common editors
-                    for ed in ["vim", "vi", "nano", "emacs"] {
-                        if Command::new("which")
-                            .arg(ed)
-                            .stdout(Stdio::null())
-                            .stderr(Stdio::null())
-                            .status()
-                            .map(|s| s.success())
-                            .unwrap_or(false)
-                        {
-                            return ed.to_string();
-                        }
-                    }
-                    "vi".to_string() // Last resort
-                }
-            });
+        loop {
+            if let Err(e) = launch_editor(editors, &config_path, logger) {
+                // Whatever a failed editor left behind is not a deliberate edit
+                fs.write(&config_path, &original).await?;
+                return Err(e);
+            }
 
-        logger.info(&format!("Opening config in {}...", editor));
+            let contents = fs.read(&config_path).await?;
+            let mut problems: Vec<String> = settings::validate_raw(&contents)
+                .iter()
+                .map(|problem| format!("{}: {}", problem.key, problem.reason))
+                .collect();
+            if problems.is_empty()
+                && let Err(e) = config_store.load().await
+            {
+                problems.push(e.to_string());
+            }
 
-        // Open editor
-        let status = Command::new(&editor)
-            .arg(&config_path)
-            .status()
-            .map_err(|e| Error::Config(format!("Failed to open editor: {}", e)))?;
+            if problems.is_empty() {
+                logger.info("Configuration updated successfully");
+                return Ok(());
+            }
 
-        if !status.success() {
-            return Err(Error::Config(format!(
-                "Editor exited with status: {}",
-                status
-            )));
-        }
+            logger.error(&format!("Invalid configuration ({} problem(s)):", problems.len()));
+            for problem in &problems {
+                logger.error(&format!("  {}", problem));
+            }
 
-        // Validate the edited config
-        match config_store.load().await {
-            Ok(_) => {
-                logger.info("Configuration updated successfully");
-                Ok(())
+            if !reopen() {
+                fs.write(&config_path, &original).await?;
+                logger.warn("Discarded the invalid edit; configuration left unchanged");
+                return Err(Error::Config(format!(
+                    "Edited configuration has {} problem(s)",
+                    problems.len()
+                )));
             }
+        }
+    }
+}
+
+/// Editors to try in order: `$VISUAL`, `$EDITOR`, then the platform defaults.
+fn editor_candidates(visual: Option<String>, editor: Option<String>) -> Vec<String> {
+    let defaults: &[&str] = if cfg!(windows) {
+        &["notepad"]
+    } else {
+        &["vi", "nano"]
+    };
+
+    let mut candidates: Vec<String> = Vec::new();
+    for candidate in [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(|value| value.trim().to_string())
+        .chain(defaults.iter().map(|d| d.to_string()))
+    {
+        if !candidate.is_empty() && !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// Open `path` in the first editor that launches.
+///
+/// Editors may carry arguments (`code --wait`). One that cannot be spawned is
+/// reported and the next is tried; one that runs but fails stops the edit.
+fn launch_editor<L: Logger>(editors: &[String], path: &Path, logger: &L) -> Result<()> {
+    for editor in editors {
+        let mut parts = editor.split_whitespace();
+        let Some(program) = parts.next() else {
+            continue;
+        };
+
+        logger.info(&format!("Opening config in {}...", editor));
+        let status = match Command::new(program).args(parts).arg(path).status() {
+            Ok(status) => status,
             Err(e) => {
-                logger.error(&format!("Invalid configuration: {}", e));
-                logger.warn("Please fix the configuration file manually");
-                Err(e)
+                logger.warn(&format!("Could not launch editor '{}': {}", editor, e));
+                continue;
             }
+        };
+
+        if !status.success() {
+            return Err(Error::Config(format!(
+                "Editor '{}' exited with status: {}",
+                editor, status
+            )));
         }
+        return Ok(());
     }
+
+    Err(Error::Config(format!(
+        "No editor could be launched (tried: {}); set $VISUAL or $EDITOR",
+        editors.join(", ")
+    )))
 }
 
 /// Reset command - resets configuration to defaults.
@@ -146,23 +230,11 @@ impl ResetCommand {
         L: Logger,
     {
         if !self.force {
-            // Prompt for confirmation
             logger.warn("This will reset all configuration to defaults.");
-            print!("Continue? [y/N] ");
-            std::io::stdout()
-                .flush()
-                .map_err(|e| Error::Config(format!("Failed to flush stdout: {}", e)))?;
-
-            let mut input = String::new();
-            std::io::stdin()
-                .read_line(&mut input)
-                .map_err(|e| Error::Config(format!("Failed to read input: {}", e)))?;
-
-            let input = input.trim().to_lowercase();
-            if input != "y" && input != "yes" {
-                logger.info("Reset cancelled");
-                return Ok(());
-            }
+        }
+        if !confirm("Continue?", self.force)? {
+            logger.info("Reset cancelled");
+            return Ok(());
         }
 
         config_store.reset().await?;
@@ -185,6 +257,55 @@ impl PathCommand {
     }
 }
 
+/// Validate command - checks the configuration file for invalid values.
+pub struct ValidateCommand;
+
+impl ValidateCommand {
+    /// Execute the validate command.
+    ///
+    /// Reports every invalid key and fails if any problem is found.
+    pub async fn execute<F, C, L>(&self, fs: &F, config_store: &C, logger: &L) -> Result<()>
+    where
+        F: FileSystem,
+        C: ConfigStore,
+        L: Logger,
+    {
+        let config_path = config_store.config_path();
+
+        if !fs.exists(&config_path) {
+            logger.info(&format!(
+                "No configuration file at {}; defaults are in use",
+                config_path.display()
+            ));
+            return Ok(());
+        }
+
+        let contents = fs.read(&config_path).await?;
+        let problems = settings::validate_raw(&contents);
+
+        if !problems.is_empty() {
+            logger.error(&format!(
+                "Found {} problem(s) in {}:",
+                problems.len(),
+                config_path.display()
+            ));
+            for problem in &problems {
+                logger.error(&format!("  {}: {}", problem.key, problem.reason));
+            }
+            return Err(Error::Config(format!(
+                "Configuration has {} problem(s)",
+                problems.len()
+            )));
+        }
+
+        // Make sure the store accepts it as well
+        config_store.load().await?;
+
+        logger.success(&format!("Configuration is valid: {}", config_path.display()));
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,4 +446,119 @@ mod tests {
         let result = cmd.execute(&mock_store).await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_editor_candidates_order() {
+        let defaults: Vec<String> = if cfg!(windows) {
+            vec!["notepad".to_string()]
+        } else {
+            vec!["vi".to_string(), "nano".to_string()]
+        };
+
+        assert_eq!(editor_candidates(None, None), defaults);
+
+        let candidates = editor_candidates(Some("code --wait".into()), Some("  ".into()));
+        assert_eq!(candidates[0], "code --wait");
+        assert_eq!(candidates[1..], defaults[..]);
+
+        // A variable naming a default is not tried twice
+        let candidates = editor_candidates(Some("hx".into()), Some(defaults[0].clone()));
+        assert_eq!(candidates[..2], ["hx".to_string(), defaults[0].clone()]);
+        assert_eq!(candidates.len(), defaults.len() + 1);
+    }
+
+    /// Write an executable script that replaces the edited file with `contents`.
+    #[cfg(unix)]
+    fn fake_editor(dir: &Path, name: &str, contents: &str) -> String {
+        fake_editor_exiting(dir, name, contents, 0)
+    }
+
+    /// Like [`fake_editor`], but the script exits with `code`.
+    #[cfg(unix)]
+    fn fake_editor_exiting(dir: &Path, name: &str, contents: &str, code: i32) -> String {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = dir.join(name);
+        std::fs::write(
+            &script,
+            format!("#!/bin/sh\nprintf '%s' '{}' > \"$1\"\nexit {}\n", contents, code),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        script.display().to_string()
+    }
+
+    #[cfg(unix)]
+    struct NullLogger;
+    #[cfg(unix)]
+    impl Logger for NullLogger {
+        fn info(&self, _msg: &str) {}
+        fn warn(&self, _msg: &str) {}
+        fn error(&self, _msg: &str) {}
+        fn debug(&self, _msg: &str) {}
+        fn success(&self, _msg: &str) {}
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_edit_keeps_valid_and_rolls_back_invalid() {
+        use crate::config::TomlConfigStore;
+        use crate::core::types::ToolType;
+        use crate::infra::StdFileSystem;
+
+        let fs = StdFileSystem::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = TomlConfigStore::with_path(fs.clone(), temp_dir.path().join("config.toml"));
+        let valid = fake_editor(temp_dir.path(), "valid.sh", "default_tool = \"claude\"");
+        let invalid = fake_editor(temp_dir.path(), "invalid.sh", "verbosity = 9");
+
+        // A missing editor is skipped in favour of the next one
+        let editors = vec!["/nonexistent/editor".to_string(), valid];
+        EditCommand
+            .edit_with(&fs, &store, &NullLogger, &editors, || panic!("not asked"))
+            .await
+            .unwrap();
+        assert_eq!(store.load().await.unwrap().default_tool, ToolType::Claude);
+
+        let mut asked = 0;
+        let result = EditCommand
+            .edit_with(&fs, &store, &NullLogger, &[invalid], || {
+                asked += 1;
+                asked < 2
+            })
+            .await;
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("1 problem")));
+        assert_eq!(asked, 2);
+        let kept = store.load().await.unwrap();
+        assert_eq!((kept.default_tool, kept.verbosity), (ToolType::Claude, 1));
+
+        let editors = vec!["/nonexistent/editor".to_string()];
+        let result = EditCommand
+            .edit_with(&fs, &store, &NullLogger, &editors, || false)
+            .await;
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("/nonexistent/editor")));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_edit_restores_config_when_editor_fails() {
+        use crate::config::TomlConfigStore;
+        use crate::infra::StdFileSystem;
+
+        let fs = StdFileSystem::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        let store = TomlConfigStore::with_path(fs.clone(), config_path.clone());
+        let failing = fake_editor_exiting(temp_dir.path(), "failing.sh", "verbosity = ", 1);
+
+        store.save(&AppConfig::default()).await.unwrap();
+        let original = fs.read(&config_path).await.unwrap();
+
+        let result = EditCommand
+            .edit_with(&fs, &store, &NullLogger, &[failing], || panic!("not asked"))
+            .await;
+
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("exited with status")));
+        assert_eq!(fs.read(&config_path).await.unwrap(), original);
+    }
 }
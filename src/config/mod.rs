@@ -7,5 +7,7 @@ pub mod commands;
 pub mod settings;
 pub mod toml_store;
 
-pub use commands::{EditCommand, GetCommand, PathCommand, ResetCommand, ShowCommand};
+pub use commands::{
+    EditCommand, GetCommand, PathCommand, ResetCommand, ShowCommand, ValidateCommand,
+};
 pub use toml_store::TomlConfigStore;
@@ -1,6 +1,8 @@
 //! Configuration settings and validation.
 
-use crate::core::types::{AppConfig, Error, Result};
+use std::path::{Component, Path, PathBuf};
+
+use crate::core::types::{AppConfig, Error, Result, ToolType};
 
 /// Validates configuration values.
 pub fn validate_config(config: &AppConfig) -> Result<()> {
@@ -12,9 +14,211 @@ pub fn validate_config(config: &AppConfig) -> Result<()> {
         )));
     }
 
+    // Validate output directory overrides
+    for (tool, dirs) in &config.output_dirs {
+        if let Some(reason) = check_override_tool(tool) {
+            return Err(Error::Config(format!("output_dirs.{}: {}", tool, reason)));
+        }
+        for (kind, dir) in [("skills", &dirs.skills), ("agents", &dirs.agents)] {
+            if let Some(reason) = dir.as_deref().and_then(check_override_path) {
+                return Err(Error::Config(format!(
+                    "output_dirs.{}.{}: {}",
+                    tool, kind, reason
+                )));
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Checks that an output directory override names a concrete tool.
+fn check_override_tool(tool: &str) -> Option<String> {
+    match tool.parse::<ToolType>() {
+        Ok(ToolType::OpenCode | ToolType::Claude) => None,
+        _ => Some(format!("Unknown tool '{}'. Expected opencode or claude.", tool)),
+    }
+}
+
+/// Checks that an output directory override stays inside the project.
+///
+/// A leading `~` or `$HOME` is rejected too: it reads as relative, but
+/// would point into the home directory once expanded.
+fn check_override_path(path: &Path) -> Option<String> {
+    let escapes = path.components().any(|c| {
+        matches!(
+            c,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_)
+        )
+    });
+    let from_home = path
+        .components()
+        .next()
+        .and_then(|c| c.as_os_str().to_str())
+        .is_some_and(|first| first.starts_with('~') || first.starts_with('$'));
+
+    if escapes || from_home || path.as_os_str().is_empty() {
+        Some(format!(
+            "'{}' must be a relative path inside the project",
+            path.display()
+        ))
+    } else {
+        None
+    }
+}
+
+/// A single problem found while validating a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// The configuration key the problem relates to.
+    pub key: String,
+    /// Why the value is invalid.
+    pub reason: String,
+}
+
+impl ConfigProblem {
+    fn new(key: &str, reason: impl Into<String>) -> Self {
+        Self {
+            key: key.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Validates raw configuration file contents, reporting every problem found.
+///
+/// Unlike [`validate_config`], this inspects the TOML before deserialization so
+/// that each invalid or unknown key can be reported by name.
+pub fn validate_raw(contents: &str) -> Vec<ConfigProblem> {
+    let table: toml::Table = match toml::from_str(contents) {
+        Ok(table) => table,
+        Err(e) => return vec![ConfigProblem::new("(file)", format!("Invalid TOML: {}", e))],
+    };
+
+    let mut problems = Vec::new();
+
+    for (key, value) in &table {
+        match key.as_str() {
+            "default_tool" => match value.as_str() {
+                Some(tool) if tool.parse::<ToolType>().is_ok() => {}
+                Some(tool) => problems.push(ConfigProblem::new(
+                    key,
+                    format!("Unknown tool '{}'. Expected auto, opencode, or claude.", tool),
+                )),
+                None => problems.push(ConfigProblem::new(key, "Must be a string")),
+            },
+            "verbosity" => match value.as_integer() {
+                Some(level) if (0..=2).contains(&level) => {}
+                Some(level) => problems.push(ConfigProblem::new(
+                    key,
+                    format!("Invalid verbosity level: {}. Must be 0-2.", level),
+                )),
+                None => problems.push(ConfigProblem::new(key, "Must be an integer")),
+            },
+            "auto_update" | "prefer_project" => {
+                if !value.is_bool() {
+                    problems.push(ConfigProblem::new(key, "Must be true or false"));
+                }
+            }
+            "output_dirs" => validate_raw_output_dirs(value, &mut problems),
+            "temp_dir" => match value.as_str() {
+                Some("") => problems.push(ConfigProblem::new(key, "Must not be empty")),
+                Some(_) => {}
+                None => problems.push(ConfigProblem::new(key, "Must be a string")),
+            },
+            _ => problems.push(ConfigProblem::new(key, "Unknown configuration key")),
+        }
+    }
+
+    problems
+}
+
+/// Validates the raw `[output_dirs.<tool>]` tables.
+fn validate_raw_output_dirs(value: &toml::Value, problems: &mut Vec<ConfigProblem>) {
+    let Some(tools) = value.as_table() else {
+        problems.push(ConfigProblem::new("output_dirs", "Must be a table"));
+        return;
+    };
+
+    for (tool, dirs) in tools {
+        let tool_key = format!("output_dirs.{}", tool);
+
+        if let Some(reason) = check_override_tool(tool) {
+            problems.push(ConfigProblem::new(&tool_key, reason));
+            continue;
+        }
+
+        let Some(dirs) = dirs.as_table() else {
+            problems.push(ConfigProblem::new(&tool_key, "Must be a table"));
+            continue;
+        };
+
+        for (kind, dir) in dirs {
+            let key = format!("{}.{}", tool_key, kind);
+            match (kind.as_str(), dir.as_str()) {
+                ("skills" | "agents", Some(dir)) => {
+                    if let Some(reason) = check_override_path(Path::new(dir)) {
+                        problems.push(ConfigProblem::new(&key, reason));
+                    }
+                }
+                ("skills" | "agents", None) => {
+                    problems.push(ConfigProblem::new(&key, "Must be a string"))
+                }
+                _ => problems.push(ConfigProblem::new(&key, "Unknown configuration key")),
+            }
+        }
+    }
+}
+
+/// Every path-typed field in the config, so home expansion treats them all alike.
+///
+/// Output directory overrides are project-relative and never expanded.
+fn path_fields(config: &mut AppConfig) -> impl Iterator<Item = &mut PathBuf> {
+    config.temp_dir.as_mut().into_iter()
+}
+
+/// Expands a leading `~` or `$HOME` in `path` to the given home directory.
+fn expand_home(path: &Path, home: &Path) -> PathBuf {
+    let Some(text) = path.to_str() else {
+        return path.to_path_buf();
+    };
+
+    for prefix in ["~", "$HOME"] {
+        if text == prefix {
+            return home.to_path_buf();
+        }
+        if let Some(rest) = text.strip_prefix(prefix).and_then(|r| r.strip_prefix('/')) {
+            return home.join(rest);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Expands `~` and `$HOME` in every path field after loading.
+///
+/// Paths are left as written if the home directory cannot be determined.
+pub fn expand_paths(config: &mut AppConfig) {
+    let Some(home) = dirs::home_dir() else {
+        return;
+    };
+    for path in path_fields(config) {
+        *path = expand_home(path, &home);
+    }
+}
+
+/// Rewrites paths under the home directory back to `~/...` before saving,
+/// so a load/save round trip leaves the file as the user wrote it.
+pub fn contract_paths(config: &mut AppConfig) {
+    let Some(home) = dirs::home_dir() else {
+        return;
+    };
+    for path in path_fields(config) {
+        if let Ok(rest) = path.strip_prefix(&home) {
+            *path = Path::new("~").join(rest);
+        }
+    }
+}
+
 /// Gets a configuration value by key using dot notation.
 pub fn get_value(config: &AppConfig, key: &str) -> Option<String> {
     match key {
@@ -46,6 +250,117 @@ mod tests {
         assert!(validate_config(&config).is_err());
     }
 
+    #[test]
+    fn test_validate_raw_valid() {
+        let contents = r#"
+default_tool = "claude"
+verbosity = 2
+auto_update = false
+prefer_project = true
+"#;
+        assert!(validate_raw(contents).is_empty());
+    }
+
+    #[test]
+    fn test_validate_raw_empty_file() {
+        assert!(validate_raw("").is_empty());
+    }
+
+    #[test]
+    fn test_validate_raw_reports_each_problem() {
+        let contents = r#"
+default_tool = "vscode"
+verbosity = -1
+auto_update = "yes"
+colour = true
+"#;
+        let mut problems = validate_raw(contents);
+        problems.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let keys: Vec<&str> = problems.iter().map(|p| p.key.as_str()).collect();
+        assert_eq!(keys, vec!["auto_update", "colour", "default_tool", "verbosity"]);
+        assert!(problems[2].reason.contains("vscode"));
+        assert!(problems[3].reason.contains("-1"));
+    }
+
+    #[test]
+    fn test_validate_raw_invalid_toml() {
+        let problems = validate_raw("this is not toml [");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].key, "(file)");
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = Path::new("/home/user");
+        assert_eq!(expand_home(Path::new("~"), home), PathBuf::from("/home/user"));
+        assert_eq!(
+            expand_home(Path::new("~/custom/skills"), home),
+            PathBuf::from("/home/user/custom/skills")
+        );
+        assert_eq!(
+            expand_home(Path::new("$HOME/custom"), home),
+            PathBuf::from("/home/user/custom")
+        );
+        // Only a whole leading component is expanded
+        assert_eq!(expand_home(Path::new("~other/x"), home), PathBuf::from("~other/x"));
+        assert_eq!(expand_home(Path::new("tools/~"), home), PathBuf::from("tools/~"));
+    }
+
+    #[test]
+    fn test_validate_config_output_dir_override() {
+        let mut config = AppConfig::default();
+        config.output_dirs.insert(
+            "claude".to_string(),
+            crate::core::types::ToolDirs {
+                skills: Some("tools/skills".into()),
+                agents: None,
+            },
+        );
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_config_rejects_escaping_override() {
+        for dir in ["/etc/skills", "../outside", "a/../../b", "~", "~/x", "~user/x", "$HOME/x"] {
+            let mut config = AppConfig::default();
+            config.output_dirs.insert(
+                "claude".to_string(),
+                crate::core::types::ToolDirs {
+                    skills: Some(dir.into()),
+                    agents: None,
+                },
+            );
+            assert!(validate_config(&config).is_err(), "{} should be rejected", dir);
+        }
+    }
+
+    #[test]
+    fn test_validate_config_rejects_unknown_override_tool() {
+        let mut config = AppConfig::default();
+        config
+            .output_dirs
+            .insert("vscode".to_string(), Default::default());
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_raw_output_dirs() {
+        let contents = r#"
+[output_dirs.claude]
+skills = "tools/skills"
+agents = "../agents"
+
+[output_dirs.auto]
+skills = "x"
+"#;
+        let mut problems = validate_raw(contents);
+        problems.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let keys: Vec<&str> = problems.iter().map(|p| p.key.as_str()).collect();
+        assert_eq!(keys, vec!["output_dirs.auto", "output_dirs.claude.agents"]);
+    }
+
     #[test]
     fn test_get_value_default_tool() {
         let config = AppConfig::default();
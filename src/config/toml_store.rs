@@ -33,11 +33,20 @@ impl<F: FileSystem> TomlConfigStore<F> {
     /// Create a new TOML config store with a custom path.
     ///
     /// Useful for testing or when you need a custom config file location.
-    #[cfg_attr(not(test), allow(dead_code))]
     pub fn with_path(fs: F, config_path: PathBuf) -> Self {
         Self { fs, config_path }
     }
 
+    /// Create a config store at `config_path` if given, else at the default location.
+    ///
+    /// Used to apply the `--config` flag.
+    pub fn with_override(fs: F, config_path: Option<PathBuf>) -> Result<Self> {
+        match config_path {
+            Some(path) => Ok(Self::with_path(fs, path)),
+            None => Self::new(fs),
+        }
+    }
+
     /// Ensure the config directory exists.
     async fn ensure_config_dir(&self) -> Result<()> {
         if let Some(parent) = self.config_path.parent().filter(|p| !self.fs.exists(p)) {
@@ -53,10 +62,12 @@ impl<F: FileSystem> TomlConfigStore<F> {
         }
 
         let contents = self.fs.read(&self.config_path).await?;
-        let config: AppConfig = toml::from_str(&contents)
-            .map_err(|e| Error::Serialization(format!("Failed to parse config: {}", e)))?;
+        let mut config: AppConfig = toml::from_str(&contents)
+            .map_err(|e| Error::toml(self.config_path.display(), &contents, &e))?;
 
+        // Validate as written (overrides may not start with `~`); expand afterwards
         settings::validate_config(&config)?;
+        settings::expand_paths(&mut config);
         Ok(config)
     }
 }
@@ -68,11 +79,13 @@ impl<F: FileSystem> ConfigStore for TomlConfigStore<F> {
     }
 
     async fn save(&self, config: &AppConfig) -> Result<()> {
-        settings::validate_config(config)?;
+        let mut config = config.clone();
+        settings::contract_paths(&mut config);
+        settings::validate_config(&config)?;
 
         self.ensure_config_dir().await?;
 
-        let contents = toml::to_string_pretty(config)
+        let contents = toml::to_string_pretty(&config)
             .map_err(|e| Error::Serialization(format!("Failed to serialize config: {}", e)))?;
 
         self.fs.write(&self.config_path, &contents).await?;
@@ -114,9 +127,22 @@ mod tests {
             async fn create_dir_all(&self, path: &Path) -> Result<()>;
             async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
             async fn copy(&self, from: &Path, to: &Path) -> Result<()>;
+            async fn remove_file(&self, path: &Path) -> Result<()>;
+            async fn remove_dir_all(&self, path: &Path) -> Result<()>;
+            async fn rename(&self, from: &Path, to: &Path) -> Result<()>;
         }
     }
 
+    #[test]
+    fn test_with_override_uses_given_path() {
+        let config_path = PathBuf::from("/custom/config.toml");
+        let store =
+            TomlConfigStore::with_override(MockFileSystem::new(), Some(config_path.clone()))
+                .unwrap();
+
+        assert_eq!(store.config_path(), config_path);
+    }
+
     #[tokio::test]
     async fn test_load_default_when_file_missing() {
         let mut mock_fs = MockFileSystem::new();
@@ -156,6 +182,55 @@ prefer_project = false
         assert!(!config.prefer_project);
     }
 
+    #[tokio::test]
+    async fn test_load_expands_home_in_paths() {
+        let mut mock_fs = MockFileSystem::new();
+        mock_fs.expect_exists().returning(|_| true);
+        mock_fs.expect_read().returning(|_| {
+            Ok(r#"
+temp_dir = "~/custom/templates"
+
+[output_dirs.claude]
+agents = "tools/agents"
+"#
+            .to_string())
+        });
+        let written = std::sync::Arc::new(std::sync::Mutex::new(String::new()));
+        let sink = written.clone();
+        mock_fs.expect_write().returning(move |_, contents| {
+            *sink.lock().unwrap() = contents.to_string();
+            Ok(())
+        });
+
+        let store = TomlConfigStore::with_path(mock_fs, PathBuf::from("/test/config.toml"));
+        let config = store.load().await.unwrap();
+
+        let home = dirs::home_dir().unwrap();
+        let temp_dir = config.temp_dir.as_deref().unwrap();
+        assert_eq!(temp_dir, home.join("custom/templates").as_path());
+        assert!(temp_dir.is_absolute());
+        // Project-relative paths are untouched
+        let dirs = &config.output_dirs["claude"];
+        assert_eq!(dirs.agents.as_deref(), Some(Path::new("tools/agents")));
+
+        // Saving writes the path back the way the user wrote it
+        store.save(&config).await.unwrap();
+        assert!(written.lock().unwrap().contains("temp_dir = \"~/custom/templates\""));
+    }
+
+    #[tokio::test]
+    async fn test_load_rejects_home_relative_override() {
+        let mut mock_fs = MockFileSystem::new();
+        mock_fs.expect_exists().returning(|_| true);
+        mock_fs.expect_read().returning(|_| {
+            Ok("[output_dirs.claude]\nskills = \"~/custom/skills\"\n".to_string())
+        });
+
+        let store = TomlConfigStore::with_path(mock_fs, PathBuf::from("/test/config.toml"));
+        let result = store.load().await;
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("inside the project")));
+    }
+
     #[tokio::test]
     async fn test_load_invalid_toml() {
         let mut mock_fs = MockFileSystem::new();
@@ -192,6 +267,7 @@ prefer_project = false
             verbosity: 2,
             auto_update: true,
             prefer_project: true,
+            ..Default::default()
         };
 
         let result = store.save(&config).await;
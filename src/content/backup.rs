@@ -0,0 +1,211 @@
+//! Snapshots of the .aiassisted directory taken before updates.
+//!
+//! Backups live next to the content in `.aiassisted.backup/<timestamp>/`,
+//! so they are never picked up as installed content. Timestamps sort
+//! chronologically, which is how the newest and oldest backups are found.
+
+use std::path::{Path, PathBuf};
+
+use crate::core::infra::FileSystem;
+use crate::core::project::ensure_removable;
+use crate::core::types::{Error, Result};
+
+/// Directory (relative to the project) holding update backups.
+pub const BACKUP_DIR: &str = ".aiassisted.backup";
+
+/// Number of backups kept by default.
+pub const DEFAULT_KEEP_BACKUPS: usize = 3;
+
+/// Copy the current `.aiassisted` directory into a new timestamped backup.
+///
+/// Returns the backup directory.
+pub async fn create_backup<F: FileSystem>(fs: &F, target_dir: &Path) -> Result<PathBuf> {
+    let source = target_dir.join(".aiassisted");
+    if !fs.is_dir(&source) {
+        return Err(Error::NotFound(format!(
+            "Nothing to back up: {} does not exist",
+            source.display()
+        )));
+    }
+
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f").to_string();
+    let backup_dir = target_dir.join(BACKUP_DIR).join(timestamp);
+
+    fs.create_dir_all(&backup_dir).await?;
+    for file in fs.walk_dir(&source).await? {
+        // Symlinks are skipped, like every other recursive copy
+        if fs.is_symlink(&file) {
+            continue;
+        }
+        if let Ok(relative) = file.strip_prefix(&source) {
+            fs.copy(&file, &backup_dir.join(relative)).await?;
+        }
+    }
+
+    Ok(backup_dir)
+}
+
+/// List backups, oldest first.
+pub async fn list_backups<F: FileSystem>(fs: &F, target_dir: &Path) -> Result<Vec<PathBuf>> {
+    let root = target_dir.join(BACKUP_DIR);
+    if !fs.is_dir(&root) {
+        return Ok(Vec::new());
+    }
+
+    let mut backups: Vec<PathBuf> = fs
+        .list_dir(&root)
+        .await?
+        .into_iter()
+        .filter(|path| fs.is_dir(path))
+        .collect();
+    backups.sort();
+
+    Ok(backups)
+}
+
+/// Remove the oldest backups so that at most `keep` remain.
+///
+/// Returns the removed backup directories.
+pub async fn prune_backups<F: FileSystem>(
+    fs: &F,
+    target_dir: &Path,
+    keep: usize,
+) -> Result<Vec<PathBuf>> {
+    let backups = list_backups(fs, target_dir).await?;
+    let excess = backups.len().saturating_sub(keep);
+
+    let mut removed = Vec::new();
+    for backup in backups.into_iter().take(excess) {
+        ensure_removable(&backup, &target_dir.join(BACKUP_DIR))?;
+        fs.remove_dir_all(&backup).await?;
+        removed.push(backup);
+    }
+
+    Ok(removed)
+}
+
+/// Replace `.aiassisted` with the most recent backup.
+///
+/// The backup is moved into place, so rolling back again restores the
+/// backup before it. Returns the restored backup directory.
+pub async fn restore_latest<F: FileSystem>(fs: &F, target_dir: &Path) -> Result<PathBuf> {
+    let latest = list_backups(fs, target_dir)
+        .await?
+        .pop()
+        .ok_or_else(|| Error::NotFound("No backups available to roll back to".to_string()))?;
+
+    let content_dir = target_dir.join(".aiassisted");
+    if fs.exists(&content_dir) {
+        ensure_removable(&content_dir, target_dir)?;
+        fs.remove_dir_all(&content_dir).await?;
+    }
+    fs.rename(&latest, &content_dir).await?;
+
+    Ok(latest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::StdFileSystem;
+    use tempfile::TempDir;
+
+    async fn install_content(fs: &StdFileSystem, root: &Path, content: &str) {
+        fs.write(&root.join(".aiassisted/manifest.json"), "{}")
+            .await
+            .unwrap();
+        fs.write(&root.join(".aiassisted/guidelines/guide.md"), content)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_backup_copies_content() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        install_content(&fs, temp_dir.path(), "v1").await;
+
+        let backup = create_backup(&fs, temp_dir.path()).await.unwrap();
+
+        assert!(backup.starts_with(temp_dir.path().join(BACKUP_DIR)));
+        assert_eq!(
+            fs.read(&backup.join("guidelines/guide.md")).await.unwrap(),
+            "v1"
+        );
+        assert!(fs.is_file(&backup.join("manifest.json")));
+    }
+
+    #[tokio::test]
+    async fn test_create_backup_without_content() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = create_backup(&fs, temp_dir.path()).await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_prune_keeps_newest() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join(BACKUP_DIR);
+        for name in [
+            "20240101_000000_000",
+            "20240102_000000_000",
+            "20240103_000000_000",
+        ] {
+            fs.create_dir_all(&root.join(name)).await.unwrap();
+        }
+
+        let removed = prune_backups(&fs, temp_dir.path(), 2).await.unwrap();
+
+        assert_eq!(removed, vec![root.join("20240101_000000_000")]);
+        assert_eq!(
+            list_backups(&fs, temp_dir.path()).await.unwrap(),
+            vec![
+                root.join("20240102_000000_000"),
+                root.join("20240103_000000_000")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_latest_replaces_content() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        install_content(&fs, temp_dir.path(), "v1").await;
+        create_backup(&fs, temp_dir.path()).await.unwrap();
+
+        // Simulate a bad update
+        fs.write(
+            &temp_dir.path().join(".aiassisted/guidelines/guide.md"),
+            "broken",
+        )
+        .await
+        .unwrap();
+        fs.write(&temp_dir.path().join(".aiassisted/extra.md"), "new")
+            .await
+            .unwrap();
+
+        restore_latest(&fs, temp_dir.path()).await.unwrap();
+
+        let content_dir = temp_dir.path().join(".aiassisted");
+        assert_eq!(
+            fs.read(&content_dir.join("guidelines/guide.md"))
+                .await
+                .unwrap(),
+            "v1"
+        );
+        assert!(!fs.exists(&content_dir.join("extra.md")));
+        assert!(list_backups(&fs, temp_dir.path()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restore_without_backups() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = restore_latest(&fs, temp_dir.path()).await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+}
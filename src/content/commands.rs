@@ -1,15 +1,30 @@
 //! Command implementations for the content domain.
 
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use serde::Serialize;
+
+use crate::core::confirm::confirm;
 use crate::core::infra::{Checksum, FileSystem, HttpClient, Logger};
-use crate::core::types::Result;
+use crate::core::types::{Error, OutputFormat, Result};
 
-use super::sync;
+use super::backup;
+use super::github::ContentSource;
+use super::manifest::{ChecksumFailure, Manifest, MANIFEST_FILE};
+use super::sync::{self, CheckReport, InstallReport, UpdateReport};
 
 /// Install command - installs .aiassisted to a target directory.
 pub struct InstallCommand {
     pub path: PathBuf,
+    pub dry_run: bool,
+    /// Maximum number of files downloaded at once.
+    pub concurrency: usize,
+    /// Keep downloading after a file fails and report every failure at the end.
+    pub keep_going: bool,
+    /// Where the manifest and content files are downloaded from.
+    pub source: ContentSource,
 }
 
 impl InstallCommand {
@@ -20,7 +35,7 @@ impl InstallCommand {
         http: &H,
         checksum: &C,
         logger: &L,
-    ) -> Result<()>
+    ) -> Result<InstallReport>
     where
         F: FileSystem,
         H: HttpClient,
@@ -28,11 +43,25 @@ impl InstallCommand {
         L: Logger,
     {
         logger.info(&format!(
-            "Installing .aiassisted to {}",
-            self.path.display()
+            "Installing .aiassisted to {}{}",
+            self.path.display(),
+            if self.dry_run { " (dry run)" } else { "" }
         ));
 
-        sync::install(fs, http, checksum, logger, &self.path).await
+        if self.dry_run {
+            return sync::install_dry_run(fs, http, logger, &self.source, &self.path).await;
+        }
+
+        sync::install(
+            fs,
+            http,
+            checksum,
+            logger,
+            &self.source,
+            &self.path,
+            self.concurrency,
+            self.keep_going,
+        ).await
     }
 }
 
@@ -40,6 +69,24 @@ impl InstallCommand {
 pub struct UpdateCommand {
     pub path: PathBuf,
     pub force: bool,
+    /// List the files that would change without downloading or writing them.
+    pub dry_run: bool,
+    /// Delete installed files that the remote manifest no longer lists.
+    pub prune: bool,
+    /// Maximum number of files downloaded at once.
+    pub concurrency: usize,
+    /// Keep downloading after a file fails and report every failure at the end.
+    pub keep_going: bool,
+    /// Snapshot the current content before updating.
+    pub backup: bool,
+    /// Number of backups to retain after taking a new one.
+    pub keep_backups: usize,
+    /// Restore the most recent backup instead of updating.
+    pub rollback: bool,
+    /// Roll back without asking (`--yes`).
+    pub yes: bool,
+    /// Where the manifest and content files are downloaded from.
+    pub source: ContentSource,
 }
 
 impl UpdateCommand {
@@ -50,41 +97,644 @@ impl UpdateCommand {
         http: &H,
         checksum: &C,
         logger: &L,
-    ) -> Result<()>
+    ) -> Result<UpdateReport>
     where
         F: FileSystem,
         H: HttpClient,
         C: Checksum,
         L: Logger,
     {
+        if self.rollback {
+            let question = format!(
+                "Replace {} with the most recent backup?",
+                self.path.join(".aiassisted").display()
+            );
+            if !confirm(&question, self.yes)? {
+                logger.info("Rollback cancelled");
+                return Ok(UpdateReport::default());
+            }
+            let restored = backup::restore_latest(fs, &self.path).await?;
+            logger.success(&format!("Rolled back to backup {}", restored.display()));
+            return Ok(UpdateReport::default());
+        }
+
         logger.info(&format!(
-            "Updating .aiassisted in {}{}",
+            "Updating .aiassisted in {}{}{}",
             self.path.display(),
-            if self.force { " (forced)" } else { "" }
+            if self.force { " (forced)" } else { "" },
+            if self.dry_run { " (dry run)" } else { "" }
         ));
 
-        sync::update(fs, http, checksum, logger, &self.path, self.force).await
+        if self.dry_run {
+            return sync::update_dry_run(
+                fs,
+                http,
+                logger,
+                &self.source,
+                &self.path,
+                self.force,
+                self.prune,
+            )
+            .await;
+        }
+
+        // Only snapshot content the update is about to change: every no-op
+        // backup would evict an older one that `--rollback` may still need
+        let mut source = self.source.clone();
+        let mut backup_dir = None;
+        let manifest_path = self.path.join(".aiassisted").join(MANIFEST_FILE);
+        if self.backup && fs.exists(&manifest_path) {
+            let local = Manifest::load_local(fs, &manifest_path).await?;
+            let remote = source.load_manifest(http).await?;
+            let diff = local.diff(&remote);
+            let changes = self.force
+                || !diff.files_to_download().is_empty()
+                || (self.prune && !diff.removed_files.is_empty());
+            // Reuse the fetched manifest rather than downloading it again
+            source = source.with_manifest(remote);
+
+            if changes {
+                let dir = backup::create_backup(fs, &self.path).await?;
+                logger.info(&format!("Backed up current content to {}", dir.display()));
+
+                let keep = self.keep_backups.max(1);
+                for removed in backup::prune_backups(fs, &self.path, keep).await? {
+                    logger.debug(&format!("Removed old backup {}", removed.display()));
+                }
+                backup_dir = Some(dir);
+            }
+        }
+
+        let report = sync::update(
+            fs,
+            http,
+            checksum,
+            logger,
+            &source,
+            &self.path,
+            self.force,
+            self.prune,
+            self.concurrency,
+            self.keep_going,
+        )
+        .await?;
+
+        Ok(UpdateReport {
+            backup: backup_dir,
+            ..report
+        })
     }
 }
 
 /// Check command - checks for updates without downloading.
 pub struct CheckCommand {
     pub path: PathBuf,
+    /// Report the verification status of every installed file.
+    pub verbose: bool,
+    /// `Json` or `Yaml` prints the manifest diff in that format instead of log lines.
+    pub format: OutputFormat,
+    /// Log nothing and fail unless the install is intact and up to date.
+    pub quiet: bool,
+    /// Where the manifest and content files are downloaded from.
+    pub source: ContentSource,
+    /// Only verify local checksums and skip the remote manifest (`--offline`).
+    pub offline: bool,
 }
 
 impl CheckCommand {
     /// Execute the check command.
-    pub async fn execute<F, H, L>(&self, fs: &F, http: &H, logger: &L) -> Result<()>
+    pub async fn execute<F, H, C, L>(
+        &self,
+        fs: &F,
+        http: &H,
+        checksum: &C,
+        logger: &L,
+    ) -> Result<CheckReport>
     where
         F: FileSystem,
         H: HttpClient,
+        C: Checksum,
         L: Logger,
     {
+        if self.quiet {
+            return self.check_quiet(fs, http, checksum).await;
+        }
+        if self.format != OutputFormat::Text {
+            return self.print_diff(fs, http).await;
+        }
+
         logger.info(&format!(
             "Checking for updates in {}",
             self.path.display()
         ));
 
-        sync::check(fs, http, logger, &self.path).await
+        let aiassisted_dir = self.path.join(".aiassisted");
+        let manifest_path = aiassisted_dir.join(MANIFEST_FILE);
+        let mut local_version = String::new();
+        let mut failed = Vec::new();
+        if fs.exists(&manifest_path) {
+            let manifest = Manifest::load_local(fs, &manifest_path).await?;
+            let failures = manifest
+                .verify_checksums(fs, checksum, &aiassisted_dir)
+                .await;
+            self.report_verification(&manifest, &failures, logger);
+            failed = failures
+                .into_iter()
+                .map(|f| aiassisted_dir.join(f.entry.path))
+                .collect();
+            local_version = manifest.version;
+        }
+
+        if self.offline {
+            if local_version.is_empty() {
+                logger.warn("Directory .aiassisted not found. Use 'install' first.");
+            } else {
+                logger.info(&format!("Local: v{}", local_version));
+            }
+            logger.info("Remote not checked (offline)");
+            return Ok(CheckReport {
+                local_version,
+                failed,
+                ..Default::default()
+            });
+        }
+
+        let report = sync::check(fs, http, logger, &self.source, &self.path).await?;
+        Ok(CheckReport { failed, ..report })
+    }
+
+    /// Exit-code-only check for CI (`--quiet`).
+    ///
+    /// Fails with `NotFound` when nothing is installed, `ChecksumMismatch` for
+    /// the first local file that does not verify, and `Outdated` when the
+    /// remote manifest differs. Offline, the remote manifest is not consulted.
+    async fn check_quiet<F: FileSystem, H: HttpClient, C: Checksum>(
+        &self,
+        fs: &F,
+        http: &H,
+        checksum: &C,
+    ) -> Result<CheckReport> {
+        let aiassisted_dir = self.path.join(".aiassisted");
+        let manifest_path = aiassisted_dir.join(MANIFEST_FILE);
+        if !fs.exists(&manifest_path) {
+            return Err(Error::NotFound(format!(
+                "{} not found. Use 'install' first.",
+                manifest_path.display()
+            )));
+        }
+
+        let local = Manifest::load_local(fs, &manifest_path).await?;
+        let failures = local.verify_checksums(fs, checksum, &aiassisted_dir).await;
+        if let Some(failure) = failures.into_iter().next() {
+            return Err(Error::ChecksumMismatch {
+                expected: failure.entry.checksum,
+                actual: failure.actual.unwrap_or_else(|| "missing".to_string()),
+            });
+        }
+
+        if self.offline {
+            return Ok(CheckReport {
+                local_version: local.version,
+                ..Default::default()
+            });
+        }
+
+        let remote = self.source.load_manifest(http).await?;
+        let diff = local.diff(&remote);
+        if diff.has_changes() {
+            return Err(Error::Outdated(format!(
+                "v{} installed, v{} available ({} new, {} modified, {} removed)",
+                local.version,
+                remote.version,
+                diff.new_files.len(),
+                diff.modified_files.len(),
+                diff.removed_files.len()
+            )));
+        }
+
+        Ok(CheckReport {
+            local_version: local.version,
+            remote_version: remote.version,
+            diff,
+            failed: Vec::new(),
+        })
+    }
+
+    /// Print the diff between the local and remote manifests in `self.format`.
+    async fn print_diff<F: FileSystem, H: HttpClient>(
+        &self,
+        fs: &F,
+        http: &H,
+    ) -> Result<CheckReport> {
+        let manifest_path = self.path.join(".aiassisted").join(MANIFEST_FILE);
+        if !fs.exists(&manifest_path) {
+            return Err(Error::NotFound(format!(
+                "{} not found. Use 'install' first.",
+                manifest_path.display()
+            )));
+        }
+
+        let local = Manifest::load_local(fs, &manifest_path).await?;
+        if self.offline {
+            return Ok(CheckReport {
+                local_version: local.version,
+                ..Default::default()
+            });
+        }
+
+        let remote = self.source.load_manifest(http).await?;
+        let diff = local.diff(&remote);
+
+        if let Some(output) = self.format.render(&diff)? {
+            println!("{}", output.trim_end());
+        }
+
+        Ok(CheckReport {
+            local_version: local.version,
+            remote_version: remote.version,
+            diff,
+            failed: Vec::new(),
+        })
+    }
+
+    /// Log per-file results (when verbose) followed by a one-line summary.
+    fn report_verification<L: Logger>(
+        &self,
+        manifest: &Manifest,
+        failures: &[ChecksumFailure],
+        logger: &L,
+    ) {
+        if self.verbose {
+            for entry in &manifest.files {
+                match failures.iter().find(|f| f.entry.path == entry.path) {
+                    None => logger.info(&format!("  OK       {}", entry.path.display())),
+                    Some(ChecksumFailure {
+                        actual: Some(actual),
+                        ..
+                    }) => logger.warn(&format!(
+                        "  MISMATCH {} (expected {}, actual {})",
+                        entry.path.display(),
+                        entry.checksum,
+                        actual
+                    )),
+                    Some(ChecksumFailure { actual: None, .. }) => {
+                        logger.warn(&format!("  MISSING  {}", entry.path.display()))
+                    }
+                }
+            }
+        }
+
+        let total = manifest.files.len();
+        if failures.is_empty() {
+            logger.success(&format!("All {} local files match the manifest", total));
+        } else {
+            let missing = failures.iter().filter(|f| f.actual.is_none()).count();
+            logger.warn(&format!(
+                "{} of {} local files failed verification ({} mismatched, {} missing). Run 'aiassisted repair' to fix them.",
+                failures.len(),
+                total,
+                failures.len() - missing,
+                missing
+            ));
+        }
+    }
+}
+
+/// Watch command - re-verifies installed files on an interval (`check --watch`).
+pub struct WatchCommand {
+    pub path: PathBuf,
+    /// Time between verification rounds.
+    pub interval: Duration,
+    /// Stop with an error on the first file that no longer verifies.
+    pub fail_on_change: bool,
+}
+
+impl WatchCommand {
+    /// Execute the watch command until `shutdown` resolves.
+    ///
+    /// The manifest is read once up front, so editing it mid-watch cannot
+    /// hide a change. Each file is reported when it stops verifying and
+    /// again if it is restored.
+    pub async fn execute<F, C, L, S>(
+        &self,
+        fs: &F,
+        checksum: &C,
+        logger: &L,
+        shutdown: S,
+    ) -> Result<()>
+    where
+        F: FileSystem,
+        C: Checksum,
+        L: Logger,
+        S: Future<Output = ()>,
+    {
+        let aiassisted_dir = self.path.join(".aiassisted");
+        let manifest_path = aiassisted_dir.join(MANIFEST_FILE);
+        if !fs.exists(&manifest_path) {
+            return Err(Error::NotFound(format!(
+                "{} not found. Use 'install' first.",
+                manifest_path.display()
+            )));
+        }
+        let manifest = Manifest::load_local(fs, &manifest_path).await?;
+
+        logger.info(&format!(
+            "Watching {} file(s) in {} every {:?} (Ctrl-C to stop)",
+            manifest.files.len(),
+            aiassisted_dir.display(),
+            self.interval
+        ));
+
+        let mut shutdown = std::pin::pin!(shutdown);
+        let mut failing = BTreeSet::new();
+        loop {
+            let failures = manifest.verify_checksums(fs, checksum, &aiassisted_dir).await;
+            let now: BTreeSet<PathBuf> = failures.iter().map(|f| f.entry.path.clone()).collect();
+
+            for failure in failures.into_iter().filter(|f| !failing.contains(&f.entry.path)) {
+                let reason = if failure.actual.is_some() { "modified" } else { "missing" };
+                logger.error(&format!("Changed: {} ({})", failure.entry.path.display(), reason));
+                if self.fail_on_change {
+                    return Err(Error::ChecksumMismatch {
+                        expected: failure.entry.checksum,
+                        actual: failure.actual.unwrap_or_else(|| "missing".to_string()),
+                    });
+                }
+            }
+            for restored in failing.difference(&now) {
+                logger.info(&format!("Restored: {}", restored.display()));
+            }
+            failing = now;
+
+            tokio::select! {
+                _ = &mut shutdown => {
+                    logger.info("Stopped watching");
+                    return Ok(());
+                }
+                _ = tokio::time::sleep(self.interval) => {}
+            }
+        }
+    }
+}
+
+/// Files inside `.aiassisted/` that belong to the tool rather than the content.
+///
+/// `.tool-cache` is the skills tool-detection cache and `skills.toml` the
+/// user's skill selection.
+const BOOKKEEPING_FILES: [&str; 3] = [MANIFEST_FILE, ".tool-cache", "skills.toml"];
+
+/// Content list command - shows the files managed in `.aiassisted/`.
+pub struct ContentListCommand {
+    pub path: PathBuf,
+    /// Re-hash every managed file and report its status.
+    pub verify: bool,
+    /// Include each managed file's recorded checksum in text output.
+    pub verbose: bool,
+    /// `Json` or `Yaml` prints the listing in that format instead of log lines.
+    pub format: OutputFormat,
+}
+
+/// Verification status of a managed file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFileStatus {
+    Ok,
+    Mismatch,
+    Missing,
+}
+
+/// A file recorded in the local manifest.
+#[derive(Debug, Clone, Serialize)]
+pub struct ManagedFile {
+    pub path: PathBuf,
+    /// Checksum recorded in the manifest.
+    pub checksum: String,
+    /// Only set with `--verify`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ContentFileStatus>,
+}
+
+/// Result of [`ContentListCommand`].
+#[derive(Debug, Default, Serialize)]
+pub struct ContentListing {
+    /// Version of the local manifest.
+    pub version: String,
+    /// Files recorded in the manifest, in manifest order.
+    pub managed: Vec<ManagedFile>,
+    /// Files present in `.aiassisted/` but absent from the manifest, sorted.
+    pub stray: Vec<PathBuf>,
+}
+
+impl ContentListCommand {
+    /// Execute the content list command.
+    pub async fn execute<F, C, L>(&self, fs: &F, checksum: &C, logger: &L) -> Result<ContentListing>
+    where
+        F: FileSystem,
+        C: Checksum,
+        L: Logger,
+    {
+        let aiassisted_dir = self.path.join(".aiassisted");
+        let manifest_path = aiassisted_dir.join(MANIFEST_FILE);
+        if !fs.exists(&manifest_path) {
+            return Err(Error::NotFound(format!(
+                "{} not found. Use 'install' first.",
+                manifest_path.display()
+            )));
+        }
+
+        let manifest = Manifest::load_local(fs, &manifest_path).await?;
+        let failures = if self.verify {
+            manifest.verify_checksums(fs, checksum, &aiassisted_dir).await
+        } else {
+            Vec::new()
+        };
+
+        let managed: Vec<ManagedFile> = manifest
+            .files
+            .iter()
+            .map(|entry| {
+                let status = self.verify.then(|| {
+                    match failures.iter().find(|f| f.entry.path == entry.path) {
+                        None => ContentFileStatus::Ok,
+                        Some(ChecksumFailure { actual: Some(_), .. }) => {
+                            ContentFileStatus::Mismatch
+                        }
+                        Some(ChecksumFailure { actual: None, .. }) => ContentFileStatus::Missing,
+                    }
+                });
+                ManagedFile {
+                    path: entry.path.clone(),
+                    checksum: entry.checksum.clone(),
+                    status,
+                }
+            })
+            .collect();
+
+        let known: BTreeSet<&Path> = manifest.files.iter().map(|e| e.path.as_path()).collect();
+        let mut stray = Vec::new();
+        for path in fs.walk_dir(&aiassisted_dir).await? {
+            let Ok(relative) = path.strip_prefix(&aiassisted_dir) else {
+                continue;
+            };
+            if known.contains(relative)
+                || BOOKKEEPING_FILES.iter().any(|f| relative == Path::new(f))
+            {
+                continue;
+            }
+            stray.push(relative.to_path_buf());
+        }
+        stray.sort();
+
+        let listing = ContentListing {
+            version: manifest.version,
+            managed,
+            stray,
+        };
+
+        match self.format.render(&listing)? {
+            Some(output) => println!("{}", output.trim_end()),
+            None => self.report(&listing, logger),
+        }
+
+        Ok(listing)
+    }
+
+    /// Log the listing as text.
+    fn report<L: Logger>(&self, listing: &ContentListing, logger: &L) {
+        logger.info(&format!(
+            "Managed files ({}, version {}):",
+            listing.managed.len(),
+            listing.version
+        ));
+        for file in &listing.managed {
+            let mut line = match file.status {
+                None => format!("  {}", file.path.display()),
+                Some(ContentFileStatus::Ok) => format!("  OK       {}", file.path.display()),
+                Some(ContentFileStatus::Mismatch) => {
+                    format!("  MISMATCH {}", file.path.display())
+                }
+                Some(ContentFileStatus::Missing) => format!("  MISSING  {}", file.path.display()),
+            };
+            if self.verbose {
+                line.push_str(&format!("  {}", file.checksum));
+            }
+            match file.status {
+                Some(ContentFileStatus::Mismatch | ContentFileStatus::Missing) => logger.warn(&line),
+                _ => logger.info(&line),
+            }
+        }
+
+        if listing.stray.is_empty() {
+            return;
+        }
+        logger.info("");
+        logger.warn(&format!(
+            "Stray files ({}, not in the manifest):",
+            listing.stray.len()
+        ));
+        for path in &listing.stray {
+            logger.info(&format!("  {}", path.display()));
+        }
+    }
+}
+
+/// Changelog command - lists files changed upstream since a manifest version.
+pub struct ChangelogCommand {
+    pub path: PathBuf,
+    pub since: String,
+    /// Where the current manifest is downloaded from.
+    pub source: ContentSource,
+}
+
+impl ChangelogCommand {
+    /// Execute the changelog command.
+    pub async fn execute<F, H, L>(&self, fs: &F, http: &H, logger: &L) -> Result<()>
+    where
+        F: FileSystem,
+        H: HttpClient,
+        L: Logger,
+    {
+        sync::changelog(fs, http, logger, &self.source, &self.path, &self.since)
+            .await
+            .map(|_| ())
+    }
+}
+
+/// Repair command - re-downloads files that fail checksum verification.
+pub struct RepairCommand {
+    pub path: PathBuf,
+    /// Maximum number of files downloaded at once.
+    pub concurrency: usize,
+    /// Keep downloading after a file fails and report every failure at the end.
+    pub keep_going: bool,
+    /// Where the manifest and content files are downloaded from.
+    pub source: ContentSource,
+}
+
+impl RepairCommand {
+    /// Execute the repair command.
+    pub async fn execute<F, H, C, L>(
+        &self,
+        fs: &F,
+        http: &H,
+        checksum: &C,
+        logger: &L,
+    ) -> Result<()>
+    where
+        F: FileSystem,
+        H: HttpClient,
+        C: Checksum,
+        L: Logger,
+    {
+        logger.info(&format!(
+            "Repairing .aiassisted in {}",
+            self.path.display()
+        ));
+
+        sync::repair(
+            fs,
+            http,
+            checksum,
+            logger,
+            &self.source,
+            &self.path,
+            self.concurrency,
+            self.keep_going,
+        )
+        .await
+            .map(|_| ())
+    }
+}
+
+/// Manifest generate command - writes manifest.json for a content directory.
+pub struct ManifestGenerateCommand {
+    /// The `.aiassisted` directory to index.
+    pub path: PathBuf,
+    /// Version string recorded in the manifest (usually a git commit).
+    pub version: String,
+}
+
+impl ManifestGenerateCommand {
+    /// Execute the manifest generate command.
+    pub async fn execute<F, C, L>(&self, fs: &F, checksum: &C, logger: &L) -> Result<()>
+    where
+        F: FileSystem,
+        C: Checksum,
+        L: Logger,
+    {
+        logger.info(&format!("Generating manifest for {}", self.path.display()));
+
+        let manifest = Manifest::generate(fs, checksum, &self.path, &self.version).await?;
+        let manifest_path = self.path.join(MANIFEST_FILE);
+        manifest.save(fs, &manifest_path).await?;
+
+        logger.success(&format!(
+            "Wrote {} ({} files, version {})",
+            manifest_path.display(),
+            manifest.files.len(),
+            manifest.version
+        ));
+
+        Ok(())
     }
 }
@@ -1,90 +1,366 @@
 //! GitHub API utilities for downloading .aiassisted content.
 
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
-use crate::core::infra::{Checksum, FileSystem, HttpClient};
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use crate::core::infra::{Checksum, DownloadOptions, FileSystem, HttpClient};
 use crate::core::types::{Error, ManifestEntry, Result};
 
+use super::manifest::Manifest;
+
+/// Raw GitHub content URL for the repository, without a git ref.
+pub const GITHUB_RAW_REPO: &str = "https://raw.githubusercontent.com/rstlix0x0/aiassisted";
+
 /// Base URL for raw GitHub content.
 pub const GITHUB_RAW_BASE: &str = "https://raw.githubusercontent.com/rstlix0x0/aiassisted/main";
 
 /// Manifest file path relative to repository root.
 pub const MANIFEST_PATH: &str = ".aiassisted/manifest.json";
 
+/// Default number of files downloaded at once.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Upper bound on concurrent downloads, to avoid exhausting sockets.
+pub const MAX_CONCURRENCY: usize = 32;
+
+/// Consecutive failures after which a `keep_going` batch gives up on the source.
+pub const CIRCUIT_BREAKER_THRESHOLD: usize = 5;
+
+/// Clamp a requested concurrency to `1..=MAX_CONCURRENCY`.
+pub fn clamp_concurrency(requested: usize) -> usize {
+    requested.clamp(1, MAX_CONCURRENCY)
+}
+
 /// Get the full URL for the manifest file.
 pub fn manifest_url() -> String {
     format!("{}/{}", GITHUB_RAW_BASE, MANIFEST_PATH)
 }
 
-/// Get the full URL for a content file.
+/// Where the manifest and content files are downloaded from.
+///
+/// Defaults to the GitHub repository. Mirrors set a manifest URL and a
+/// content base; each file is fetched from `<content base>/<entry path>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentSource {
+    manifest_url: String,
+    content_base: String,
+    /// Manifest supplied up front (`--manifest`), used instead of downloading one
+    manifest: Option<Manifest>,
+    /// Content-addressed cache consulted before downloading (see [`default_cache_dir`])
+    cache_dir: Option<PathBuf>,
+}
+
+impl Default for ContentSource {
+    fn default() -> Self {
+        Self {
+            manifest_url: manifest_url(),
+            content_base: format!("{}/.aiassisted", GITHUB_RAW_BASE),
+            manifest: None,
+            cache_dir: None,
+        }
+    }
+}
+
+impl ContentSource {
+    /// Build a source from optional overrides, validating each URL.
+    ///
+    /// Unset overrides fall back to GitHub, except that a manifest URL on its
+    /// own implies a content base of the directory containing the manifest.
+    pub fn new(manifest_url: Option<&str>, content_base: Option<&str>) -> Result<Self> {
+        let defaults = Self::default();
+
+        let manifest_url = match manifest_url {
+            Some(url) => Some(validate_url("--manifest-url", url)?),
+            None => None,
+        };
+        let content_base = match (content_base, &manifest_url) {
+            (Some(base), _) => validate_url("--content-base", base)?,
+            (None, Some(url)) => url
+                .rsplit_once('/')
+                .map_or(url.as_str(), |(dir, _)| dir)
+                .to_string(),
+            (None, None) => defaults.content_base,
+        };
+        let manifest_url = manifest_url.unwrap_or(defaults.manifest_url);
+
+        Ok(Self {
+            manifest_url,
+            content_base: content_base.trim_end_matches('/').to_string(),
+            manifest: None,
+            cache_dir: None,
+        })
+    }
+
+    /// Use `manifest` instead of downloading one from the manifest URL.
+    pub fn with_manifest(mut self, manifest: Manifest) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    /// Read and populate the content cache in `cache_dir` (`--no-cache` skips this).
+    pub fn with_cache(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    /// Cache path for content with `checksum`, or `None` without a cache.
+    ///
+    /// Anything but a SHA256 hex digest gets no path, so a manifest cannot
+    /// point outside the cache.
+    fn cached_object(&self, checksum: &str) -> Option<PathBuf> {
+        let is_digest = checksum.len() == 64 && checksum.bytes().all(|b| b.is_ascii_hexdigit());
+        let cache_dir = self.cache_dir.as_ref().filter(|_| is_digest)?;
+        Some(cache_dir.join("objects").join(checksum))
+    }
+
+    /// URL of the manifest file.
+    pub fn manifest_url(&self) -> &str {
+        &self.manifest_url
+    }
+
+    /// The supplied manifest, or the one downloaded from the manifest URL.
+    pub async fn load_manifest<H: HttpClient>(&self, http: &H) -> Result<Manifest> {
+        match &self.manifest {
+            Some(manifest) => Ok(manifest.clone()),
+            None => Manifest::load_remote(http, &self.manifest_url).await,
+        }
+    }
+
+    /// URL of a content file from the manifest.
+    ///
+    /// Path components are always joined with `/`, so Windows-style paths
+    /// still produce valid URLs.
+    pub fn content_url(&self, path: &Path) -> String {
+        let url_path = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => Some(part.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        format!("{}/{}", self.content_base, url_path)
+    }
+}
+
+/// Check that `url` is an absolute http(s) URL with a host.
+fn validate_url(flag: &str, url: &str) -> Result<String> {
+    let invalid = |reason: &str| Error::Config(format!("Invalid {} '{}': {}", flag, url, reason));
+
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| invalid("must start with http:// or https://"))?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() {
+        return Err(invalid("missing host"));
+    }
+    if url.chars().any(char::is_whitespace) {
+        return Err(invalid("contains whitespace"));
+    }
+
+    Ok(url.to_string())
+}
+
+/// Get the manifest URL at a specific git ref (branch, tag or commit).
+///
+/// Manifest versions are commit hashes, so a version can be used directly.
+pub fn manifest_url_at(git_ref: &str) -> String {
+    format!("{}/{}/{}", GITHUB_RAW_REPO, git_ref, MANIFEST_PATH)
+}
+
+/// Get the full URL for a content file on GitHub.
 pub fn content_url(path: &Path) -> String {
-    format!(
-        "{}/.aiassisted/{}",
-        GITHUB_RAW_BASE,
-        path.display()
-    )
+    ContentSource::default().content_url(path)
+}
+
+/// The shared content cache, `~/.aiassisted/cache`.
+///
+/// Objects are stored as `objects/<sha256>`, so identical files downloaded
+/// for different projects are fetched once.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".aiassisted").join("cache"))
 }
 
-/// Download a single file from GitHub with checksum verification.
+/// Download a single file from `source` with checksum verification.
+///
+/// The body is streamed to a staging file next to the destination and only
+/// renamed into place once its checksum matches, so the installed copy is
+/// never replaced by a bad download. An interrupted download is resumed on
+/// the next attempt. With a content cache, a cached copy that still matches
+/// the checksum is used instead of the network, and verified downloads are
+/// added to the cache. Returns the number of bytes written.
 pub async fn download_file<H, C, F>(
     http: &H,
     checksum: &C,
     fs: &F,
+    source: &ContentSource,
     entry: &ManifestEntry,
     dest_dir: &Path,
-) -> Result<()>
+) -> Result<u64>
 where
     H: HttpClient,
     C: Checksum,
     F: FileSystem,
 {
-    let url = content_url(&entry.path);
+    let url = source.content_url(&entry.path);
     let dest_path = dest_dir.join(".aiassisted").join(&entry.path);
+    let cached_object = source.cached_object(&entry.checksum);
+
+    // A corrupt or half-written cache entry just falls through to a download
+    let cached = match &cached_object {
+        Some(object) if fs.is_file(object) => fs
+            .read_bytes(object)
+            .await
+            .ok()
+            .filter(|content| checksum.sha256(content) == entry.checksum),
+        _ => None,
+    };
+
+    // Ensure parent directory exists
+    if let Some(parent) = dest_path.parent() {
+        fs.create_dir_all(parent).await?;
+    }
 
-    // Download content
-    let content = http.get(&url).await?;
+    if let Some(content) = cached {
+        fs.write_bytes(&dest_path, &content).await?;
+        return Ok(content.len() as u64);
+    }
+
+    // The checksum below rejects a stale partial file, so resuming is safe
+    let staging_path = staging_path(&dest_path);
+    let options = DownloadOptions {
+        max_size: None,
+        resume: true,
+    };
+    let size = http.download_with(&url, &staging_path, options).await?;
 
     // Verify checksum
-    let actual_checksum = checksum.sha256(content.as_bytes());
+    let actual_checksum = checksum.sha256_file(&staging_path)?;
     if actual_checksum != entry.checksum {
+        let _ = fs.remove_file(&staging_path).await;
         return Err(Error::ChecksumMismatch {
             expected: entry.checksum.clone(),
             actual: actual_checksum,
         });
     }
 
-    // Ensure parent directory exists
-    if let Some(parent) = dest_path.parent() {
-        fs.create_dir_all(parent).await?;
+    if let Some(object) = &cached_object {
+        // An unwritable cache only costs a download next time
+        let populated = match object.parent() {
+            Some(dir) => fs.create_dir_all(dir).await,
+            None => Ok(()),
+        };
+        if populated.is_ok() {
+            let _ = fs.copy(&staging_path, object).await;
+        }
     }
 
-    // Write file
-    fs.write(&dest_path, &content).await?;
+    fs.rename(&staging_path, &dest_path).await?;
+
+    Ok(size)
+}
+
+/// Where [`download_file`] stages `dest` until its checksum is verified.
+fn staging_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".download");
+    dest.with_file_name(name)
+}
 
-    Ok(())
+/// Files written by [`download_batch`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Downloads {
+    /// Written paths, in the order of the requested entries.
+    pub paths: Vec<PathBuf>,
+    /// Total bytes downloaded.
+    pub bytes: u64,
 }
 
-/// Download multiple files in batch.
+/// Download multiple files in batch, at most `concurrency` at a time.
+///
+/// Stops at the first failure unless `keep_going` is set, in which case every
+/// file is attempted and the error lists each file that failed with its reason.
+/// Even then, [`CIRCUIT_BREAKER_THRESHOLD`] failures in a row abort the rest of
+/// the batch, since the source is most likely down; a success resets the count.
+/// Files that did download stay written either way.
+#[allow(clippy::too_many_arguments)]
 pub async fn download_batch<H, C, F>(
     http: &H,
     checksum: &C,
     fs: &F,
+    source: &ContentSource,
     entries: &[ManifestEntry],
     dest_dir: &Path,
-) -> Result<Vec<PathBuf>>
+    concurrency: usize,
+    keep_going: bool,
+) -> Result<Downloads>
 where
     H: HttpClient,
     C: Checksum,
     F: FileSystem,
 {
-    let mut downloaded = Vec::new();
+    let downloads = stream::iter(entries)
+        .map(|entry| async move {
+            let bytes = download_file(http, checksum, fs, source, entry, dest_dir)
+                .await
+                .map_err(|e| (entry, e))?;
+            Ok((dest_dir.join(".aiassisted").join(&entry.path), bytes))
+        })
+        .buffered(clamp_concurrency(concurrency));
+
+    if !keep_going {
+        return downloads
+            .map_err(|(_, e)| e)
+            .try_fold(Downloads::default(), |mut downloads, (path, bytes)| async move {
+                downloads.paths.push(path);
+                downloads.bytes += bytes;
+                Ok(downloads)
+            })
+            .await;
+    }
+
+    let mut downloads = std::pin::pin!(downloads);
+    let mut succeeded = Downloads::default();
+    let mut failures = Vec::new();
+    let mut consecutive_failures = 0;
 
-    for entry in entries {
-        download_file(http, checksum, fs, entry, dest_dir).await?;
-        downloaded.push(dest_dir.join(".aiassisted").join(&entry.path));
+    while let Some(result) = downloads.next().await {
+        match result {
+            Ok((path, bytes)) => {
+                succeeded.paths.push(path);
+                succeeded.bytes += bytes;
+                consecutive_failures = 0;
+            }
+            Err((entry, e)) => {
+                failures.push(format!("  {}: {}", entry.path.display(), e));
+                consecutive_failures += 1;
+                if consecutive_failures >= CIRCUIT_BREAKER_THRESHOLD {
+                    return Err(Error::Network(format!(
+                        "Gave up after {} consecutive download failures ({} of {} file(s) done); \
+                         {} appears to be unavailable:\n{}",
+                        consecutive_failures,
+                        succeeded.paths.len() + failures.len(),
+                        entries.len(),
+                        source.content_base,
+                        failures.join("\n")
+                    )));
+                }
+            }
+        }
     }
 
-    Ok(downloaded)
+    if failures.is_empty() {
+        return Ok(succeeded);
+    }
+    Err(Error::Network(format!(
+        "{} of {} file(s) failed to download:\n{}",
+        failures.len(),
+        entries.len(),
+        failures.join("\n")
+    )))
 }
 
 #[cfg(test)]
@@ -102,6 +378,12 @@ mod tests {
             async fn get(&self, url: &str) -> Result<String>;
             async fn get_bytes(&self, url: &str) -> Result<Vec<u8>>;
             async fn download(&self, url: &str, dest: &Path) -> Result<()>;
+            async fn download_with(
+                &self,
+                url: &str,
+                dest: &Path,
+                options: DownloadOptions,
+            ) -> Result<u64>;
         }
     }
 
@@ -111,6 +393,7 @@ mod tests {
         impl crate::core::infra::Checksum for Checksum {
             fn sha256(&self, content: &[u8]) -> String;
             fn sha256_file(&self, path: &Path) -> Result<String>;
+            fn sha256_reader(&self, reader: &mut dyn std::io::Read) -> Result<String>;
         }
     }
 
@@ -127,9 +410,18 @@ mod tests {
             async fn create_dir_all(&self, path: &Path) -> Result<()>;
             async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
             async fn copy(&self, from: &Path, to: &Path) -> Result<()>;
+            async fn remove_file(&self, path: &Path) -> Result<()>;
+            async fn remove_dir_all(&self, path: &Path) -> Result<()>;
+            async fn rename(&self, from: &Path, to: &Path) -> Result<()>;
         }
     }
 
+    /// Stand-in for a finished download of `content` to `dest`.
+    fn write_download(dest: &Path, content: &[u8]) -> Result<u64> {
+        std::fs::write(dest, content)?;
+        Ok(content.len() as u64)
+    }
+
     #[test]
     fn test_manifest_url() {
         let url = manifest_url();
@@ -139,6 +431,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_manifest_url_at() {
+        assert_eq!(
+            manifest_url_at("87d2583"),
+            "https://raw.githubusercontent.com/rstlix0x0/aiassisted/87d2583/.aiassisted/manifest.json"
+        );
+        assert_eq!(manifest_url_at("main"), manifest_url());
+    }
+
     #[test]
     fn test_content_url() {
         let path = Path::new("guidelines/architecture.md");
@@ -159,6 +460,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_content_url_uses_forward_slashes() {
+        let entry = ManifestEntry {
+            path: ["guidelines", "rust", "style.md"].iter().collect(),
+            checksum: "abc123".to_string(),
+        };
+        let url = content_url(&entry.path);
+        assert_eq!(
+            url,
+            "https://raw.githubusercontent.com/rstlix0x0/aiassisted/main/.aiassisted/guidelines/rust/style.md"
+        );
+        assert!(!url.contains('\\'));
+    }
+
+    #[test]
+    fn test_content_source_defaults_to_github() {
+        let source = ContentSource::new(None, None).unwrap();
+        assert_eq!(source, ContentSource::default());
+        assert_eq!(source.manifest_url(), manifest_url());
+        assert_eq!(
+            source.content_url(Path::new("guidelines/architecture.md")),
+            content_url(Path::new("guidelines/architecture.md"))
+        );
+    }
+
+    #[test]
+    fn test_content_source_overrides() {
+        let source = ContentSource::new(
+            Some("https://mirror.example.com/aiassisted/manifest.json"),
+            Some("https://cdn.example.com/content/"),
+        )
+        .unwrap();
+        assert_eq!(
+            source.manifest_url(),
+            "https://mirror.example.com/aiassisted/manifest.json"
+        );
+        assert_eq!(
+            source.content_url(Path::new("rust/style.md")),
+            "https://cdn.example.com/content/rust/style.md"
+        );
+
+        // Without a content base, files live next to the manifest
+        let source =
+            ContentSource::new(Some("http://mirror.local/aiassisted/manifest.json"), None).unwrap();
+        assert_eq!(
+            source.content_url(Path::new("README.md")),
+            "http://mirror.local/aiassisted/README.md"
+        );
+    }
+
+    #[test]
+    fn test_cached_object_requires_sha256_digest() {
+        let digest = "a".repeat(64);
+        assert_eq!(ContentSource::default().cached_object(&digest), None);
+
+        let source = ContentSource::default().with_cache(PathBuf::from("/cache"));
+        assert_eq!(
+            source.cached_object(&digest),
+            Some(PathBuf::from("/cache/objects").join(&digest))
+        );
+        for checksum in ["abc123", "../../../etc/passwd", &"g".repeat(64)] {
+            assert_eq!(source.cached_object(checksum), None, "{}", checksum);
+        }
+    }
+
+    #[test]
+    fn test_content_source_rejects_invalid_urls() {
+        for url in [
+            "mirror.local/manifest.json",
+            "ftp://mirror.local/m.json",
+            "https://",
+            "https:///m.json",
+            "https://mirror.local/a b.json",
+        ] {
+            let result = ContentSource::new(Some(url), None);
+            assert!(matches!(result, Err(Error::Config(_))), "accepted {}", url);
+        }
+        let result = ContentSource::new(None, Some("file:///srv/content"));
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("--content-base")));
+    }
+
     #[test]
     fn test_content_url_root_file() {
         let path = Path::new("README.md");
@@ -181,27 +563,31 @@ mod tests {
         let mut mock_checksum = MockChecksum::new();
         let mut mock_fs = MockFileSystem::new();
 
-        // Expect HTTP GET
+        // Expect directory creation
+        mock_fs
+            .expect_create_dir_all()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // Expect a resumable download to the staging file
         mock_http
-            .expect_get()
+            .expect_download_with()
+            .withf(|url, dest, options| {
+                url.ends_with("/test.txt") && dest.ends_with("test.txt.download") && options.resume
+            })
             .times(1)
-            .returning(|_| Ok("file content".to_string()));
+            .returning(|_, _, _| Ok(12));
 
         // Expect checksum calculation
         mock_checksum
-            .expect_sha256()
+            .expect_sha256_file()
             .times(1)
-            .returning(|_| "abc123".to_string());
-
-        // Expect directory creation
-        mock_fs
-            .expect_create_dir_all()
-            .times(1)
-            .returning(|_| Ok(()));
+            .returning(|_| Ok("abc123".to_string()));
 
-        // Expect file write
+        // Expect the verified file to be moved into place
         mock_fs
-            .expect_write()
+            .expect_rename()
+            .withf(|from, to| from.ends_with("test.txt.download") && to.ends_with("test.txt"))
             .times(1)
             .returning(|_, _| Ok(()));
 
@@ -209,12 +595,13 @@ mod tests {
             &mock_http,
             &mock_checksum,
             &mock_fs,
+            &ContentSource::default(),
             &entry,
             temp_dir.path(),
         )
         .await;
 
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 12);
     }
 
     #[tokio::test]
@@ -227,22 +614,32 @@ mod tests {
 
         let mut mock_http = MockHttpClient::new();
         let mut mock_checksum = MockChecksum::new();
-        let mock_fs = MockFileSystem::new();
+        let mut mock_fs = MockFileSystem::new();
+
+        mock_fs.expect_create_dir_all().returning(|_| Ok(()));
 
         mock_http
-            .expect_get()
+            .expect_download_with()
             .times(1)
-            .returning(|_| Ok("file content".to_string()));
+            .returning(|_, _, _| Ok(12));
 
         mock_checksum
-            .expect_sha256()
+            .expect_sha256_file()
+            .times(1)
+            .returning(|_| Ok("wrong_checksum".to_string()));
+
+        // The bad download is discarded instead of renamed into place
+        mock_fs
+            .expect_remove_file()
+            .withf(|path| path.ends_with("test.txt.download"))
             .times(1)
-            .returning(|_| "wrong_checksum".to_string());
+            .returning(|_| Ok(()));
 
         let result = download_file(
             &mock_http,
             &mock_checksum,
             &mock_fs,
+            &ContentSource::default(),
             &entry,
             temp_dir.path(),
         )
@@ -268,17 +665,20 @@ mod tests {
 
         let mut mock_http = MockHttpClient::new();
         let mock_checksum = MockChecksum::new();
-        let mock_fs = MockFileSystem::new();
+        let mut mock_fs = MockFileSystem::new();
+
+        mock_fs.expect_create_dir_all().returning(|_| Ok(()));
 
         mock_http
-            .expect_get()
+            .expect_download_with()
             .times(1)
-            .returning(|_| Err(Error::Network("Connection failed".to_string())));
+            .returning(|_, _, _| Err(Error::Network("Connection failed".to_string())));
 
         let result = download_file(
             &mock_http,
             &mock_checksum,
             &mock_fs,
+            &ContentSource::default(),
             &entry,
             temp_dir.path(),
         )
@@ -305,30 +705,30 @@ mod tests {
         let mut mock_checksum = MockChecksum::new();
         let mut mock_fs = MockFileSystem::new();
 
-        // Expect 2 HTTP GETs
+        // Expect 2 downloads
         mock_http
-            .expect_get()
+            .expect_download_with()
             .times(2)
-            .returning(|_| Ok("content".to_string()));
+            .returning(|_, _, _| Ok(b"content".len() as u64));
 
         // Expect 2 checksum calculations
         mock_checksum
-            .expect_sha256()
+            .expect_sha256_file()
             .times(1)
-            .returning(|_| "checksum1".to_string());
+            .returning(|_| Ok("checksum1".to_string()));
         mock_checksum
-            .expect_sha256()
+            .expect_sha256_file()
             .times(1)
-            .returning(|_| "checksum2".to_string());
+            .returning(|_| Ok("checksum2".to_string()));
 
-        // Expect directory creations and file writes
+        // Expect directory creations and files moved into place
         mock_fs
             .expect_create_dir_all()
             .times(2)
             .returning(|_| Ok(()));
 
         mock_fs
-            .expect_write()
+            .expect_rename()
             .times(2)
             .returning(|_, _| Ok(()));
 
@@ -336,14 +736,18 @@ mod tests {
             &mock_http,
             &mock_checksum,
             &mock_fs,
+            &ContentSource::default(),
             &entries,
             temp_dir.path(),
+            1,
+            false,
         )
         .await;
 
         assert!(result.is_ok());
         let downloaded = result.unwrap();
-        assert_eq!(downloaded.len(), 2);
+        assert_eq!(downloaded.paths.len(), 2);
+        assert_eq!(downloaded.bytes, 2 * b"content".len() as u64);
     }
 
     #[tokio::test]
@@ -364,39 +768,42 @@ mod tests {
         let mut mock_checksum = MockChecksum::new();
         let mut mock_fs = MockFileSystem::new();
 
+        mock_fs
+            .expect_create_dir_all()
+            .times(2)
+            .returning(|_| Ok(()));
+
         // First file succeeds
         mock_http
-            .expect_get()
+            .expect_download_with()
             .times(1)
-            .returning(|_| Ok("content".to_string()));
+            .returning(|_, _, _| Ok(b"content".len() as u64));
 
         mock_checksum
-            .expect_sha256()
+            .expect_sha256_file()
             .times(1)
-            .returning(|_| "checksum1".to_string());
-
-        mock_fs
-            .expect_create_dir_all()
-            .times(1)
-            .returning(|_| Ok(()));
+            .returning(|_| Ok("checksum1".to_string()));
 
         mock_fs
-            .expect_write()
+            .expect_rename()
             .times(1)
             .returning(|_, _| Ok(()));
 
         // Second file fails
         mock_http
-            .expect_get()
+            .expect_download_with()
             .times(1)
-            .returning(|_| Err(Error::Network("Failed".to_string())));
+            .returning(|_, _, _| Err(Error::Network("Failed".to_string())));
 
         let result = download_batch(
             &mock_http,
             &mock_checksum,
             &mock_fs,
+            &ContentSource::default(),
             &entries,
             temp_dir.path(),
+            1,
+            false,
         )
         .await;
 
@@ -404,6 +811,85 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_download_batch_keep_going_reports_every_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let fs = crate::infra::StdFileSystem::new();
+        let checksum = crate::infra::Sha2Checksum::new();
+
+        let entries: Vec<ManifestEntry> = (0..3)
+            .map(|i| ManifestEntry {
+                path: PathBuf::from(format!("file{}.txt", i)),
+                checksum: checksum.sha256(b"content"),
+            })
+            .collect();
+
+        // file1.txt's URL fails; the others download fine
+        let mut http = MockHttpClient::new();
+        http.expect_download_with().times(3).returning(|url, dest, _| {
+            if url.ends_with("/file1.txt") {
+                Err(Error::Network("HTTP 404".to_string()))
+            } else {
+                write_download(dest, b"content")
+            }
+        });
+
+        let source = ContentSource::default();
+        let result =
+            download_batch(&http, &checksum, &fs, &source, &entries, temp_dir.path(), 1, true)
+                .await;
+
+        let Err(Error::Network(msg)) = result else {
+            panic!("expected an aggregate error, got {:?}", result);
+        };
+        assert!(msg.starts_with("1 of 3 file(s) failed to download"));
+        assert!(msg.contains("file1.txt: Network error: HTTP 404"));
+
+        // The other files were still written, including the one after the failure
+        let content_dir = temp_dir.path().join(".aiassisted");
+        assert!(content_dir.join("file0.txt").exists());
+        assert!(!content_dir.join("file1.txt").exists());
+        assert!(content_dir.join("file2.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_batch_keep_going_trips_circuit_breaker() {
+        let temp_dir = TempDir::new().unwrap();
+        let fs = crate::infra::StdFileSystem::new();
+        let checksum = crate::infra::Sha2Checksum::new();
+
+        let entries: Vec<ManifestEntry> = (0..20)
+            .map(|i| ManifestEntry {
+                path: PathBuf::from(format!("file{}.txt", i)),
+                checksum: checksum.sha256(b"content"),
+            })
+            .collect();
+
+        // The first file works, then the source goes down for good
+        let mut http = MockHttpClient::new();
+        http.expect_download_with()
+            .times(1 + CIRCUIT_BREAKER_THRESHOLD)
+            .returning(|url, dest, _| {
+                if url.ends_with("/file0.txt") {
+                    write_download(dest, b"content")
+                } else {
+                    Err(Error::Network("connection refused".to_string()))
+                }
+            });
+
+        let source = ContentSource::default();
+        let result =
+            download_batch(&http, &checksum, &fs, &source, &entries, temp_dir.path(), 1, true)
+                .await;
+
+        let Err(Error::Network(msg)) = result else {
+            panic!("expected the breaker to trip, got {:?}", result);
+        };
+        assert!(msg.starts_with("Gave up after 5 consecutive download failures (6 of 20"));
+        assert!(msg.contains("/.aiassisted appears to be unavailable"));
+        assert!(temp_dir.path().join(".aiassisted/file0.txt").exists());
+    }
+
     #[tokio::test]
     async fn test_download_batch_empty() {
         let temp_dir = TempDir::new().unwrap();
@@ -417,13 +903,83 @@ mod tests {
             &mock_http,
             &mock_checksum,
             &mock_fs,
+            &ContentSource::default(),
             &entries,
             temp_dir.path(),
+            1,
+            false,
         )
         .await;
 
         assert!(result.is_ok());
         let downloaded = result.unwrap();
-        assert_eq!(downloaded.len(), 0);
+        assert_eq!(downloaded, Downloads::default());
+    }
+
+    /// HttpClient that records the peak number of concurrent requests.
+    #[derive(Default)]
+    struct CountingHttpClient {
+        in_flight: std::sync::atomic::AtomicUsize,
+        max_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for CountingHttpClient {
+        async fn get(&self, url: &str) -> Result<String> {
+            use std::sync::atomic::Ordering;
+
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(url.to_string())
+        }
+
+        async fn get_bytes(&self, url: &str) -> Result<Vec<u8>> {
+            self.get(url).await.map(String::into_bytes)
+        }
+
+        async fn download(&self, url: &str, dest: &Path) -> Result<()> {
+            let content = self.get(url).await?;
+            tokio::fs::write(dest, content).await?;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_batch_respects_concurrency_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let fs = crate::infra::StdFileSystem::new();
+        let checksum = crate::infra::Sha2Checksum::new();
+        let http = CountingHttpClient::default();
+
+        // The counting client echoes the URL, so that is the file content
+        let entries: Vec<ManifestEntry> = (0..10)
+            .map(|i| {
+                let path = PathBuf::from(format!("file{}.txt", i));
+                ManifestEntry {
+                    checksum: checksum.sha256(content_url(&path).as_bytes()),
+                    path,
+                }
+            })
+            .collect();
+
+        let source = ContentSource::default();
+        let downloaded = download_batch(&http, &checksum, &fs, &source, &entries, temp_dir.path(), 3, false)
+            .await
+            .unwrap();
+
+        assert_eq!(downloaded.paths.len(), 10);
+        assert_eq!(downloaded.paths[0], temp_dir.path().join(".aiassisted/file0.txt"));
+        let peak = http.max_in_flight.load(std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(peak, 3);
+    }
+
+    #[test]
+    fn test_clamp_concurrency() {
+        assert_eq!(clamp_concurrency(0), 1);
+        assert_eq!(clamp_concurrency(4), 4);
+        assert_eq!(clamp_concurrency(10_000), MAX_CONCURRENCY);
     }
 }
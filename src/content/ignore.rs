@@ -0,0 +1,171 @@
+//! `.aiassistedignore` rules for excluding files from generated manifests.
+//!
+//! The format is a small subset of `.gitignore`:
+//! - blank lines and lines starting with `#` are skipped
+//! - `*` matches within a path segment, `**` matches across segments, `?` matches one character
+//! - a pattern without `/` matches a file or directory name at any depth
+//! - a pattern with `/` is matched against the path from the root
+//! - a trailing `/` only matches directories
+//! - a leading `!` re-includes a previously ignored path (last match wins)
+
+use std::path::{Component, Path};
+
+/// Name of the ignore file looked up in the content root.
+pub const IGNORE_FILE: &str = ".aiassistedignore";
+
+/// A single ignore pattern.
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: String,
+    negated: bool,
+    dir_only: bool,
+    anchored: bool,
+}
+
+/// Parsed `.aiassistedignore` rules.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<Rule>,
+}
+
+impl IgnoreRules {
+    /// Parse ignore rules from the contents of an ignore file.
+    pub fn parse(contents: &str) -> Self {
+        let rules = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let dir_only = line.ends_with('/');
+                let line = line.trim_end_matches('/');
+                let anchored = line.contains('/');
+
+                Rule {
+                    pattern: line.trim_start_matches('/').to_string(),
+                    negated,
+                    dir_only,
+                    anchored,
+                }
+            })
+            .filter(|rule| !rule.pattern.is_empty())
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Check whether a file path (relative to the content root) is ignored.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let segments: Vec<String> = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.matches(&segments) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+impl Rule {
+    /// Match against the file itself or any of its parent directories.
+    fn matches(&self, segments: &[String]) -> bool {
+        (1..=segments.len()).any(|len| {
+            let is_dir = len < segments.len();
+            if self.dir_only && !is_dir {
+                return false;
+            }
+
+            if self.anchored {
+                glob_match(&self.pattern, &segments[..len].join("/"))
+            } else {
+                glob_match(&self.pattern, &segments[len - 1])
+            }
+        })
+    }
+}
+
+/// Match `text` against a glob supporting `*`, `**` and `?`.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_from(&pattern, &text)
+}
+
+fn glob_match_from(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            // `**/` also matches zero directories
+            if let Some(after_slash) = rest.strip_prefix(&['/'][..])
+                && glob_match_from(after_slash, text)
+            {
+                return true;
+            }
+            (0..=text.len()).any(|i| glob_match_from(rest, &text[i..]))
+        }
+        Some('*') => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != '/')
+            .any(|i| glob_match_from(&pattern[1..], &text[i..])),
+        Some('?') => {
+            !text.is_empty() && text[0] != '/' && glob_match_from(&pattern[1..], &text[1..])
+        }
+        Some(c) => text.first() == Some(c) && glob_match_from(&pattern[1..], &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.md", "README.md"));
+        assert!(!glob_match("*.md", "docs/README.md"));
+        assert!(glob_match("docs/**/*.md", "docs/a/b/c.md"));
+        assert!(glob_match("docs/**/*.md", "docs/c.md"));
+        assert!(glob_match("file?.txt", "file1.txt"));
+        assert!(!glob_match("file?.txt", "file10.txt"));
+    }
+
+    #[test]
+    fn test_unanchored_pattern_matches_any_depth() {
+        let rules = IgnoreRules::parse("*.tmp\n.DS_Store\n");
+
+        assert!(rules.is_ignored(Path::new("scratch.tmp")));
+        assert!(rules.is_ignored(Path::new("guidelines/rust/notes.tmp")));
+        assert!(rules.is_ignored(Path::new("skills/.DS_Store")));
+        assert!(!rules.is_ignored(Path::new("guidelines/rust/guide.md")));
+    }
+
+    #[test]
+    fn test_directory_and_anchored_patterns() {
+        let rules = IgnoreRules::parse("# comment\n\ndrafts/\n/templates/local\n");
+
+        assert!(rules.is_ignored(Path::new("drafts/idea.md")));
+        assert!(rules.is_ignored(Path::new("skills/drafts/idea.md")));
+        assert!(rules.is_ignored(Path::new("templates/local/skill.md")));
+        assert!(!rules.is_ignored(Path::new("other/templates/local/skill.md")));
+        // A file named like a directory-only pattern is kept
+        assert!(!rules.is_ignored(Path::new("drafts")));
+    }
+
+    #[test]
+    fn test_negation_reincludes() {
+        let rules = IgnoreRules::parse("*.md\n!keep.md\n");
+
+        assert!(rules.is_ignored(Path::new("drop.md")));
+        assert!(!rules.is_ignored(Path::new("keep.md")));
+    }
+}
@@ -3,31 +3,121 @@
 //! The manifest.json file contains a list of all files in the .aiassisted
 //! directory along with their SHA256 checksums.
 
+use std::io::{Read, Write};
 use std::path::Path;
 
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use crate::core::infra::{FileSystem, HttpClient};
+use crate::core::infra::{Checksum, FileSystem, HttpClient};
 use crate::core::types::{Error, ManifestEntry, Result};
 
+use super::ignore::{IgnoreRules, IGNORE_FILE};
+
+/// File name of the manifest inside a `.aiassisted` directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
 /// Manifest structure matching the JSON format.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Manifest {
     pub version: String,
     pub files: Vec<ManifestEntry>,
+    /// SHA256 of the compact JSON `files` array, guarding against truncation in transit.
+    /// Older manifests omit it, in which case nothing is checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_checksum: Option<String>,
 }
 
 impl Manifest {
     /// Load manifest from a local file.
     pub async fn load_local<F: FileSystem>(fs: &F, path: &Path) -> Result<Self> {
         let content = fs.read(path).await?;
-        serde_json::from_str(&content).map_err(|e| Error::Serialization(e.to_string()))
+        Self::parse_json(content.as_bytes(), &path.display().to_string())
     }
 
     /// Load manifest from a remote URL.
+    ///
+    /// The body is decompressed whenever it is gzip data, whatever the URL or
+    /// `Content-Encoding` says; anything else is parsed as plain JSON.
     pub async fn load_remote<H: HttpClient>(http: &H, url: &str) -> Result<Self> {
-        let content = http.get(url).await?;
-        serde_json::from_str(&content).map_err(|e| Error::Serialization(e.to_string()))
+        let bytes = http.get_bytes(url).await?;
+        Self::parse_bytes(&bytes, url)
+    }
+
+    /// Load a manifest from any reader, such as stdin (`--manifest -`).
+    ///
+    /// Accepts plain or gzip-compressed JSON, like [`Manifest::from_bytes`].
+    pub fn load_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::parse_bytes(&bytes, "manifest")
+    }
+
+    /// Parse a manifest from raw bytes, decompressing gzip data if present.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::parse_bytes(bytes, "manifest")
+    }
+
+    /// Parse raw manifest bytes, naming `source` in error messages.
+    fn parse_bytes(bytes: &[u8], source: &str) -> Result<Self> {
+        // Gzip streams start with the magic bytes 1f 8b
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            let mut json = Vec::new();
+            GzDecoder::new(bytes).read_to_end(&mut json).map_err(|e| {
+                Error::Serialization(format!("Failed to decompress manifest {}: {}", source, e))
+            })?;
+            return Self::parse_json(&json, source);
+        }
+
+        Self::parse_json(bytes, source)
+    }
+
+    /// Parse manifest JSON and check its self-checksum, if it has one.
+    fn parse_json(json: &[u8], source: &str) -> Result<Self> {
+        let manifest: Self = serde_json::from_slice(json).map_err(|e| Error::json(source, &e))?;
+        manifest.verify_self_checksum()?;
+        Ok(manifest)
+    }
+
+    /// Compute the checksum of the `files` array as serialized in compact JSON.
+    pub fn files_checksum(&self) -> Result<String> {
+        let json =
+            serde_json::to_vec(&self.files).map_err(|e| Error::Serialization(e.to_string()))?;
+        Ok(format!("{:x}", Sha256::digest(&json)))
+    }
+
+    /// Record the current `files` checksum in `self_checksum`.
+    pub fn with_self_checksum(mut self) -> Result<Self> {
+        self.self_checksum = Some(self.files_checksum()?);
+        Ok(self)
+    }
+
+    /// Check `self_checksum` against the `files` array; manifests without one pass.
+    pub fn verify_self_checksum(&self) -> Result<()> {
+        let Some(expected) = &self.self_checksum else {
+            return Ok(());
+        };
+
+        let actual = self.files_checksum()?;
+        if *expected != actual {
+            return Err(Error::ChecksumMismatch {
+                expected: expected.clone(),
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Serialize this manifest as gzip-compressed JSON.
+    pub fn to_gzip(&self) -> Result<Vec<u8>> {
+        let json = serde_json::to_vec(self).map_err(|e| Error::Serialization(e.to_string()))?;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&json)?;
+        Ok(encoder.finish()?)
     }
 
     /// Save manifest to a local file.
@@ -37,10 +127,99 @@ impl Manifest {
         fs.write(path, &content).await
     }
 
+    /// Build a manifest for every file beneath `root` (a `.aiassisted` directory).
+    ///
+    /// The manifest itself, the ignore file and anything matched by
+    /// `.aiassistedignore` are excluded. Entries are sorted by path.
+    pub async fn generate<F: FileSystem, C: Checksum>(
+        fs: &F,
+        checksum: &C,
+        root: &Path,
+        version: &str,
+    ) -> Result<Self> {
+        if !fs.is_dir(root) {
+            return Err(Error::NotFound(format!(
+                "Content directory not found: {}",
+                root.display()
+            )));
+        }
+
+        let ignore_path = root.join(IGNORE_FILE);
+        let rules = if fs.exists(&ignore_path) {
+            IgnoreRules::parse(&fs.read(&ignore_path).await?)
+        } else {
+            IgnoreRules::default()
+        };
+
+        let mut files = Vec::new();
+        for path in fs.walk_dir(root).await? {
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            if relative == Path::new(MANIFEST_FILE)
+                || relative == Path::new(IGNORE_FILE)
+                || rules.is_ignored(relative)
+            {
+                continue;
+            }
+
+            let content = fs.read_bytes(&path).await?;
+            files.push(ManifestEntry {
+                path: relative.to_path_buf(),
+                checksum: checksum.sha256(&content),
+            });
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Self {
+            version: version.to_string(),
+            files,
+            self_checksum: None,
+        }
+        .with_self_checksum()
+    }
+
+    /// Verify local files against the checksums recorded in this manifest.
+    ///
+    /// `base_dir` is the `.aiassisted` directory the entry paths are relative to.
+    /// Returns every entry whose file is missing, unreadable, or has different content.
+    pub async fn verify_checksums<F: FileSystem, C: Checksum>(
+        &self,
+        fs: &F,
+        checksum: &C,
+        base_dir: &Path,
+    ) -> Vec<ChecksumFailure> {
+        let mut failures = Vec::new();
+
+        for entry in &self.files {
+            let file_path = base_dir.join(&entry.path);
+            let actual = match fs.read_bytes(&file_path).await {
+                Ok(content) => Some(checksum.sha256(&content)),
+                Err(_) => None,
+            };
+
+            if actual.as_deref() != Some(entry.checksum.as_str()) {
+                failures.push(ChecksumFailure {
+                    entry: entry.clone(),
+                    actual,
+                });
+            }
+        }
+
+        failures
+    }
+
     /// Compare this manifest with another to find differences.
     pub fn diff(&self, other: &Manifest) -> ManifestDiff {
         let mut new_files = Vec::new();
         let mut modified_files = Vec::new();
+        let mut unchanged_files = Vec::new();
+        let removed_files = self
+            .files
+            .iter()
+            .filter(|local| !other.files.iter().any(|e| e.path == local.path))
+            .cloned()
+            .collect();
 
         for other_entry in &other.files {
             match self
@@ -51,6 +230,8 @@ impl Manifest {
                 Some(local_entry) => {
                     if local_entry.checksum != other_entry.checksum {
                         modified_files.push(other_entry.clone());
+                    } else {
+                        unchanged_files.push(other_entry.clone());
                     }
                 }
                 None => {
@@ -62,21 +243,38 @@ impl Manifest {
         ManifestDiff {
             new_files,
             modified_files,
+            unchanged_files,
+            removed_files,
         }
     }
 }
 
+/// A local file that failed checksum verification.
+#[derive(Debug, Clone)]
+pub struct ChecksumFailure {
+    /// The manifest entry that failed.
+    pub entry: ManifestEntry,
+    /// Actual checksum, or `None` if the file is missing or unreadable.
+    pub actual: Option<String>,
+}
+
 /// Difference between two manifests.
-#[derive(Debug)]
+#[derive(Debug, Default, Serialize)]
 pub struct ManifestDiff {
     pub new_files: Vec<ManifestEntry>,
     pub modified_files: Vec<ManifestEntry>,
+    /// Files present in both manifests with the same checksum.
+    pub unchanged_files: Vec<ManifestEntry>,
+    /// Files in this manifest that the other one no longer lists.
+    pub removed_files: Vec<ManifestEntry>,
 }
 
 impl ManifestDiff {
-    /// Check if there are any changes.
+    /// Check if there are any changes, including removals.
     pub fn has_changes(&self) -> bool {
-        !self.new_files.is_empty() || !self.modified_files.is_empty()
+        !self.new_files.is_empty()
+            || !self.modified_files.is_empty()
+            || !self.removed_files.is_empty()
     }
 
     /// Get all files that need to be downloaded.
@@ -92,6 +290,7 @@ impl ManifestDiff {
 mod tests {
     use super::*;
     use mockall::{mock, predicate::*};
+    use crate::core::types::{OutputFormat, OUTPUT_SCHEMA_VERSION};
     use std::path::PathBuf;
 
     // Mock FileSystem for testing
@@ -108,6 +307,9 @@ mod tests {
             async fn create_dir_all(&self, path: &Path) -> Result<()>;
             async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
             async fn copy(&self, from: &Path, to: &Path) -> Result<()>;
+            async fn remove_file(&self, path: &Path) -> Result<()>;
+            async fn remove_dir_all(&self, path: &Path) -> Result<()>;
+            async fn rename(&self, from: &Path, to: &Path) -> Result<()>;
         }
     }
 
@@ -130,9 +332,59 @@ mod tests {
         impl crate::core::infra::Checksum for Checksum {
             fn sha256(&self, content: &[u8]) -> String;
             fn sha256_file(&self, path: &Path) -> Result<String>;
+            fn sha256_reader(&self, reader: &mut dyn std::io::Read) -> Result<String>;
         }
     }
 
+    #[test]
+    fn test_gzip_manifest_roundtrip() {
+        let manifest = Manifest {
+            version: "1.0.0".to_string(),
+            files: vec![ManifestEntry {
+                path: PathBuf::from("guidelines/rust.md"),
+                checksum: "abc123".to_string(),
+            }],
+            self_checksum: None,
+        };
+
+        let compressed = manifest.to_gzip().unwrap();
+        assert!(compressed.starts_with(&[0x1f, 0x8b]));
+
+        let loaded = Manifest::from_bytes(&compressed).unwrap();
+        assert_eq!(loaded.version, "1.0.0");
+        assert_eq!(loaded.files.len(), 1);
+        assert_eq!(loaded.files[0].path, PathBuf::from("guidelines/rust.md"));
+    }
+
+    #[test]
+    fn test_from_bytes_plain_json() {
+        let json = br#"{"version":"2.0.0","files":[]}"#;
+        let manifest = Manifest::from_bytes(json).unwrap();
+        assert_eq!(manifest.version, "2.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_load_remote_gzip() {
+        let manifest = Manifest {
+            version: "1.2.3".to_string(),
+            files: vec![],
+            self_checksum: None,
+        };
+        let compressed = manifest.to_gzip().unwrap();
+
+        let mut mock_http = MockHttpClient::new();
+        mock_http
+            .expect_get_bytes()
+            .with(eq("https://example.com/manifest.json.gz"))
+            .times(1)
+            .returning(move |_| Ok(compressed.clone()));
+
+        let loaded = Manifest::load_remote(&mock_http, "https://example.com/manifest.json.gz")
+            .await
+            .unwrap();
+        assert_eq!(loaded.version, "1.2.3");
+    }
+
     #[test]
     fn test_manifest_diff_no_changes() {
         let manifest1 = Manifest {
@@ -141,6 +393,7 @@ mod tests {
                 path: PathBuf::from("file1.txt"),
                 checksum: "abc123".to_string(),
             }],
+            self_checksum: None,
         };
 
         let manifest2 = manifest1.clone();
@@ -156,6 +409,7 @@ mod tests {
         let manifest1 = Manifest {
             version: "1.0.0".to_string(),
             files: vec![],
+            self_checksum: None,
         };
 
         let manifest2 = Manifest {
@@ -164,6 +418,7 @@ mod tests {
                 path: PathBuf::from("file1.txt"),
                 checksum: "abc123".to_string(),
             }],
+            self_checksum: None,
         };
 
         let diff = manifest1.diff(&manifest2);
@@ -173,6 +428,34 @@ mod tests {
         assert_eq!(diff.modified_files.len(), 0);
     }
 
+    #[test]
+    fn test_manifest_diff_removed_file() {
+        let entry = |name: &str| ManifestEntry {
+            path: PathBuf::from(name),
+            checksum: "abc123".to_string(),
+        };
+        let manifest1 = Manifest {
+            version: "1.0.0".to_string(),
+            files: vec![entry("kept.txt"), entry("dropped.txt")],
+            self_checksum: None,
+        };
+        let manifest2 = Manifest {
+            version: "1.0.1".to_string(),
+            files: vec![entry("kept.txt")],
+            self_checksum: None,
+        };
+
+        let diff = manifest1.diff(&manifest2);
+
+        assert!(diff.has_changes());
+        assert!(diff.new_files.is_empty());
+        assert!(diff.modified_files.is_empty());
+        assert_eq!(diff.removed_files.len(), 1);
+        assert_eq!(diff.removed_files[0].path, PathBuf::from("dropped.txt"));
+        // Removals only run one way
+        assert!(manifest2.diff(&manifest1).removed_files.is_empty());
+    }
+
     #[test]
     fn test_manifest_diff_modified_file() {
         let manifest1 = Manifest {
@@ -181,6 +464,7 @@ mod tests {
                 path: PathBuf::from("file1.txt"),
                 checksum: "abc123".to_string(),
             }],
+            self_checksum: None,
         };
 
         let manifest2 = Manifest {
@@ -189,6 +473,7 @@ mod tests {
                 path: PathBuf::from("file1.txt"),
                 checksum: "def456".to_string(),
             }],
+            self_checksum: None,
         };
 
         let diff = manifest1.diff(&manifest2);
@@ -212,6 +497,7 @@ mod tests {
                     checksum: "old456".to_string(),
                 },
             ],
+            self_checksum: None,
         };
 
         let manifest2 = Manifest {
@@ -230,6 +516,7 @@ mod tests {
                     checksum: "new789".to_string(),
                 },
             ],
+            self_checksum: None,
         };
 
         let diff = manifest1.diff(&manifest2);
@@ -247,6 +534,7 @@ mod tests {
                 path: PathBuf::from("file1.txt"),
                 checksum: "abc123".to_string(),
             }],
+            self_checksum: None,
         };
 
         let manifest2 = Manifest {
@@ -261,6 +549,7 @@ mod tests {
                     checksum: "ghi789".to_string(),
                 },
             ],
+            self_checksum: None,
         };
 
         let diff = manifest1.diff(&manifest2);
@@ -269,16 +558,83 @@ mod tests {
         assert_eq!(to_download.len(), 2); // 1 modified + 1 new
     }
 
+    #[test]
+    fn test_manifest_diff_json_shape() {
+        let local = Manifest {
+            version: "1.0.0".to_string(),
+            files: vec![
+                ManifestEntry {
+                    path: PathBuf::from("guidelines").join("same.md"),
+                    checksum: "a".to_string(),
+                },
+                ManifestEntry {
+                    path: PathBuf::from("changed.md"),
+                    checksum: "b".to_string(),
+                },
+            ],
+            self_checksum: None,
+        };
+        let mut remote = local.clone();
+        remote.files[1].checksum = "c".to_string();
+        remote.files.push(ManifestEntry {
+            path: PathBuf::from("new.md"),
+            checksum: "d".to_string(),
+        });
+
+        let json = serde_json::to_value(local.diff(&remote)).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "new_files": [{"path": "new.md", "checksum": "d"}],
+                "modified_files": [{"path": "changed.md", "checksum": "c"}],
+                "unchanged_files": [{"path": "guidelines/same.md", "checksum": "a"}],
+                "removed_files": [],
+            })
+        );
+    }
+
+    #[test]
+    fn test_manifest_diff_yaml_summary() {
+        let local = Manifest {
+            version: "1.0.0".to_string(),
+            files: vec![ManifestEntry {
+                path: PathBuf::from("changed.md"),
+                checksum: "b".to_string(),
+            }],
+            self_checksum: None,
+        };
+        let mut remote = local.clone();
+        remote.files[0].checksum = "c".to_string();
+
+        let yaml = OutputFormat::Yaml.render(&local.diff(&remote)).unwrap().unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(value["modified_files"][0]["path"], "changed.md");
+        assert_eq!(value["modified_files"][0]["checksum"], "c");
+        assert!(value["new_files"].as_sequence().unwrap().is_empty());
+        assert_eq!(value["schema_version"], OUTPUT_SCHEMA_VERSION);
+
+        let json = OutputFormat::Json.render(&local.diff(&remote)).unwrap().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema_version"], OUTPUT_SCHEMA_VERSION);
+        assert!(json.trim_start_matches("{\n").starts_with("  \"schema_version\""));
+        assert_eq!(value["modified_files"][0]["path"], "changed.md");
+        assert!(OutputFormat::Text.render(&local.diff(&remote)).unwrap().is_none());
+    }
+
     #[test]
     fn test_diff_empty_manifests() {
         let manifest1 = Manifest {
             version: "1.0.0".to_string(),
             files: vec![],
+            self_checksum: None,
         };
 
         let manifest2 = Manifest {
             version: "1.0.1".to_string(),
             files: vec![],
+            self_checksum: None,
         };
 
         let diff = manifest1.diff(&manifest2);
@@ -307,6 +663,32 @@ mod tests {
         assert_eq!(manifest.files[0].path, PathBuf::from("test.txt"));
     }
 
+    #[tokio::test]
+    async fn test_load_reader_matches_load_local() {
+        let fs = crate::infra::MemFileSystem::new();
+        let path = Path::new("/project/.aiassisted/manifest.json");
+        let manifest = Manifest {
+            version: "1.0.0".to_string(),
+            files: vec![ManifestEntry {
+                path: PathBuf::from("guidelines/rust.md"),
+                checksum: "abc123".to_string(),
+            }],
+            self_checksum: None,
+        }
+        .with_self_checksum()
+        .unwrap();
+        manifest.save(&fs, path).await.unwrap();
+        let bytes = fs.read(path).await.unwrap();
+
+        let from_reader = Manifest::load_reader(std::io::Cursor::new(bytes.as_bytes())).unwrap();
+        assert_eq!(from_reader, Manifest::load_local(&fs, path).await.unwrap());
+        assert_eq!(from_reader, manifest);
+
+        let gzipped = manifest.to_gzip().unwrap();
+        assert_eq!(Manifest::load_reader(gzipped.as_slice()).unwrap(), manifest);
+        assert!(Manifest::load_reader("not json".as_bytes()).is_err());
+    }
+
     #[tokio::test]
     async fn test_load_local_invalid_json() {
         let mut mock_fs = MockFileSystem::new();
@@ -317,7 +699,8 @@ mod tests {
             .returning(|_| Ok("invalid json".to_string()));
 
         let result = Manifest::load_local(&mock_fs, Path::new("manifest.json")).await;
-        assert!(result.is_err());
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("manifest.json:1:1 — "), "{}", message);
     }
 
     #[tokio::test]
@@ -339,9 +722,9 @@ mod tests {
         let manifest_json = r#"{"version":"2.0.0","files":[{"path":"remote.txt","checksum":"def456"}]}"#;
 
         mock_http
-            .expect_get()
+            .expect_get_bytes()
             .times(1)
-            .returning(move |_| Ok(manifest_json.to_string()));
+            .returning(move |_| Ok(manifest_json.into()));
 
         let result = Manifest::load_remote(&mock_http, "https://example.com/manifest.json").await;
         assert!(result.is_ok());
@@ -356,14 +739,84 @@ mod tests {
         let mut mock_http = MockHttpClient::new();
 
         mock_http
-            .expect_get()
+            .expect_get_bytes()
             .times(1)
-            .returning(|_| Err(Error::Network("Connection failed".to_string())));
+            .returning(|_| Err(Error::Network("Connection failed".into())));
 
         let result = Manifest::load_remote(&mock_http, "https://example.com/manifest.json").await;
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_verify_checksums_all_valid() {
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_checksum = MockChecksum::new();
+        let manifest = Manifest {
+            version: "1.0.0".to_string(),
+            files: vec![ManifestEntry {
+                path: PathBuf::from("file1.txt"),
+                checksum: "abc123".to_string(),
+            }],
+            self_checksum: None,
+        };
+
+        mock_fs
+            .expect_read()
+            .with(eq(PathBuf::from("/project/.aiassisted/file1.txt")))
+            .times(1)
+            .returning(|_| Ok("content".to_string()));
+        mock_checksum
+            .expect_sha256()
+            .times(1)
+            .returning(|_| "abc123".to_string());
+
+        let failures = manifest
+            .verify_checksums(&mock_fs, &mock_checksum, Path::new("/project/.aiassisted"))
+            .await;
+        assert!(failures.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_checksums_mismatch_and_missing() {
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_checksum = MockChecksum::new();
+        let manifest = Manifest {
+            version: "1.0.0".to_string(),
+            files: vec![
+                ManifestEntry {
+                    path: PathBuf::from("corrupt.txt"),
+                    checksum: "abc123".to_string(),
+                },
+                ManifestEntry {
+                    path: PathBuf::from("missing.txt"),
+                    checksum: "def456".to_string(),
+                },
+            ],
+            self_checksum: None,
+        };
+
+        mock_fs.expect_read().returning(|path| {
+            if path.ends_with("missing.txt") {
+                Err(Error::NotFound("missing".to_string()))
+            } else {
+                Ok("partial".to_string())
+            }
+        });
+        mock_checksum
+            .expect_sha256()
+            .returning(|_| "wrong".to_string());
+
+        let failures = manifest
+            .verify_checksums(&mock_fs, &mock_checksum, Path::new("/base"))
+            .await;
+
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].entry.path, PathBuf::from("corrupt.txt"));
+        assert_eq!(failures[0].actual.as_deref(), Some("wrong"));
+        assert_eq!(failures[1].entry.path, PathBuf::from("missing.txt"));
+        assert!(failures[1].actual.is_none());
+    }
+
     #[tokio::test]
     async fn test_save_success() {
         let mut mock_fs = MockFileSystem::new();
@@ -373,6 +826,7 @@ mod tests {
                 path: PathBuf::from("test.txt"),
                 checksum: "abc123".to_string(),
             }],
+            self_checksum: None,
         };
 
         mock_fs
@@ -383,4 +837,106 @@ mod tests {
         let result = manifest.save(&mock_fs, Path::new("output.json")).await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_self_checksum_matching() {
+        let manifest = Manifest {
+            version: "1.0.0".to_string(),
+            files: vec![ManifestEntry {
+                path: PathBuf::from("guidelines/rust.md"),
+                checksum: "abc123".to_string(),
+            }],
+            self_checksum: None,
+        }
+        .with_self_checksum()
+        .unwrap();
+
+        let json = serde_json::to_vec(&manifest).unwrap();
+        let loaded = Manifest::from_bytes(&json).unwrap();
+        assert_eq!(loaded.self_checksum, manifest.self_checksum);
+
+        // Manifests without a self-checksum are still accepted
+        let legacy = br#"{"version":"1.0.0","files":[]}"#;
+        assert!(Manifest::from_bytes(legacy).unwrap().self_checksum.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_self_checksum_tampered() {
+        let manifest = Manifest {
+            version: "1.0.0".to_string(),
+            files: vec![
+                ManifestEntry {
+                    path: PathBuf::from("a.md"),
+                    checksum: "aaa".to_string(),
+                },
+                ManifestEntry {
+                    path: PathBuf::from("b.md"),
+                    checksum: "bbb".to_string(),
+                },
+            ],
+            self_checksum: None,
+        }
+        .with_self_checksum()
+        .unwrap();
+
+        // Drop an entry as if the list had been truncated
+        let mut tampered = manifest.clone();
+        tampered.files.pop();
+        let json = serde_json::to_string(&tampered).unwrap();
+
+        let mut mock_http = MockHttpClient::new();
+        mock_http
+            .expect_get_bytes()
+            .returning(move |_| Ok(json.clone().into_bytes()));
+
+        let result = Manifest::load_remote(&mock_http, "https://example.com/manifest.json").await;
+        assert!(matches!(
+            result,
+            Err(Error::ChecksumMismatch { expected, .. })
+                if Some(&expected) == manifest.self_checksum.as_ref()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_generate_round_trip() {
+        use crate::infra::{Sha2Checksum, StdFileSystem};
+
+        let fs = StdFileSystem::new();
+        let checksum = Sha2Checksum::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().join(".aiassisted");
+
+        fs.write(&root.join("guidelines/rust/guide.md"), "guide").await.unwrap();
+        fs.write(&root.join("prompts/commit.md"), "commit").await.unwrap();
+        fs.write(&root.join("drafts/wip.md"), "wip").await.unwrap();
+        fs.write(&root.join("notes.tmp"), "tmp").await.unwrap();
+        fs.write(&root.join(IGNORE_FILE), "drafts/\n*.tmp\n").await.unwrap();
+        fs.write(&root.join(MANIFEST_FILE), "{}").await.unwrap();
+
+        let manifest = Manifest::generate(&fs, &checksum, &root, "abc123").await.unwrap();
+        assert_eq!(manifest.self_checksum, Some(manifest.files_checksum().unwrap()));
+
+        let paths: Vec<&Path> = manifest.files.iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![Path::new("guidelines/rust/guide.md"), Path::new("prompts/commit.md")]
+        );
+        assert_eq!(manifest.version, "abc123");
+
+        // Save, reload and verify against the files on disk
+        manifest.save(&fs, &root.join(MANIFEST_FILE)).await.unwrap();
+        let loaded = Manifest::load_local(&fs, &root.join(MANIFEST_FILE)).await.unwrap();
+        assert_eq!(loaded.files.len(), 2);
+        assert!(loaded.verify_checksums(&fs, &checksum, &root).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_generate_missing_root() {
+        let mut mock_fs = MockFileSystem::new();
+        mock_fs.expect_is_dir().returning(|_| false);
+        let checksum = crate::infra::Sha2Checksum::new();
+
+        let result = Manifest::generate(&mock_fs, &checksum, Path::new("/missing"), "v1").await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
 }
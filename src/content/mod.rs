@@ -3,9 +3,17 @@
 //! This module handles installing, updating, and checking the .aiassisted
 //! directory structure that contains guidelines, templates, and instructions.
 
+pub mod backup;
 pub mod commands;
 pub mod github;
+pub mod ignore;
 pub mod manifest;
 pub mod sync;
 
-pub use commands::{CheckCommand, InstallCommand, UpdateCommand};
+pub use commands::{
+    ChangelogCommand, CheckCommand, ContentFileStatus, ContentListCommand, ContentListing,
+    InstallCommand, ManagedFile, ManifestGenerateCommand, RepairCommand, UpdateCommand,
+    WatchCommand,
+};
+pub use github::ContentSource;
+pub use sync::{CheckReport, InstallReport, UpdateReport};
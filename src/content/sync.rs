@@ -1,37 +1,195 @@
 //! Sync logic for installing and updating .aiassisted content.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use crate::core::infra::{Checksum, FileSystem, HttpClient, Logger};
-use crate::core::types::Result;
+use crate::core::project::ensure_removable;
+use crate::core::types::{Error, ManifestEntry, Result};
 
-use super::github;
-use super::manifest::Manifest;
+use super::github::{self, ContentSource};
+use super::manifest::{Manifest, ManifestDiff};
+
+/// Outcome of an install.
+///
+/// Paths are inside the `.aiassisted` directory. Empty if the directory
+/// already existed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstallReport {
+    /// Files downloaded and written.
+    pub downloaded: Vec<PathBuf>,
+    /// Files in the manifest that were not written (every file on a dry run).
+    pub skipped: Vec<PathBuf>,
+    /// Version of the installed manifest.
+    pub manifest_version: String,
+    /// Total bytes downloaded.
+    pub bytes: u64,
+    /// Wall-clock time the install took.
+    pub elapsed: Duration,
+}
+
+/// Outcome of an update.
+///
+/// Paths are inside the `.aiassisted` directory. Empty if nothing is
+/// installed or on rollback.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateReport {
+    /// Files downloaded and written.
+    pub downloaded: Vec<PathBuf>,
+    /// Files left as they were because they are unchanged upstream
+    /// (every file on a dry run).
+    pub skipped: Vec<PathBuf>,
+    /// Files deleted because the remote manifest dropped them (`--prune`).
+    pub removed: Vec<PathBuf>,
+    /// Version of the remote manifest.
+    pub manifest_version: String,
+    /// Backup taken before updating, if any.
+    pub backup: Option<PathBuf>,
+    /// Total bytes downloaded.
+    pub bytes: u64,
+    /// Wall-clock time the update took, excluding any backup.
+    pub elapsed: Duration,
+}
+
+/// Outcome of a check.
+///
+/// Empty if nothing is installed.
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    /// Version of the local manifest.
+    pub local_version: String,
+    /// Version of the remote manifest.
+    pub remote_version: String,
+    /// Changes from the local manifest to the remote one.
+    pub diff: ManifestDiff,
+    /// Installed files that failed checksum verification.
+    pub failed: Vec<PathBuf>,
+}
+
+/// Format a byte count with binary units, e.g. `1.5 MB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+    format!("{:.1} {}", size, unit)
+}
+
+/// Paths of `entries` inside the `.aiassisted` directory of `target_dir`.
+fn content_paths(target_dir: &Path, entries: &[ManifestEntry]) -> Vec<PathBuf> {
+    let aiassisted_dir = target_dir.join(".aiassisted");
+    entries
+        .iter()
+        .map(|entry| aiassisted_dir.join(&entry.path))
+        .collect()
+}
+
+/// Save `manifest` when a `keep_going` batch failed part-way.
+///
+/// The files that did download now match it, so `repair` only has to fetch the
+/// failed ones. The batch result is passed through unchanged.
+async fn keep_partial<F: FileSystem, L: Logger>(
+    fs: &F,
+    logger: &L,
+    manifest: &Manifest,
+    manifest_path: &Path,
+    keep_going: bool,
+    downloads: Result<github::Downloads>,
+) -> Result<github::Downloads> {
+    if keep_going && downloads.is_err() {
+        manifest.save(fs, manifest_path).await?;
+        logger.warn("Kept the files that downloaded. Run 'aiassisted repair' to retry the rest.");
+    }
+    downloads
+}
+
+/// Like [`keep_partial`], for an update from the `local` to the `remote` manifest.
+///
+/// Only files that now match the remote checksum are recorded at it. Files
+/// whose download failed keep their local entry at the old checksum, or are
+/// left out if they are new, so the next update fetches them again. Orphans
+/// stay listed as they do after an update without `--prune`.
+async fn keep_partial_update<F: FileSystem, C: Checksum, L: Logger>(
+    fs: &F,
+    checksum: &C,
+    logger: &L,
+    (local, remote): (&Manifest, &Manifest),
+    aiassisted_dir: &Path,
+    keep_going: bool,
+    downloads: Result<github::Downloads>,
+) -> Result<github::Downloads> {
+    if !keep_going || downloads.is_ok() {
+        return downloads;
+    }
+
+    let mut files = Vec::new();
+    for entry in &remote.files {
+        let path = aiassisted_dir.join(&entry.path);
+        let current = match fs.read_bytes(&path).await {
+            Ok(content) => checksum.sha256(&content) == entry.checksum,
+            Err(_) => false,
+        };
+        if current {
+            files.push(entry.clone());
+        } else if let Some(old) = local.files.iter().find(|e| e.path == entry.path) {
+            files.push(old.clone());
+        }
+    }
+    let installed = Manifest {
+        files,
+        self_checksum: None,
+        ..remote.clone()
+    };
+    let manifest = recorded_manifest(&installed, &local.diff(remote).removed_files);
+
+    manifest.save(fs, &aiassisted_dir.join("manifest.json")).await?;
+    logger.warn("Kept the files that downloaded. Run 'aiassisted update' again to retry the rest.");
+    downloads
+}
 
 /// Install .aiassisted to a target directory.
+///
+/// With `keep_going`, a failed download does not stop the others, and the
+/// manifest is saved anyway so `repair` can fetch the missing files.
+#[allow(clippy::too_many_arguments)]
 pub async fn install<F, H, C, L>(
     fs: &F,
     http: &H,
     checksum: &C,
     logger: &L,
+    source: &ContentSource,
     target_dir: &Path,
-) -> Result<()>
+    concurrency: usize,
+    keep_going: bool,
+) -> Result<InstallReport>
 where
     F: FileSystem,
     H: HttpClient,
     C: Checksum,
     L: Logger,
 {
+    let started = Instant::now();
     let aiassisted_dir = target_dir.join(".aiassisted");
 
     // Check if already installed
     if fs.exists(&aiassisted_dir) {
         logger.warn("Directory .aiassisted already exists. Use 'update' to update it.");
-        return Ok(());
+        return Ok(InstallReport::default());
     }
 
     logger.info("Downloading manifest...");
-    let manifest = Manifest::load_remote(http, &github::manifest_url()).await?;
+    let manifest = source.load_manifest(http).await?;
 
     logger.info(&format!(
         "Manifest loaded: version {}, {} files",
@@ -44,42 +202,128 @@ where
 
     // Download all files
     logger.info("Downloading files...");
-    let downloaded = github::download_batch(http, checksum, fs, &manifest.files, target_dir).await?;
+    let manifest_path = aiassisted_dir.join("manifest.json");
+    let downloads = github::download_batch(
+        http,
+        checksum,
+        fs,
+        source,
+        &manifest.files,
+        target_dir,
+        concurrency,
+        keep_going,
+    )
+    .await;
+    let downloads = keep_partial(fs, logger, &manifest, &manifest_path, keep_going, downloads)
+        .await?;
 
+    // Save manifest locally
+    manifest.save(fs, &manifest_path).await?;
+
+    let elapsed = started.elapsed();
     logger.success(&format!(
-        "Successfully installed {} files to {}",
-        downloaded.len(),
+        "Installed {} files ({}) in {:.1}s to {}",
+        downloads.paths.len(),
+        format_bytes(downloads.bytes),
+        elapsed.as_secs_f64(),
         aiassisted_dir.display()
     ));
 
-    // Save manifest locally
-    let manifest_path = aiassisted_dir.join("manifest.json");
-    manifest.save(fs, &manifest_path).await?;
+    Ok(InstallReport {
+        downloaded: downloads.paths,
+        skipped: Vec::new(),
+        manifest_version: manifest.version,
+        bytes: downloads.bytes,
+        elapsed,
+    })
+}
+
+/// Preview an installation without downloading content or writing to disk.
+///
+/// Only the remote manifest is fetched.
+pub async fn install_dry_run<F, H, L>(
+    fs: &F,
+    http: &H,
+    logger: &L,
+    source: &ContentSource,
+    target_dir: &Path,
+) -> Result<InstallReport>
+where
+    F: FileSystem,
+    H: HttpClient,
+    L: Logger,
+{
+    let aiassisted_dir = target_dir.join(".aiassisted");
+
+    // Check if already installed
+    if fs.exists(&aiassisted_dir) {
+        logger.warn("Directory .aiassisted already exists. Use 'update' to update it.");
+        return Ok(InstallReport::default());
+    }
+
+    logger.info("Downloading manifest...");
+    let manifest = source.load_manifest(http).await?;
 
-    Ok(())
+    logger.info(&format!(
+        "Manifest loaded: version {}, {} files",
+        manifest.version,
+        manifest.files.len()
+    ));
+
+    logger.info("Files that would be downloaded:");
+    for entry in &manifest.files {
+        logger.info(&format!(
+            "  + {} -> {}",
+            entry.path.display(),
+            aiassisted_dir.join(&entry.path).display()
+        ));
+    }
+
+    logger.info(&format!(
+        "Dry run: {} files would be installed to {}",
+        manifest.files.len(),
+        aiassisted_dir.display()
+    ));
+
+    Ok(InstallReport {
+        skipped: content_paths(target_dir, &manifest.files),
+        manifest_version: manifest.version,
+        ..InstallReport::default()
+    })
 }
 
 /// Update existing .aiassisted installation.
+///
+/// `keep_going` behaves as for [`install`], except that the saved manifest
+/// only moves files that did download to their remote checksum.
+/// With `prune`, files the remote manifest no longer lists are deleted;
+/// otherwise they are left in place and reported.
+#[allow(clippy::too_many_arguments)]
 pub async fn update<F, H, C, L>(
     fs: &F,
     http: &H,
     checksum: &C,
     logger: &L,
+    source: &ContentSource,
     target_dir: &Path,
     force: bool,
-) -> Result<()>
+    prune: bool,
+    concurrency: usize,
+    keep_going: bool,
+) -> Result<UpdateReport>
 where
     F: FileSystem,
     H: HttpClient,
     C: Checksum,
     L: Logger,
 {
+    let started = Instant::now();
     let aiassisted_dir = target_dir.join(".aiassisted");
 
     // Check if installed
     if !fs.exists(&aiassisted_dir) {
         logger.warn("Directory .aiassisted not found. Use 'install' first.");
-        return Ok(());
+        return Ok(UpdateReport::default());
     }
 
     logger.info("Checking for updates...");
@@ -87,46 +331,275 @@ where
     // Load local and remote manifests
     let local_manifest_path = aiassisted_dir.join("manifest.json");
     let local_manifest = Manifest::load_local(fs, &local_manifest_path).await?;
-    let remote_manifest = Manifest::load_remote(http, &github::manifest_url()).await?;
+    let remote_manifest = source.load_manifest(http).await?;
 
     logger.info(&format!(
         "Local: v{}, Remote: v{}",
         local_manifest.version, remote_manifest.version
     ));
 
-    if force {
+    let report = if force {
         logger.info("Force update: downloading all files...");
-        let downloaded =
-            github::download_batch(http, checksum, fs, &remote_manifest.files, target_dir).await?;
+        let downloads = github::download_batch(
+            http,
+            checksum,
+            fs,
+            source,
+            &remote_manifest.files,
+            target_dir,
+            concurrency,
+            keep_going,
+        )
+        .await;
+        let downloads = keep_partial_update(
+            fs,
+            checksum,
+            logger,
+            (&local_manifest, &remote_manifest),
+            &aiassisted_dir,
+            keep_going,
+            downloads,
+        )
+        .await?;
 
-        logger.success(&format!("Updated {} files (forced)", downloaded.len()));
+        UpdateReport {
+            downloaded: downloads.paths,
+            manifest_version: remote_manifest.version.clone(),
+            bytes: downloads.bytes,
+            ..UpdateReport::default()
+        }
     } else {
         // Compare manifests
         let diff = local_manifest.diff(&remote_manifest);
+        let skipped = content_paths(target_dir, &diff.unchanged_files);
 
         if !diff.has_changes() {
             logger.info("No updates available.");
-            return Ok(());
+            return Ok(UpdateReport {
+                skipped,
+                manifest_version: remote_manifest.version,
+                ..UpdateReport::default()
+            });
         }
 
         logger.info(&format!(
-            "Updates available: {} new, {} modified",
+            "Updates available: {} new, {} modified, {} removed",
             diff.new_files.len(),
-            diff.modified_files.len()
+            diff.modified_files.len(),
+            diff.removed_files.len()
         ));
 
         // Download only changed files
         let files_to_download = diff.files_to_download();
-        let downloaded =
-            github::download_batch(http, checksum, fs, &files_to_download, target_dir).await?;
+        let downloads = github::download_batch(
+            http,
+            checksum,
+            fs,
+            source,
+            &files_to_download,
+            target_dir,
+            concurrency,
+            keep_going,
+        )
+        .await;
+        let downloads = keep_partial_update(
+            fs,
+            checksum,
+            logger,
+            (&local_manifest, &remote_manifest),
+            &aiassisted_dir,
+            keep_going,
+            downloads,
+        )
+        .await?;
+
+        UpdateReport {
+            downloaded: downloads.paths,
+            skipped,
+            manifest_version: remote_manifest.version.clone(),
+            bytes: downloads.bytes,
+            ..UpdateReport::default()
+        }
+    };
 
-        logger.success(&format!("Updated {} files", downloaded.len()));
+    let orphans = local_manifest.diff(&remote_manifest).removed_files;
+    let (removed, kept_orphans) = if prune {
+        let removed = prune_orphans(fs, checksum, logger, &aiassisted_dir, &orphans).await?;
+        (removed, Vec::new())
+    } else {
+        if !orphans.is_empty() {
+            logger.warn(&format!(
+                "{} file(s) no longer in the manifest were left in place. Use --prune to delete them.",
+                orphans.len()
+            ));
+        }
+        (Vec::new(), orphans)
+    };
+
+    // Save updated manifest, still listing orphans until they are pruned
+    recorded_manifest(&remote_manifest, &kept_orphans)
+        .save(fs, &local_manifest_path)
+        .await?;
+
+    let elapsed = started.elapsed();
+    logger.success(&format!(
+        "Updated {} files ({}){} in {:.1}s{}",
+        report.downloaded.len(),
+        format_bytes(report.bytes),
+        if removed.is_empty() {
+            String::new()
+        } else {
+            format!(", removed {}", removed.len())
+        },
+        elapsed.as_secs_f64(),
+        if force { " (forced)" } else { "" }
+    ));
+
+    Ok(UpdateReport {
+        removed,
+        elapsed,
+        ..report
+    })
+}
+
+/// The manifest an update records: `remote`, plus `kept` local entries.
+///
+/// Orphans left in place stay listed so a later `update --prune` still finds
+/// them. The remote self-checksum no longer covers the list once it differs.
+fn recorded_manifest(remote: &Manifest, kept: &[ManifestEntry]) -> Manifest {
+    let mut manifest = remote.clone();
+    if !kept.is_empty() {
+        manifest.files.extend(kept.iter().cloned());
+        manifest.self_checksum = None;
     }
+    manifest
+}
 
-    // Save updated manifest
-    remote_manifest.save(fs, &local_manifest_path).await?;
+/// Delete the installed copies of `orphans`, returning the paths removed.
+///
+/// Each path must resolve inside `aiassisted_dir`; a manifest entry pointing
+/// elsewhere aborts the prune. Files already gone are skipped, and so are
+/// files edited since they were installed, which are reported instead.
+async fn prune_orphans<F: FileSystem, C: Checksum, L: Logger>(
+    fs: &F,
+    checksum: &C,
+    logger: &L,
+    aiassisted_dir: &Path,
+    orphans: &[ManifestEntry],
+) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    for entry in orphans {
+        let path = aiassisted_dir.join(&entry.path);
+        ensure_removable(&path, aiassisted_dir)?;
+        if !fs.exists(&path) {
+            continue;
+        }
+        if checksum.sha256(&fs.read_bytes(&path).await?) != entry.checksum {
+            logger.warn(&format!(
+                "Kept {}: modified since it was installed",
+                entry.path.display()
+            ));
+            continue;
+        }
+        fs.remove_file(&path).await?;
+        logger.info(&format!("  - {}", entry.path.display()));
+        removed.push(path);
+    }
+    Ok(removed)
+}
 
-    Ok(())
+/// Preview an update without downloading content or writing to disk.
+///
+/// Only the remote manifest is fetched. With `force`, every remote file is
+/// listed as it would be re-downloaded. Files dropped from the remote manifest
+/// are listed as deleted with `prune` and as left in place without it.
+pub async fn update_dry_run<F, H, L>(
+    fs: &F,
+    http: &H,
+    logger: &L,
+    source: &ContentSource,
+    target_dir: &Path,
+    force: bool,
+    prune: bool,
+) -> Result<UpdateReport>
+where
+    F: FileSystem,
+    H: HttpClient,
+    L: Logger,
+{
+    let aiassisted_dir = target_dir.join(".aiassisted");
+
+    // Check if installed
+    if !fs.exists(&aiassisted_dir) {
+        logger.warn("Directory .aiassisted not found. Use 'install' first.");
+        return Ok(UpdateReport::default());
+    }
+
+    let local_manifest = Manifest::load_local(fs, &aiassisted_dir.join("manifest.json")).await?;
+    let remote_manifest = source.load_manifest(http).await?;
+
+    logger.info(&format!(
+        "Local: v{}, Remote: v{}",
+        local_manifest.version, remote_manifest.version
+    ));
+
+    let diff = local_manifest.diff(&remote_manifest);
+    let mut changes: Vec<(&str, &ManifestEntry)> = diff
+        .new_files
+        .iter()
+        .map(|entry| ("+", entry))
+        .chain(diff.modified_files.iter().map(|entry| ("~", entry)))
+        .collect();
+    if force {
+        changes.extend(diff.unchanged_files.iter().map(|entry| ("=", entry)));
+    }
+
+    if changes.is_empty() && diff.removed_files.is_empty() {
+        logger.info("No updates available.");
+    } else if !changes.is_empty() {
+        logger.info("Files that would be downloaded:");
+        for (marker, entry) in &changes {
+            logger.info(&format!(
+                "  {} {} -> {}",
+                marker,
+                entry.path.display(),
+                aiassisted_dir.join(&entry.path).display()
+            ));
+        }
+    }
+
+    if !diff.removed_files.is_empty() {
+        logger.info(if prune {
+            "Files that would be deleted:"
+        } else {
+            "Files no longer in the manifest (kept without --prune):"
+        });
+        for entry in &diff.removed_files {
+            logger.info(&format!(
+                "  - {} -> {}",
+                entry.path.display(),
+                aiassisted_dir.join(&entry.path).display()
+            ));
+        }
+    }
+
+    logger.info(&format!(
+        "Dry run: {} new, {} modified{} would be written to {}",
+        diff.new_files.len(),
+        diff.modified_files.len(),
+        if force {
+            format!(", {} unchanged (forced)", diff.unchanged_files.len())
+        } else {
+            String::new()
+        },
+        aiassisted_dir.display()
+    ));
+
+    Ok(UpdateReport {
+        skipped: content_paths(target_dir, &remote_manifest.files),
+        manifest_version: remote_manifest.version,
+        ..UpdateReport::default()
+    })
 }
 
 /// Check for updates without downloading.
@@ -134,8 +607,9 @@ pub async fn check<F, H, L>(
     fs: &F,
     http: &H,
     logger: &L,
+    source: &ContentSource,
     target_dir: &Path,
-) -> Result<()>
+) -> Result<CheckReport>
 where
     F: FileSystem,
     H: HttpClient,
@@ -146,7 +620,7 @@ where
     // Check if installed
     if !fs.exists(&aiassisted_dir) {
         logger.warn("Directory .aiassisted not found. Use 'install' first.");
-        return Ok(());
+        return Ok(CheckReport::default());
     }
 
     logger.info("Checking for updates...");
@@ -154,7 +628,7 @@ where
     // Load local and remote manifests
     let local_manifest_path = aiassisted_dir.join("manifest.json");
     let local_manifest = Manifest::load_local(fs, &local_manifest_path).await?;
-    let remote_manifest = Manifest::load_remote(http, &github::manifest_url()).await?;
+    let remote_manifest = source.load_manifest(http).await?;
 
     logger.info(&format!(
         "Local: v{}, Remote: v{}",
@@ -163,16 +637,24 @@ where
 
     // Compare manifests
     let diff = local_manifest.diff(&remote_manifest);
+    let report = CheckReport {
+        local_version: local_manifest.version,
+        remote_version: remote_manifest.version,
+        diff,
+        failed: Vec::new(),
+    };
+    let diff = &report.diff;
 
     if !diff.has_changes() {
         logger.success("No updates available. You're up to date!");
-        return Ok(());
+        return Ok(report);
     }
 
     logger.info(&format!(
-        "Updates available: {} new, {} modified",
+        "Updates available: {} new, {} modified, {} removed",
         diff.new_files.len(),
-        diff.modified_files.len()
+        diff.modified_files.len(),
+        diff.removed_files.len()
     ));
 
     // List new files
@@ -191,14 +673,188 @@ where
         }
     }
 
+    // List files dropped upstream
+    if !diff.removed_files.is_empty() {
+        logger.info("Removed files:");
+        for entry in &diff.removed_files {
+            logger.info(&format!("  - {}", entry.path.display()));
+        }
+    }
+
     logger.info("Run 'aiassisted update' to download updates.");
 
-    Ok(())
+    Ok(report)
+}
+
+/// List files added or modified upstream since manifest version `since`.
+///
+/// The local manifest is used as the baseline when it matches `since`;
+/// otherwise the manifest for that version is fetched by git ref. The current
+/// manifest comes from `source`.
+pub async fn changelog<F, H, L>(
+    fs: &F,
+    http: &H,
+    logger: &L,
+    source: &ContentSource,
+    target_dir: &Path,
+    since: &str,
+) -> Result<ManifestDiff>
+where
+    F: FileSystem,
+    H: HttpClient,
+    L: Logger,
+{
+    let local_manifest_path = target_dir.join(".aiassisted").join("manifest.json");
+    let local_manifest = if fs.exists(&local_manifest_path) {
+        Some(Manifest::load_local(fs, &local_manifest_path).await?)
+    } else {
+        None
+    };
+
+    let base_manifest = match local_manifest {
+        Some(manifest) if manifest.version == since => manifest,
+        _ => {
+            logger.info(&format!("Fetching manifest for v{}...", since));
+            Manifest::load_remote(http, &github::manifest_url_at(since))
+                .await
+                .map_err(|e| match e {
+                    Error::Network(msg) => Error::NotFound(format!(
+                        "Manifest for version {} not available: {}",
+                        since, msg
+                    )),
+                    other => other,
+                })?
+        }
+    };
+    let remote_manifest = source.load_manifest(http).await?;
+
+    logger.info(&format!(
+        "Changes since v{} (current: v{})",
+        base_manifest.version, remote_manifest.version
+    ));
+
+    let diff = base_manifest.diff(&remote_manifest);
+
+    if !diff.has_changes() {
+        logger.success("No files changed.");
+        return Ok(diff);
+    }
+
+    for entry in &diff.new_files {
+        logger.info(&format!("  + {}", entry.path.display()));
+    }
+    for entry in &diff.modified_files {
+        logger.info(&format!("  ~ {}", entry.path.display()));
+    }
+    for entry in &diff.removed_files {
+        logger.info(&format!("  - {}", entry.path.display()));
+    }
+
+    logger.info(&format!(
+        "{} new, {} modified, {} removed",
+        diff.new_files.len(),
+        diff.modified_files.len(),
+        diff.removed_files.len()
+    ));
+
+    Ok(diff)
+}
+
+/// Repair an installation by re-downloading files that fail verification.
+///
+/// Returns the number of files repaired.
+#[allow(clippy::too_many_arguments)]
+pub async fn repair<F, H, C, L>(
+    fs: &F,
+    http: &H,
+    checksum: &C,
+    logger: &L,
+    source: &ContentSource,
+    target_dir: &Path,
+    concurrency: usize,
+    keep_going: bool,
+) -> Result<usize>
+where
+    F: FileSystem,
+    H: HttpClient,
+    C: Checksum,
+    L: Logger,
+{
+    let aiassisted_dir = target_dir.join(".aiassisted");
+
+    // Check if installed
+    if !fs.exists(&aiassisted_dir) {
+        logger.warn("Directory .aiassisted not found. Use 'install' first.");
+        return Ok(0);
+    }
+
+    logger.info("Verifying installed files...");
+
+    let local_manifest_path = aiassisted_dir.join("manifest.json");
+    let manifest = Manifest::load_local(fs, &local_manifest_path).await?;
+
+    let failures = manifest.verify_checksums(fs, checksum, &aiassisted_dir).await;
+
+    if failures.is_empty() {
+        logger.success(&format!(
+            "All {} files verified. Nothing to repair.",
+            manifest.files.len()
+        ));
+        return Ok(0);
+    }
+
+    logger.info(&format!("{} file(s) failed verification:", failures.len()));
+    for failure in &failures {
+        let reason = if failure.actual.is_some() { "corrupt" } else { "missing" };
+        logger.info(&format!("  ! {} ({})", failure.entry.path.display(), reason));
+    }
+
+    // Re-download only the failing files
+    logger.info("Re-downloading files...");
+    let to_repair: Vec<_> = failures.into_iter().map(|f| f.entry).collect();
+    github::download_batch(
+        http,
+        checksum,
+        fs,
+        source,
+        &to_repair,
+        target_dir,
+        concurrency,
+        keep_going,
+    )
+    .await?;
+
+    // Re-verify the repaired files
+    let repaired_manifest = Manifest {
+        version: manifest.version.clone(),
+        files: to_repair,
+        self_checksum: None,
+    };
+    let remaining = repaired_manifest
+        .verify_checksums(fs, checksum, &aiassisted_dir)
+        .await;
+
+    if let Some(failure) = remaining.first() {
+        logger.error(&format!(
+            "{} file(s) still fail verification after repair",
+            remaining.len()
+        ));
+        return Err(Error::ChecksumMismatch {
+            expected: failure.entry.checksum.clone(),
+            actual: failure.actual.clone().unwrap_or_else(|| "missing".to_string()),
+        });
+    }
+
+    let repaired = repaired_manifest.files.len();
+    logger.success(&format!("Repaired {} file(s)", repaired));
+
+    Ok(repaired)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::infra::DownloadOptions;
     use mockall::{mock, predicate::*};
     use std::path::PathBuf;
     use tempfile::TempDir;
@@ -217,6 +873,9 @@ mod tests {
             async fn create_dir_all(&self, path: &Path) -> Result<()>;
             async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
             async fn copy(&self, from: &Path, to: &Path) -> Result<()>;
+            async fn remove_file(&self, path: &Path) -> Result<()>;
+            async fn remove_dir_all(&self, path: &Path) -> Result<()>;
+            async fn rename(&self, from: &Path, to: &Path) -> Result<()>;
         }
     }
 
@@ -228,6 +887,12 @@ mod tests {
             async fn get(&self, url: &str) -> Result<String>;
             async fn get_bytes(&self, url: &str) -> Result<Vec<u8>>;
             async fn download(&self, url: &str, dest: &Path) -> Result<()>;
+            async fn download_with(
+                &self,
+                url: &str,
+                dest: &Path,
+                options: DownloadOptions,
+            ) -> Result<u64>;
         }
     }
 
@@ -237,6 +902,7 @@ mod tests {
         impl crate::core::infra::Checksum for Checksum {
             fn sha256(&self, content: &[u8]) -> String;
             fn sha256_file(&self, path: &Path) -> Result<String>;
+            fn sha256_reader(&self, reader: &mut dyn std::io::Read) -> Result<String>;
         }
     }
 
@@ -274,13 +940,196 @@ mod tests {
             &mock_http,
             &mock_checksum,
             &mock_logger,
+            &ContentSource::default(),
             temp_dir.path(),
+            1,
+            false,
         )
         .await;
 
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_install_dry_run_writes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_http = MockHttpClient::new();
+        let mut mock_logger = MockLogger::new();
+
+        // No write/create_dir_all expectations: any disk write fails the test
+        mock_fs.expect_exists().times(1).returning(|_| false);
+
+        // Only the manifest is fetched
+        mock_http
+            .expect_get_bytes()
+            .times(1)
+            .withf(|url: &str| url.ends_with("manifest.json"))
+            .returning(|_| {
+                Ok(r#"{"version":"1.0.0","files":[
+                    {"path":"guidelines/a.md","checksum":"aaa"},
+                    {"path":"prompts/b.md","checksum":"bbb"}
+                ]}"#
+                    .into())
+            });
+
+        mock_logger.expect_info().return_const(());
+
+        let source = ContentSource::default();
+        let report = install_dry_run(&mock_fs, &mock_http, &mock_logger, &source, temp_dir.path())
+            .await
+            .unwrap();
+
+        assert!(!temp_dir.path().join(".aiassisted").exists());
+        assert!(report.downloaded.is_empty());
+        assert_eq!(report.skipped.len(), 2);
+        assert_eq!(report.manifest_version, "1.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_update_reports_downloaded_and_skipped_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_http = MockHttpClient::new();
+        let mut mock_checksum = MockChecksum::new();
+        let mut mock_logger = MockLogger::new();
+
+        mock_fs.expect_exists().returning(|_| true);
+        mock_fs.expect_read().returning(|_| {
+            Ok(r#"{"version":"1.0.0","files":[
+                {"path":"a.md","checksum":"a"},
+                {"path":"b.md","checksum":"b"}
+            ]}"#
+            .to_string())
+        });
+        mock_fs.expect_create_dir_all().returning(|_| Ok(()));
+        mock_fs.expect_write().returning(|_, _| Ok(()));
+
+        mock_http
+            .expect_get_bytes()
+            .times(1)
+            .withf(|url: &str| url.ends_with("manifest.json"))
+            .returning(|_| {
+            Ok(r#"{"version":"2.0.0","files":[
+                {"path":"a.md","checksum":"a"},
+                {"path":"b.md","checksum":"b2"},
+                {"path":"c.md","checksum":"c"}
+            ]}"#
+            .into())
+        });
+        mock_http
+            .expect_download_with()
+            .times(2)
+            .withf(|_, _, options| options.resume)
+            .returning(|url, _, _| Ok(if url.ends_with("/b.md") { 2 } else { 1 }));
+        mock_fs
+            .expect_rename()
+            .times(2)
+            .withf(|from: &Path, to: &Path| from.with_extension("") == to)
+            .returning(|_, _| Ok(()));
+
+        // Content doubles as its checksum to keep the mock simple
+        mock_checksum
+            .expect_sha256()
+            .returning(|content| String::from_utf8_lossy(content).to_string());
+        mock_checksum.expect_sha256_file().returning(|path| {
+            Ok(if path.ends_with("b.md.download") { "b2" } else { "c" }.to_string())
+        });
+
+        mock_logger.expect_info().return_const(());
+        mock_logger
+            .expect_success()
+            .withf(|msg: &str| msg.starts_with("Updated 2 files (3 B) in "))
+            .return_const(());
+
+        let report = update(
+            &mock_fs,
+            &mock_http,
+            &mock_checksum,
+            &mock_logger,
+            &ContentSource::default(),
+            temp_dir.path(),
+            false,
+            false,
+            1,
+            false,
+        )
+        .await
+        .unwrap();
+
+        let content_dir = temp_dir.path().join(".aiassisted");
+        assert_eq!(
+            report.downloaded,
+            vec![content_dir.join("c.md"), content_dir.join("b.md")]
+        );
+        assert_eq!(report.skipped, vec![content_dir.join("a.md")]);
+        assert_eq!(report.manifest_version, "2.0.0");
+        assert_eq!(report.backup, None);
+        assert_eq!(report.bytes, 3);
+    }
+
+    #[tokio::test]
+    async fn test_update_dry_run_writes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_http = MockHttpClient::new();
+        let mut mock_logger = MockLogger::new();
+
+        // No write/create_dir_all expectations: any disk write fails the test
+        mock_fs.expect_exists().returning(|_| true);
+        mock_fs.expect_read().returning(|_| {
+            Ok(r#"{"version":"1.0.0","files":[
+                {"path":"a.md","checksum":"a"},
+                {"path":"b.md","checksum":"b"}
+            ]}"#
+            .to_string())
+        });
+
+        // Only the manifest is fetched, never get_bytes or download
+        mock_http
+            .expect_get_bytes()
+            .times(1)
+            .withf(|url: &str| url.ends_with("manifest.json"))
+            .returning(|_| {
+                Ok(r#"{"version":"2.0.0","files":[
+                    {"path":"a.md","checksum":"a"},
+                    {"path":"b.md","checksum":"b2"},
+                    {"path":"c.md","checksum":"c"}
+                ]}"#
+                .into())
+            });
+
+        mock_logger
+            .expect_info()
+            .withf(|msg: &str| !msg.contains("a.md ->"))
+            .return_const(());
+
+        let report = update_dry_run(
+            &mock_fs,
+            &mock_http,
+            &mock_logger,
+            &ContentSource::default(),
+            temp_dir.path(),
+            false,
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(report.downloaded.is_empty());
+        assert_eq!(report.skipped.len(), 3);
+        assert_eq!(report.manifest_version, "2.0.0");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
     #[tokio::test]
     async fn test_check_not_installed() {
         let temp_dir = TempDir::new().unwrap();
@@ -297,8 +1146,211 @@ mod tests {
             .withf(|msg: &str| msg.contains("not found"))
             .return_const(());
 
-        let result = check(&mock_fs, &mock_http, &mock_logger, temp_dir.path()).await;
+        let source = ContentSource::default();
+        let result = check(&mock_fs, &mock_http, &mock_logger, &source, temp_dir.path()).await;
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_repair_not_installed() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mock_fs = MockFileSystem::new();
+        let mock_http = MockHttpClient::new();
+        let mock_checksum = MockChecksum::new();
+        let mut mock_logger = MockLogger::new();
+
+        mock_fs.expect_exists().times(1).returning(|_| false);
+        mock_logger
+            .expect_warn()
+            .times(1)
+            .withf(|msg: &str| msg.contains("not found"))
+            .return_const(());
+
+        let result = repair(
+            &mock_fs,
+            &mock_http,
+            &mock_checksum,
+            &mock_logger,
+            &ContentSource::default(),
+            temp_dir.path(),
+            1,
+            false,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_repair_redownloads_failed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_http = MockHttpClient::new();
+        let mut mock_checksum = MockChecksum::new();
+        let mut mock_logger = MockLogger::new();
+
+        let manifest_json = r#"{"version":"1.0.0","files":[
+            {"path":"good.txt","checksum":"good"},
+            {"path":"bad.txt","checksum":"fixed"}
+        ]}"#;
+
+        // Tracks whether bad.txt has been rewritten
+        let repaired = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        mock_fs.expect_exists().returning(|_| true);
+        let repaired_read = repaired.clone();
+        mock_fs.expect_read().returning(move |path| {
+            if path.ends_with("manifest.json") {
+                Ok(manifest_json.to_string())
+            } else if path.ends_with("good.txt") {
+                Ok("good".to_string())
+            } else if repaired_read.load(std::sync::atomic::Ordering::SeqCst) {
+                Ok("fixed".to_string())
+            } else {
+                Ok("broken".to_string())
+            }
+        });
+        mock_fs.expect_create_dir_all().returning(|_| Ok(()));
+        let repaired_rename = repaired.clone();
+        mock_fs
+            .expect_rename()
+            .times(1)
+            .withf(|_: &Path, to: &Path| to.ends_with("bad.txt"))
+            .returning(move |_, _| {
+                repaired_rename.store(true, std::sync::atomic::Ordering::SeqCst);
+                Ok(())
+            });
+
+        // Only bad.txt is downloaded
+        mock_http
+            .expect_download_with()
+            .times(1)
+            .withf(|url: &str, _, _| url.ends_with("/bad.txt"))
+            .returning(|_, _, _| Ok(5));
+
+        // Content doubles as its checksum to keep the mock simple
+        mock_checksum
+            .expect_sha256()
+            .returning(|content| String::from_utf8_lossy(content).to_string());
+        mock_checksum.expect_sha256_file().returning(|_| Ok("fixed".to_string()));
+
+        mock_logger.expect_info().return_const(());
+        mock_logger.expect_success().return_const(());
+
+        let result = repair(
+            &mock_fs,
+            &mock_http,
+            &mock_checksum,
+            &mock_logger,
+            &ContentSource::default(),
+            temp_dir.path(),
+            1,
+            false,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_changelog_uses_local_manifest_as_baseline() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_http = MockHttpClient::new();
+        let mut mock_logger = MockLogger::new();
+
+        mock_fs.expect_exists().returning(|_| true);
+        mock_fs.expect_read().returning(|_| {
+            Ok(r#"{"version":"abc123","files":[
+                {"path":"a.md","checksum":"1"},
+                {"path":"b.md","checksum":"2"}
+            ]}"#
+            .to_string())
+        });
+
+        // Only the current manifest is fetched, from the configured source
+        let source =
+            ContentSource::new(Some("https://mirror.example/manifest.json"), None).unwrap();
+        mock_http
+            .expect_get_bytes()
+            .times(1)
+            .withf(|url: &str| url == "https://mirror.example/manifest.json")
+            .returning(|_| {
+                Ok(r#"{"version":"def456","files":[
+                    {"path":"a.md","checksum":"1"},
+                    {"path":"b.md","checksum":"3"},
+                    {"path":"c.md","checksum":"4"}
+                ]}"#
+                .into())
+            });
+
+        mock_logger.expect_info().return_const(());
+
+        let diff =
+            changelog(&mock_fs, &mock_http, &mock_logger, &source, temp_dir.path(), "abc123")
+                .await
+                .unwrap();
+
+        assert_eq!(diff.new_files.len(), 1);
+        assert_eq!(diff.new_files[0].path, Path::new("c.md"));
+        assert_eq!(diff.modified_files.len(), 1);
+        assert_eq!(diff.modified_files[0].path, Path::new("b.md"));
+    }
+
+    #[tokio::test]
+    async fn test_changelog_fetches_old_version_by_ref() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_http = MockHttpClient::new();
+        let mut mock_logger = MockLogger::new();
+
+        mock_fs.expect_exists().returning(|_| false);
+
+        mock_http
+            .expect_get_bytes()
+            .times(1)
+            .withf(|url: &str| url == github::manifest_url_at("abc123"))
+            .returning(|_| Ok(r#"{"version":"abc123","files":[]}"#.into()));
+        mock_http
+            .expect_get_bytes()
+            .times(1)
+            .withf(|url: &str| url == github::manifest_url())
+            .returning(|_| {
+                Ok(r#"{"version":"def456","files":[{"path":"a.md","checksum":"1"}]}"#.into())
+            });
+
+        mock_logger.expect_info().return_const(());
+
+        let source = ContentSource::default();
+        let diff =
+            changelog(&mock_fs, &mock_http, &mock_logger, &source, temp_dir.path(), "abc123")
+                .await
+                .unwrap();
+
+        assert_eq!(diff.new_files.len(), 1);
+        assert!(diff.modified_files.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_changelog_unknown_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mock_fs = MockFileSystem::new();
+        let mut mock_http = MockHttpClient::new();
+        let mut mock_logger = MockLogger::new();
+
+        mock_fs.expect_exists().returning(|_| false);
+        mock_http
+            .expect_get_bytes()
+            .times(1)
+            .returning(|url| Err(Error::Network(format!("HTTP 404 Not Found for {}", url))));
+        mock_logger.expect_info().return_const(());
+
+        let source = ContentSource::default();
+        let result =
+            changelog(&mock_fs, &mock_http, &mock_logger, &source, temp_dir.path(), "missing")
+                .await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
 }
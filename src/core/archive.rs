@@ -0,0 +1,122 @@
+//! tar.gz helpers shared by self-update and skill sharing.
+
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tar::{Archive, Builder, EntryType, Header};
+
+use super::types::{Error, Result};
+
+/// Reject archive entries that could escape the extraction directory.
+pub fn validate_entry_path(path: &Path) -> Result<()> {
+    let escapes = path.components().any(|c| {
+        matches!(
+            c,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_)
+        )
+    });
+
+    if escapes {
+        return Err(Error::Parse(format!(
+            "Archive entry escapes extraction directory: {}",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Build a tar.gz archive in memory from `(path, contents)` pairs.
+///
+/// Entries get fixed permissions and timestamps, so packing the same files
+/// twice produces the same archive.
+pub fn pack_tar_gz(files: &[(PathBuf, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut builder = Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+
+    for (path, contents) in files {
+        validate_entry_path(path)?;
+        let mut header = Header::new_gnu();
+        header.set_entry_type(EntryType::Regular);
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        builder.append_data(&mut header, path, contents.as_slice())?;
+    }
+
+    let mut encoder = builder.into_inner()?;
+    encoder.flush()?;
+    Ok(encoder.finish()?)
+}
+
+/// Read every regular file from a tar.gz archive held in memory.
+///
+/// Directory entries are skipped; links and other special entries are
+/// rejected, as is any archive whose files add up to more than `max_size` bytes.
+pub fn unpack_tar_gz(bytes: &[u8], max_size: u64) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let mut archive = Archive::new(GzDecoder::new(bytes));
+    let mut files = Vec::new();
+    let mut total: u64 = 0;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        validate_entry_path(&path)?;
+
+        match entry.header().entry_type() {
+            EntryType::Directory => continue,
+            EntryType::Regular => {}
+            other => {
+                return Err(Error::Parse(format!(
+                    "Unsupported archive entry type {:?}: {}",
+                    other,
+                    path.display()
+                )));
+            }
+        }
+
+        let mut contents = Vec::new();
+        entry.by_ref().take(max_size - total + 1).read_to_end(&mut contents)?;
+        total += contents.len() as u64;
+        if total > max_size {
+            return Err(Error::Parse(format!(
+                "Archive contents exceed the limit of {} bytes",
+                max_size
+            )));
+        }
+
+        files.push((path, contents));
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_entry_path() {
+        assert!(validate_entry_path(Path::new("aiassisted")).is_ok());
+        assert!(validate_entry_path(Path::new("dist/aiassisted")).is_ok());
+        assert!(validate_entry_path(Path::new("../aiassisted")).is_err());
+        assert!(validate_entry_path(Path::new("a/../../aiassisted")).is_err());
+        assert!(validate_entry_path(Path::new("/etc/aiassisted")).is_err());
+    }
+
+    #[test]
+    fn test_pack_unpack_roundtrip() {
+        let files = vec![
+            (PathBuf::from("skill/SKILL.md"), b"# Skill".to_vec()),
+            (PathBuf::from("skill/assets/logo.bin"), vec![0, 159, 255]),
+        ];
+        let archive = pack_tar_gz(&files).unwrap();
+        assert_eq!(pack_tar_gz(&files).unwrap(), archive);
+        assert_eq!(unpack_tar_gz(&archive, 1024).unwrap(), files);
+
+        let result = unpack_tar_gz(&archive, 8);
+        assert!(matches!(result, Err(Error::Parse(msg)) if msg.contains("limit of 8 bytes")));
+    }
+}
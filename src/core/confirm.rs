@@ -0,0 +1,75 @@
+//! Confirmation prompts for destructive commands.
+
+use std::io::{IsTerminal, Write};
+
+use super::types::{Error, Result};
+
+/// Ask the user to confirm a destructive operation.
+///
+/// Returns `true` straight away when `assume_yes` is set (`--force`/`--yes`).
+/// Without a terminal on both stdin and stdout the prompt would block CI, so
+/// this fails instead of asking.
+pub fn confirm(question: &str, assume_yes: bool) -> Result<bool> {
+    let interactive = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    confirm_with(question, assume_yes, interactive, read_answer)
+}
+
+/// [`confirm`] with the terminal check and input source supplied by the caller.
+fn confirm_with<A>(question: &str, assume_yes: bool, interactive: bool, ask: A) -> Result<bool>
+where
+    A: FnOnce(&str) -> Result<String>,
+{
+    if assume_yes {
+        return Ok(true);
+    }
+    if !interactive {
+        return Err(Error::Config(
+            "refusing to proceed non-interactively without --yes".to_string(),
+        ));
+    }
+
+    let answer = ask(question)?.trim().to_lowercase();
+    Ok(answer == "y" || answer == "yes")
+}
+
+/// Print `question` with a `[y/N]` suffix and read one line from stdin.
+fn read_answer(question: &str) -> Result<String> {
+    print!("{} [y/N] ", question);
+    std::io::stdout()
+        .flush()
+        .map_err(|e| Error::Config(format!("Failed to flush stdout: {}", e)))?;
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| Error::Config(format!("Failed to read input: {}", e)))?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn never_asked(_: &str) -> Result<String> {
+        panic!("prompt should not be shown");
+    }
+
+    #[test]
+    fn test_non_interactive_refuses_without_yes() {
+        let result = confirm_with("Continue?", false, false, never_asked);
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("without --yes")));
+    }
+
+    #[test]
+    fn test_assume_yes_skips_prompt() {
+        assert!(confirm_with("Continue?", true, false, never_asked).unwrap());
+        assert!(confirm_with("Continue?", true, true, never_asked).unwrap());
+    }
+
+    #[test]
+    fn test_interactive_answer() {
+        assert!(confirm_with("Continue?", false, true, |_| Ok("Y\n".to_string())).unwrap());
+        assert!(confirm_with("Continue?", false, true, |_| Ok("yes\n".to_string())).unwrap());
+        assert!(!confirm_with("Continue?", false, true, |_| Ok("\n".to_string())).unwrap());
+    }
+}
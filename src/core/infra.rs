@@ -3,11 +3,13 @@
 //! These traits define the interfaces for infrastructure concerns like
 //! file system operations, HTTP clients, checksums, and logging.
 
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use async_trait::async_trait;
 
-use super::types::Result;
+use super::types::{Error, Result};
 
 /// Abstraction for file system operations.
 #[async_trait]
@@ -18,6 +20,28 @@ pub trait FileSystem: Send + Sync {
     /// Write content to a file.
     async fn write(&self, path: &Path, content: &str) -> Result<()>;
 
+    /// Read the raw contents of a file.
+    ///
+    /// Use this for files that may not be UTF-8. The default implementation
+    /// goes through `read` and only handles text.
+    async fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        self.read(path).await.map(String::into_bytes)
+    }
+
+    /// Write raw bytes to a file.
+    ///
+    /// The default implementation goes through `write` and rejects content
+    /// that is not UTF-8.
+    async fn write_bytes(&self, path: &Path, content: &[u8]) -> Result<()> {
+        let text = std::str::from_utf8(content).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })?;
+        self.write(path, text).await
+    }
+
     /// Check if a path exists.
     fn exists(&self, path: &Path) -> bool;
 
@@ -27,14 +51,95 @@ pub trait FileSystem: Send + Sync {
     /// Check if a path is a file.
     fn is_file(&self, path: &Path) -> bool;
 
+    /// Check if a path is a symbolic link (without following it).
+    ///
+    /// Recursive copies skip symlinks rather than following them, so a link
+    /// to a parent directory cannot cause a cycle. The default implementation
+    /// reports no symlinks.
+    fn is_symlink(&self, _path: &Path) -> bool {
+        false
+    }
+
+    /// Last modification time of `path`, if known.
+    ///
+    /// Used as a cheap change marker. The default implementation tracks no
+    /// modification times.
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        None
+    }
+
+    /// Resolve `path` to an absolute path without `.` or `..` components.
+    ///
+    /// Fails if the path does not exist. The default implementation only
+    /// checks existence and returns the path unchanged.
+    async fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        if !self.exists(path) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{}: not found", path.display()),
+            )
+            .into());
+        }
+        Ok(path.to_path_buf())
+    }
+
     /// Create a directory and all parent directories.
     async fn create_dir_all(&self, path: &Path) -> Result<()>;
 
     /// List entries in a directory.
     async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
 
-    /// Copy a file from source to destination.
+    /// Copy a file from source to destination, keeping its modification time.
     async fn copy(&self, from: &Path, to: &Path) -> Result<()>;
+
+    /// Remove a file.
+    async fn remove_file(&self, path: &Path) -> Result<()>;
+
+    /// Remove a directory and all of its contents.
+    async fn remove_dir_all(&self, path: &Path) -> Result<()>;
+
+    /// Rename (move) a file or directory.
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()>;
+
+    /// Recursively list all files beneath `root`.
+    ///
+    /// Returns an empty list if `root` does not exist. Symlinked directories
+    /// are not descended into. The default implementation walks the tree
+    /// iteratively with `list_dir`.
+    async fn walk_dir(&self, root: &Path) -> Result<Vec<PathBuf>> {
+        if !self.exists(root) {
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        let mut pending = vec![root.to_path_buf()];
+
+        while let Some(dir) = pending.pop() {
+            for entry in self.list_dir(&dir).await? {
+                if self.is_symlink(&entry) && self.is_dir(&entry) {
+                    continue;
+                }
+                if self.is_dir(&entry) {
+                    pending.push(entry);
+                } else {
+                    files.push(entry);
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Like [`walk_dir`](Self::walk_dir), but descending into symlinked
+    /// directories and listing files reached through them.
+    ///
+    /// A link back to one of its own ancestors is skipped rather than walked
+    /// forever, as is a link whose target does not exist. The default
+    /// implementation is [`walk_dir`](Self::walk_dir), for file systems
+    /// without symlinks.
+    async fn walk_dir_following(&self, root: &Path) -> Result<Vec<PathBuf>> {
+        self.walk_dir(root).await
+    }
 }
 
 /// Abstraction for HTTP client operations.
@@ -47,7 +152,47 @@ pub trait HttpClient: Send + Sync {
     async fn get_bytes(&self, url: &str) -> Result<Vec<u8>>;
 
     /// Download a file from a URL to a destination path.
+    ///
+    /// The response body is streamed to disk rather than buffered in memory.
     async fn download(&self, url: &str, dest: &Path) -> Result<()>;
+
+    /// [`download`](Self::download) with a size cap, optionally resuming an
+    /// interrupted download. Returns the size of the downloaded file.
+    ///
+    /// The default implementation never resumes, and only checks the size
+    /// once the download is complete, removing `dest` if it is over the cap.
+    async fn download_with(&self, url: &str, dest: &Path, options: DownloadOptions) -> Result<u64> {
+        self.download(url, dest).await?;
+
+        let size = std::fs::metadata(dest)?.len();
+        if let Some(max_size) = options.max_size
+            && size > max_size
+        {
+            let _ = std::fs::remove_file(dest);
+            return Err(size_limit_error(url, size, max_size));
+        }
+
+        Ok(size)
+    }
+}
+
+/// The error for a download of `url` that grew past `max_size` bytes.
+pub fn size_limit_error(url: &str, size: u64, max_size: u64) -> Error {
+    Error::Network(format!(
+        "Download from {} is {} bytes, exceeding the limit of {} bytes",
+        url, size, max_size
+    ))
+}
+
+/// Options for [`HttpClient::download_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DownloadOptions {
+    /// Abort as soon as the body is larger than this many bytes.
+    pub max_size: Option<u64>,
+    /// Continue a `.part` file left by an interrupted download of the same
+    /// file. Only set this when the caller verifies the result against a
+    /// checksum: a stale or planted partial file is otherwise installed as-is.
+    pub resume: bool,
 }
 
 /// Abstraction for checksum operations.
@@ -57,6 +202,9 @@ pub trait Checksum: Send + Sync {
 
     /// Calculate SHA256 checksum of a file.
     fn sha256_file(&self, path: &Path) -> Result<String>;
+
+    /// Calculate SHA256 checksum of everything read from `reader`.
+    fn sha256_reader(&self, reader: &mut dyn Read) -> Result<String>;
 }
 
 /// Abstraction for logging operations.
@@ -75,4 +223,11 @@ pub trait Logger: Send + Sync {
 
     /// Log a success message.
     fn success(&self, msg: &str);
+
+    /// Called by commands between self-contained steps; an error stops the command there.
+    ///
+    /// Lets a logger end a command early without interrupting a write in progress.
+    fn checkpoint(&self) -> Result<()> {
+        Ok(())
+    }
 }
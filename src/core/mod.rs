@@ -7,12 +7,18 @@
 //! # Module Structure
 //!
 //! - [`types`] - Shared types (Error, Result, ToolType, DTOs)
+//! - [`archive`] - tar.gz packing and safe extraction helpers
 //! - [`infra`] - Infrastructure traits (FileSystem, HttpClient, Checksum, Logger)
 //! - [`config`] - Config domain traits (ConfigStore)
+//! - [`confirm`] - Confirmation prompts that refuse to block without a terminal
 //! - [`selfupdate`] - Self-update domain traits (ReleaseProvider)
+//! - [`project`] - Project path resolution and removal guards shared by all commands
 
+pub mod archive;
 pub mod config;
+pub mod confirm;
 pub mod infra;
+pub mod project;
 pub mod selfupdate;
 pub mod types;
 
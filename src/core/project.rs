@@ -0,0 +1,134 @@
+//! Project path resolution shared by all commands.
+
+use std::path::{Component, Path, PathBuf};
+
+use super::infra::FileSystem;
+use super::types::{Error, Result};
+
+/// Resolve a project path given on the command line to a canonical path.
+///
+/// Relative paths and `..` components are resolved so messages and writes
+/// refer to the real location. A missing path is created when `create` is
+/// set (for `install --path` into a new directory) and rejected otherwise.
+pub async fn resolve_project_path<F: FileSystem>(
+    fs: &F,
+    path: &Path,
+    create: bool,
+) -> Result<PathBuf> {
+    if !fs.exists(path) {
+        if !create {
+            return Err(Error::NotFound(format!(
+                "project path does not exist: {}",
+                path.display()
+            )));
+        }
+        fs.create_dir_all(path).await?;
+    }
+
+    if !fs.is_dir(path) {
+        return Err(Error::Config(format!(
+            "project path is not a directory: {}",
+            path.display()
+        )));
+    }
+
+    fs.canonicalize(path).await
+}
+
+/// Check that `path` is safe to delete recursively.
+///
+/// Every recursive removal goes through this first, so a bug that produces an
+/// empty path, a filesystem root or the home directory fails loudly instead of
+/// deleting far too much. `path` must also lie strictly inside `base` (the
+/// project or home directory it was built from) without any `..` components.
+pub fn ensure_removable(path: &Path, base: &Path) -> Result<()> {
+    let refuse = |reason: &str| {
+        Err(Error::Config(format!(
+            "refusing to remove '{}': {}",
+            path.display(),
+            reason
+        )))
+    };
+
+    if path.as_os_str().is_empty() {
+        return refuse("path is empty");
+    }
+    if path.parent().is_none() || !path.components().any(|c| matches!(c, Component::Normal(_))) {
+        return refuse("path is a filesystem root");
+    }
+    if dirs::home_dir().is_some_and(|home| path == home) {
+        return refuse("path is the home directory");
+    }
+    if path.components().any(|c| c == Component::ParentDir) {
+        return refuse("path contains '..'");
+    }
+    if base.as_os_str().is_empty() || path == base || !path.starts_with(base) {
+        return refuse(&format!("path is not inside {}", base.display()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::MemFileSystem;
+
+    #[tokio::test]
+    async fn test_resolves_existing_path() {
+        let fs = MemFileSystem::new();
+        fs.create_dir_all(Path::new("/work/project")).await.unwrap();
+
+        let resolved = resolve_project_path(&fs, Path::new("/work/./project/"), false)
+            .await
+            .unwrap();
+        assert_eq!(resolved, PathBuf::from("/work/project"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_path_is_rejected_unless_created() {
+        let fs = MemFileSystem::new();
+
+        let result = resolve_project_path(&fs, Path::new("/new/project"), false).await;
+        assert!(matches!(result, Err(Error::NotFound(msg)) if msg.contains("does not exist")));
+
+        let resolved = resolve_project_path(&fs, Path::new("/new/project"), true)
+            .await
+            .unwrap();
+        assert_eq!(resolved, PathBuf::from("/new/project"));
+        assert!(fs.is_dir(&resolved));
+    }
+
+    #[test]
+    fn test_ensure_removable_refuses_dangerous_paths() {
+        let home = dirs::home_dir().unwrap();
+        let refused = |path: &Path, base: &Path, reason: &str| {
+            let result = ensure_removable(path, base);
+            assert!(
+                matches!(&result, Err(Error::Config(msg)) if msg.contains(reason)),
+                "{}: {:?}",
+                path.display(),
+                result
+            );
+        };
+
+        refused(Path::new("/"), Path::new("/"), "filesystem root");
+        refused(Path::new(""), Path::new("/work"), "empty");
+        refused(&home, Path::new("/"), "home directory");
+        refused(Path::new("/work/../etc"), Path::new("/work"), "'..'");
+        refused(Path::new("/work"), Path::new("/work"), "not inside");
+        refused(Path::new("/etc/.aiassisted"), Path::new("/work"), "not inside");
+
+        assert!(ensure_removable(Path::new("/work/.aiassisted"), Path::new("/work")).is_ok());
+        assert!(ensure_removable(&home.join(".aiassisted/source"), &home).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_file_is_not_a_project_path() {
+        let fs = MemFileSystem::new();
+        fs.write(Path::new("/notes.txt"), "").await.unwrap();
+
+        let result = resolve_project_path(&fs, Path::new("/notes.txt"), true).await;
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("not a directory")));
+    }
+}
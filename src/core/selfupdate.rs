@@ -20,5 +20,12 @@ pub trait ReleaseProvider: Send + Sync {
     async fn is_update_available(&self, current_version: &str) -> Result<bool>;
 
     /// Download a release to the specified destination.
-    async fn download_release(&self, release: &ReleaseInfo, dest: &Path) -> Result<()>;
+    ///
+    /// Fails without writing more than `max_size` bytes if the archive is larger.
+    async fn download_release(
+        &self,
+        release: &ReleaseInfo,
+        dest: &Path,
+        max_size: u64,
+    ) -> Result<()>;
 }
@@ -1,8 +1,9 @@
 //! Core types shared across all domains.
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use thiserror::Error;
 
 /// Result type alias for the application.
@@ -34,6 +35,91 @@ pub enum Error {
 
     #[error("Serialization error: {0}")]
     Serialization(String),
+
+    #[error("Out of date: {0}")]
+    Outdated(String),
+
+    #[error("{0} warning(s) reported in strict mode")]
+    Warnings(usize),
+}
+
+impl Error {
+    /// Process exit code for this error, so scripts can tell failures apart.
+    ///
+    /// | Code | Category                 |
+    /// |------|--------------------------|
+    /// | 1    | Any other error          |
+    /// | 2    | Network                  |
+    /// | 3    | Checksum mismatch        |
+    /// | 4    | Not found                |
+    /// | 5    | Configuration            |
+    /// | 6    | I/O                      |
+    /// | 7    | Out of date              |
+    /// | 8    | Strict-mode warnings     |
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Network(_) => 2,
+            Error::ChecksumMismatch { .. } => 3,
+            Error::NotFound(_) => 4,
+            Error::Config(_) => 5,
+            Error::Io(_) => 6,
+            Error::Outdated(_) => 7,
+            Error::Warnings(_) => 8,
+            Error::Template(_) | Error::Parse(_) | Error::Serialization(_) => 1,
+        }
+    }
+
+    /// Serialization error for malformed JSON read from `source`.
+    ///
+    /// Displays as `source:line:col — message`.
+    pub fn json(source: impl std::fmt::Display, err: &serde_json::Error) -> Self {
+        // serde_json appends " at line L column C" itself; strip it so the
+        // position only appears once, in front
+        let message = err.to_string();
+        let suffix = format!(" at line {} column {}", err.line(), err.column());
+        let message = message.strip_suffix(&suffix).unwrap_or(&message);
+
+        Error::Serialization(located(source, Some((err.line(), err.column())), message))
+    }
+
+    /// Parse error for malformed TOML read from `source`.
+    ///
+    /// `contents` is the text that failed to parse, used to turn the error
+    /// span into a line and column. Displays as `source:line:col — message`.
+    pub fn toml(source: impl std::fmt::Display, contents: &str, err: &toml::de::Error) -> Self {
+        let position = err
+            .span()
+            .map(|span| line_col(contents, span.start));
+
+        Error::Parse(located(source, position, err.message()))
+    }
+}
+
+/// Format `message` prefixed by its source and (1-based) position.
+fn located(
+    source: impl std::fmt::Display,
+    position: Option<(usize, usize)>,
+    message: &str,
+) -> String {
+    match position {
+        Some((line, column)) if line > 0 => {
+            format!("{}:{}:{} — {}", source, line, column, message)
+        }
+        _ => format!("{} — {}", source, message),
+    }
+}
+
+/// 1-based line and column of byte `offset` in `contents`.
+fn line_col(contents: &str, offset: usize) -> (usize, usize) {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|l| l.chars().count())
+        .unwrap_or(0)
+        + 1;
+    (line, column)
 }
 
 /// Supported AI tools for template generation.
@@ -49,6 +135,25 @@ pub enum ToolType {
     Claude,
 }
 
+impl ToolType {
+    /// All concrete tools (everything except `Auto`)
+    pub const ALL: [ToolType; 2] = [ToolType::Claude, ToolType::OpenCode];
+
+    /// Where the tool looks for project skills, relative to the project root
+    ///
+    /// Each skill is a `<name>/SKILL.md` directory inside it. No catch-all
+    /// arm: a new tool must declare its directory here. `Auto` only gets here
+    /// when nothing was detected and no default is set, and falls back to Claude.
+    pub fn skills_dir(&self) -> &'static str {
+        match self {
+            // Claude Code loads project skills from .claude/skills/<name>/SKILL.md
+            ToolType::Claude | ToolType::Auto => ".claude/skills",
+            // OpenCode loads project skills from .opencode/skills/<name>/SKILL.md
+            ToolType::OpenCode => ".opencode/skills",
+        }
+    }
+}
+
 impl std::fmt::Display for ToolType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -73,14 +178,86 @@ impl std::str::FromStr for ToolType {
 }
 
 /// A manifest entry representing a file with its checksum.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ManifestEntry {
-    /// Relative path to the file.
+    /// Relative path to the file, serialized with `/` separators on every platform.
+    #[serde(serialize_with = "serialize_slash_path")]
     pub path: PathBuf,
     /// SHA256 checksum of the file content.
     pub checksum: String,
 }
 
+/// Serialize a relative path with forward slashes regardless of platform.
+fn serialize_slash_path<S: Serializer>(
+    path: &Path,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    let parts: Vec<_> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    serializer.serialize_str(&parts.join("/"))
+}
+
+/// Output format for commands that can emit machine-readable results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable log lines.
+    #[default]
+    Text,
+    /// A single JSON document on stdout.
+    Json,
+    /// A single YAML document on stdout.
+    Yaml,
+}
+
+/// Version of the JSON/YAML output shape, emitted as `schema_version`.
+///
+/// Bump this whenever a field is renamed, removed or changes meaning; adding
+/// a field does not need a bump.
+pub const OUTPUT_SCHEMA_VERSION: &str = "1";
+
+/// A command summary with the schema version in front of its own fields.
+#[derive(Serialize)]
+struct Versioned<'a, T> {
+    schema_version: &'static str,
+    #[serde(flatten)]
+    value: &'a T,
+}
+
+impl OutputFormat {
+    /// Serialize a command's summary in this format, or `None` for `Text`.
+    ///
+    /// The summary must serialize as a map; it is tagged with
+    /// [`OUTPUT_SCHEMA_VERSION`].
+    pub fn render<T: Serialize>(self, value: &T) -> Result<Option<String>> {
+        let value = Versioned {
+            schema_version: OUTPUT_SCHEMA_VERSION,
+            value,
+        };
+        match self {
+            OutputFormat::Text => Ok(None),
+            OutputFormat::Json => serde_json::to_string_pretty(&value)
+                .map(Some)
+                .map_err(|e| Error::json("output", &e)),
+            OutputFormat::Yaml => serde_yaml::to_string(&value)
+                .map(Some)
+                .map_err(|e| Error::Serialization(format!("Failed to serialize YAML: {}", e))),
+        }
+    }
+}
+
+/// Custom output directories for one tool, relative to the project root.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolDirs {
+    /// Directory to install skills into.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills: Option<PathBuf>,
+    /// Directory to install compiled agents into.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agents: Option<PathBuf>,
+}
+
 /// Application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -96,6 +273,13 @@ pub struct AppConfig {
     /// Whether to prefer project templates over global.
     #[serde(default = "default_true")]
     pub prefer_project: bool,
+    /// Per-tool output directory overrides, keyed by tool name
+    /// (`claude` or `opencode`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub output_dirs: BTreeMap<String, ToolDirs>,
+    /// Directory for self-update downloads, instead of the system temp directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_dir: Option<PathBuf>,
 }
 
 fn default_verbosity() -> u8 {
@@ -113,6 +297,8 @@ impl Default for AppConfig {
             verbosity: 1,
             auto_update: true,
             prefer_project: true,
+            output_dirs: BTreeMap::new(),
+            temp_dir: None,
         }
     }
 }
@@ -126,5 +312,73 @@ pub struct ReleaseInfo {
     pub download_url: String,
     /// Optional checksum for verification.
     pub checksum: Option<String>,
+    /// Release notes (markdown), if the release has any.
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Web page of the release, linked when the notes are shown truncated.
+    #[serde(default)]
+    pub notes_url: Option<String>,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_error_includes_source_and_position() {
+        let err = serde_json::from_str::<serde_json::Value>("{\n  \"a\": 1,\n  oops\n}")
+            .unwrap_err();
+        let error = Error::json("manifest.json", &err);
+
+        assert!(matches!(error, Error::Serialization(_)));
+        assert_eq!(
+            error.to_string(),
+            "Serialization error: manifest.json:3:3 — key must be a string"
+        );
+    }
+
+    #[test]
+    fn test_toml_error_includes_source_and_position() {
+        let contents = "verbosity = 1\ndefault_tool = \n";
+        let err = toml::from_str::<toml::Table>(contents).unwrap_err();
+        let error = Error::toml("config.toml", contents, &err);
+
+        assert!(matches!(error, Error::Parse(_)));
+        assert!(error.to_string().starts_with("Parse error: config.toml:2:16 — "));
+    }
+
+    #[test]
+    fn test_line_col() {
+        assert_eq!(line_col("abc", 0), (1, 1));
+        assert_eq!(line_col("abc\ndef", 5), (2, 2));
+        assert_eq!(line_col("abc", 99), (1, 4));
+    }
+
+    #[test]
+    fn test_exit_codes_by_category() {
+        assert_eq!(Error::Network("down".into()).exit_code(), 2);
+        assert_eq!(
+            Error::ChecksumMismatch {
+                expected: "a".into(),
+                actual: "b".into()
+            }
+            .exit_code(),
+            3
+        );
+        assert_eq!(Error::NotFound("x".into()).exit_code(), 4);
+        assert_eq!(Error::Config("bad".into()).exit_code(), 5);
+        assert_eq!(
+            Error::Io(std::io::Error::other("disk")).exit_code(),
+            6
+        );
+        assert_eq!(Error::Outdated("1 -> 2".into()).exit_code(), 7);
+        assert_eq!(Error::Warnings(2).exit_code(), 8);
+    }
+
+    #[test]
+    fn test_exit_code_catch_all() {
+        assert_eq!(Error::Parse("x".into()).exit_code(), 1);
+        assert_eq!(Error::Serialization("x".into()).exit_code(), 1);
+        assert_eq!(Error::Template("x".into()).exit_code(), 1);
+    }
+}
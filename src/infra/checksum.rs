@@ -30,7 +30,10 @@ impl Checksum for Sha2Checksum {
 
     fn sha256_file(&self, path: &Path) -> Result<String> {
         let file = File::open(path)?;
-        let mut reader = BufReader::new(file);
+        self.sha256_reader(&mut BufReader::new(file))
+    }
+
+    fn sha256_reader(&self, reader: &mut dyn Read) -> Result<String> {
         let mut hasher = Sha256::new();
         let mut buffer = [0u8; 8192];
 
@@ -154,4 +157,15 @@ mod tests {
         assert_eq!(hash1, hash2);
         assert_eq!(hash2, hash3);
     }
+
+    #[test]
+    fn test_sha256_reader_matches_sha256() {
+        let checksum = Sha2Checksum::new();
+        let data = vec![b'a'; 20_000];
+
+        let mut reader = std::io::Cursor::new(data.clone());
+        let streamed = checksum.sha256_reader(&mut reader).unwrap();
+
+        assert_eq!(streamed, checksum.sha256(&data));
+    }
 }
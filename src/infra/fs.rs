@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use async_trait::async_trait;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
+use walkdir::WalkDir;
 
 use crate::core::infra::FileSystem;
 use crate::core::types::Result;
@@ -36,6 +37,20 @@ impl FileSystem for StdFileSystem {
         Ok(())
     }
 
+    async fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        Ok(fs::read(path).await?)
+    }
+
+    async fn write_bytes(&self, path: &Path, content: &[u8]) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let mut file = fs::File::create(path).await?;
+        file.write_all(content).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
     fn exists(&self, path: &Path) -> bool {
         path.exists()
     }
@@ -48,6 +63,18 @@ impl FileSystem for StdFileSystem {
         path.is_file()
     }
 
+    fn is_symlink(&self, path: &Path) -> bool {
+        path.is_symlink()
+    }
+
+    fn modified(&self, path: &Path) -> Option<std::time::SystemTime> {
+        std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+    }
+
+    async fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        Ok(fs::canonicalize(path).await?)
+    }
+
     async fn create_dir_all(&self, path: &Path) -> Result<()> {
         Ok(fs::create_dir_all(path).await?)
     }
@@ -66,8 +93,87 @@ impl FileSystem for StdFileSystem {
             fs::create_dir_all(parent).await?;
         }
         fs::copy(from, to).await?;
+
+        // Carry the modification time over so the copy does not look newer
+        let modified = fs::metadata(from).await?.modified()?;
+        let dest = fs::OpenOptions::new().write(true).open(to).await?;
+        dest.into_std().await.set_modified(modified)?;
         Ok(())
     }
+
+    async fn remove_file(&self, path: &Path) -> Result<()> {
+        Ok(fs::remove_file(path).await?)
+    }
+
+    async fn remove_dir_all(&self, path: &Path) -> Result<()> {
+        Ok(fs::remove_dir_all(path).await?)
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::rename(from, to).await?;
+        Ok(())
+    }
+
+    async fn walk_dir(&self, root: &Path) -> Result<Vec<PathBuf>> {
+        if !root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let root = root.to_path_buf();
+        let files = tokio::task::spawn_blocking(move || {
+            let mut files = Vec::new();
+            for entry in WalkDir::new(&root).sort_by_file_name() {
+                let entry = entry.map_err(std::io::Error::from)?;
+                // Symlinked directories are listed by WalkDir but not entered; drop them
+                if entry.file_type().is_dir() || (entry.path_is_symlink() && entry.path().is_dir()) {
+                    continue;
+                }
+                files.push(entry.into_path());
+            }
+            Ok::<_, std::io::Error>(files)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        Ok(files)
+    }
+
+    async fn walk_dir_following(&self, root: &Path) -> Result<Vec<PathBuf>> {
+        if !root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let root = root.to_path_buf();
+        let files = tokio::task::spawn_blocking(move || {
+            let mut files = Vec::new();
+            for entry in WalkDir::new(&root).follow_links(true).sort_by_file_name() {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    // A cycle or a dangling link: skip it, keep walking
+                    Err(e)
+                        if e.loop_ancestor().is_some()
+                            || e.io_error()
+                                .is_some_and(|io| io.kind() == std::io::ErrorKind::NotFound) =>
+                    {
+                        continue;
+                    }
+                    Err(e) => return Err(std::io::Error::from(e)),
+                };
+                if entry.file_type().is_dir() {
+                    continue;
+                }
+                files.push(entry.into_path());
+            }
+            Ok::<_, std::io::Error>(files)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        Ok(files)
+    }
 }
 
 #[cfg(test)]
@@ -179,6 +285,20 @@ mod tests {
         assert!(entries.iter().any(|p| p.ends_with("subdir")));
     }
 
+    #[tokio::test]
+    async fn test_read_write_bytes_roundtrip() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("nested/image.png");
+        // PNG signature followed by bytes that are not valid UTF-8
+        let content = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0x00, 0xfe];
+
+        fs.write_bytes(&file_path, &content).await.unwrap();
+
+        assert_eq!(fs.read_bytes(&file_path).await.unwrap(), content);
+        assert!(fs.read(&file_path).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_list_empty_dir() {
         let fs = StdFileSystem::new();
@@ -228,6 +348,29 @@ mod tests {
         assert_eq!(content, "copy me");
     }
 
+    #[tokio::test]
+    async fn test_copy_preserves_mtime() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.txt");
+        let dest = temp_dir.path().join("dest.txt");
+
+        fs.write(&source, "copy me").await.unwrap();
+        let old = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        std::fs::File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        fs.copy(&source, &dest).await.unwrap();
+
+        let copied = std::fs::metadata(&dest).unwrap().modified().unwrap();
+        let drift = copied.duration_since(old).unwrap_or_else(|e| e.duration());
+        assert!(drift < std::time::Duration::from_secs(2), "mtime off by {:?}", drift);
+    }
+
     #[tokio::test]
     async fn test_copy_nonexistent_source() {
         let fs = StdFileSystem::new();
@@ -239,6 +382,138 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_remove_file() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("remove_me.txt");
+
+        fs.write(&file_path, "content").await.unwrap();
+        fs.remove_file(&file_path).await.unwrap();
+
+        assert!(!fs.exists(&file_path));
+    }
+
+    #[tokio::test]
+    async fn test_remove_nonexistent_file() {
+        let fs = StdFileSystem::new();
+        let result = fs.remove_file(Path::new("/nonexistent/file.txt")).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remove_dir_all() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let dir_path = temp_dir.path().join("subdir");
+
+        fs.write(&dir_path.join("a/b/file.txt"), "content")
+            .await
+            .unwrap();
+        fs.remove_dir_all(&dir_path).await.unwrap();
+
+        assert!(!fs.exists(&dir_path));
+        assert!(fs.exists(temp_dir.path()));
+    }
+
+    #[tokio::test]
+    async fn test_remove_nonexistent_dir() {
+        let fs = StdFileSystem::new();
+        let result = fs.remove_dir_all(Path::new("/nonexistent/dir")).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rename_file() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("old.txt");
+        let dest = temp_dir.path().join("a/b/new.txt");
+
+        fs.write(&source, "move me").await.unwrap();
+        fs.rename(&source, &dest).await.unwrap();
+
+        assert!(!fs.exists(&source));
+        let content = fs.read(&dest).await.unwrap();
+        assert_eq!(content, "move me");
+    }
+
+    #[tokio::test]
+    async fn test_rename_dir() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("old_dir");
+        let dest = temp_dir.path().join("new_dir");
+
+        fs.write(&source.join("file.txt"), "content").await.unwrap();
+        fs.rename(&source, &dest).await.unwrap();
+
+        assert!(!fs.exists(&source));
+        assert!(fs.is_file(&dest.join("file.txt")));
+    }
+
+    #[tokio::test]
+    async fn test_rename_nonexistent_source() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let result = fs
+            .rename(Path::new("/nonexistent/source.txt"), &temp_dir.path().join("dest.txt"))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_walk_dir_nested_tree() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs.write(&root.join("top.md"), "top").await.unwrap();
+        fs.write(&root.join("a/one.md"), "one").await.unwrap();
+        fs.write(&root.join("a/b/c/deep.md"), "deep").await.unwrap();
+        fs.create_dir_all(&root.join("empty")).await.unwrap();
+
+        let mut files = fs.walk_dir(root).await.unwrap();
+        files.sort();
+
+        assert_eq!(
+            files,
+            vec![
+                root.join("a/b/c/deep.md"),
+                root.join("a/one.md"),
+                root.join("top.md"),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_walk_dir_following_skips_loops_and_dangling_links() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("root");
+        let shared = temp_dir.path().join("shared");
+
+        fs.write(&root.join("top.md"), "top").await.unwrap();
+        fs.write(&shared.join("ref.md"), "ref").await.unwrap();
+        std::os::unix::fs::symlink(&shared, root.join("linked")).unwrap();
+        std::os::unix::fs::symlink(&root, root.join("loop")).unwrap();
+        std::os::unix::fs::symlink(root.join("gone.md"), root.join("dangling.md")).unwrap();
+
+        assert_eq!(fs.walk_dir(&root).await.unwrap().len(), 2);
+
+        let mut files = fs.walk_dir_following(&root).await.unwrap();
+        files.sort();
+        assert_eq!(files, vec![root.join("linked/ref.md"), root.join("top.md")]);
+    }
+
+    #[tokio::test]
+    async fn test_walk_dir_nonexistent_root() {
+        let fs = StdFileSystem::new();
+        let files = fs.walk_dir(Path::new("/nonexistent/dir")).await.unwrap();
+        assert!(files.is_empty());
+    }
+
     #[tokio::test]
     async fn test_write_unicode_content() {
         let fs = StdFileSystem::new();
@@ -1,32 +1,239 @@
 //! HTTP client implementation using reqwest.
 
-use std::path::Path;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use tokio::fs::File;
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE, USER_AGENT};
+use tokio::fs::{File, OpenOptions};
 use tokio::io::AsyncWriteExt;
 
-use crate::core::infra::HttpClient;
+use crate::core::infra::{size_limit_error, DownloadOptions, HttpClient};
 use crate::core::types::{Error, Result};
 
+/// User-Agent sent with every request unless overridden, e.g. `aiassisted/0.3.0`.
+pub const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Environment variables consulted for proxy configuration, in priority order.
+const PROXY_ENV_VARS: &[&str] = &[
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+/// Query parameters whose values are replaced in logged URLs.
+const SENSITIVE_QUERY_PARAMS: &[&str] = &[
+    "token",
+    "access_token",
+    "api_key",
+    "key",
+    "signature",
+    "sig",
+    "password",
+];
+
+/// Callback receiving one line per HTTP request.
+#[derive(Clone)]
+struct RequestLog(Arc<dyn Fn(&str) + Send + Sync>);
+
+impl fmt::Debug for RequestLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestLog")
+    }
+}
+
 /// HTTP client implementation using reqwest.
 #[derive(Debug, Clone)]
 pub struct ReqwestClient {
     client: reqwest::Client,
+    /// Proxy in use, if any (for error reporting).
+    proxy: Option<String>,
+    /// Refuse all network access (`--offline`).
+    offline: bool,
+    /// Download rate limit shared by every request from this client (and its
+    /// clones), if throttled.
+    throttle: Option<Arc<Throttle>>,
+    /// Where to report requests, if anywhere.
+    request_log: Option<RequestLog>,
+    /// User-Agent replacing [`DEFAULT_USER_AGENT`], if set.
+    user_agent: Option<String>,
 }
 
 impl ReqwestClient {
     /// Create a new ReqwestClient instance.
+    ///
+    /// Proxy settings are taken from the standard `HTTP_PROXY`, `HTTPS_PROXY`,
+    /// `ALL_PROXY` and `NO_PROXY` environment variables.
     pub fn new() -> Self {
         Self {
-            client: reqwest::Client::builder()
-                .user_agent(concat!(
-                    env!("CARGO_PKG_NAME"),
-                    "/",
-                    env!("CARGO_PKG_VERSION")
-                ))
+            client: Self::builder()
                 .build()
                 .expect("Failed to create HTTP client"),
+            proxy: proxy_from_env(),
+            offline: false,
+            throttle: None,
+            request_log: None,
+            user_agent: None,
+        }
+    }
+
+    /// Create a ReqwestClient that refuses every request.
+    ///
+    /// Used for `--offline`: commands that need remote data fail with a
+    /// network error instead of touching the network.
+    pub fn offline() -> Self {
+        Self {
+            offline: true,
+            ..Self::new()
+        }
+    }
+
+    /// Create a new ReqwestClient that routes all requests through `proxy_url`.
+    ///
+    /// Hosts listed in `NO_PROXY` still bypass the proxy.
+    pub fn with_proxy(proxy_url: &str) -> Result<Self> {
+        let proxy = reqwest::Proxy::all(proxy_url)
+            .map_err(|e| Error::Config(format!("Invalid proxy URL '{}': {}", proxy_url, e)))?
+            .no_proxy(reqwest::NoProxy::from_env());
+
+        let client = Self::builder()
+            .proxy(proxy)
+            .build()
+            .map_err(|e| Error::Config(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            proxy: Some(proxy_url.to_string()),
+            offline: false,
+            throttle: None,
+            request_log: None,
+            user_agent: None,
+        })
+    }
+
+    /// Cap response bodies to `bytes_per_second` while streaming them.
+    ///
+    /// The cap applies to all requests together, so concurrent downloads
+    /// share it. `None` (the default) disables throttling.
+    pub fn with_rate_limit(mut self, bytes_per_second: Option<u64>) -> Self {
+        self.throttle = bytes_per_second
+            .filter(|rate| *rate > 0)
+            .map(|rate| Arc::new(Throttle::new(rate)));
+        self
+    }
+
+    /// Report every request to `log` as `METHOD url -> status (N bytes)`.
+    ///
+    /// Credentials in the URL and sensitive query parameters are redacted
+    /// before the line is built. The CLI wires this to the debug log.
+    pub fn with_request_log(mut self, log: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.request_log = Some(RequestLog(Arc::new(log)));
+        self
+    }
+
+    /// Send `user_agent` instead of [`DEFAULT_USER_AGENT`] on every request.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Start a GET request, applying the User-Agent override if any.
+    fn get_request(&self, url: &str) -> reqwest::RequestBuilder {
+        let request = self.client.get(url);
+        match &self.user_agent {
+            Some(user_agent) => request.header(USER_AGENT, user_agent),
+            None => request,
+        }
+    }
+
+    /// Log the outcome of a request, if a request log is configured.
+    fn log_request(&self, url: &str, outcome: &str) {
+        if let Some(RequestLog(log)) = &self.request_log {
+            log(&format!("GET {} -> {}", redact_url(url), outcome));
+        }
+    }
+
+    /// Send a GET request, logging failures and error statuses.
+    ///
+    /// With `ranged`, the request carries a `Range` header and a 416 response is
+    /// handed back to the caller instead of treated as an error.
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        url: &str,
+        ranged: bool,
+    ) -> Result<reqwest::Response> {
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                self.log_request(url, "failed");
+                return Err(self.network_error(e));
+            }
+        };
+
+        let range_refused = ranged && response.status() == StatusCode::RANGE_NOT_SATISFIABLE;
+        if !response.status().is_success() && !range_refused {
+            self.log_request(url, &response.status().to_string());
+            return Err(Error::Network(format!(
+                "HTTP {} for {}",
+                response.status(),
+                url
+            )));
+        }
+
+        Ok(response)
+    }
+
+    /// Base client builder shared by all constructors.
+    fn builder() -> reqwest::ClientBuilder {
+        reqwest::Client::builder().user_agent(DEFAULT_USER_AGENT)
+    }
+
+    /// Read a response body chunk by chunk, paced by `throttle`.
+    async fn read_throttled(
+        &self,
+        mut response: reqwest::Response,
+        throttle: &Throttle,
+    ) -> Result<Vec<u8>> {
+        let mut body = Vec::new();
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| self.network_error(e))?
+        {
+            body.extend_from_slice(&chunk);
+            throttle.consumed(chunk.len()).await;
+        }
+
+        Ok(body)
+    }
+
+    /// Fail fast when network access has been disabled.
+    fn ensure_online(&self, url: &str) -> Result<()> {
+        if self.offline {
+            return Err(Error::Network(format!(
+                "offline mode: refusing to fetch {} (rerun without --offline)",
+                url
+            )));
+        }
+        Ok(())
+    }
+
+    /// Convert a reqwest error, calling out an unreachable proxy explicitly.
+    fn network_error(&self, e: reqwest::Error) -> Error {
+        match &self.proxy {
+            Some(proxy) if e.is_connect() => Error::Network(format!(
+                "Failed to connect through proxy {}: {}",
+                proxy, e
+            )),
+            _ => Error::Network(e.to_string()),
         }
     }
 }
@@ -37,63 +244,729 @@ impl Default for ReqwestClient {
     }
 }
 
+/// Paces streamed reads to a target rate in bytes per second.
+///
+/// Each chunk books the next slot of transfer time, so chunks from concurrent
+/// requests queue behind each other instead of each getting the full rate.
+#[derive(Debug)]
+struct Throttle {
+    rate: u64,
+    /// When the bytes booked so far have been transferred at `rate`.
+    next_free: Mutex<Instant>,
+}
+
+impl Throttle {
+    fn new(rate: u64) -> Self {
+        Self {
+            rate,
+            next_free: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Record `len` bytes read, sleeping if we are ahead of the target rate.
+    async fn consumed(&self, len: usize) {
+        let deadline = {
+            let mut next_free = self.next_free.lock().unwrap_or_else(|e| e.into_inner());
+            // Idle time is not banked, so a pause does not allow a burst after it
+            let start = (*next_free).max(Instant::now());
+            *next_free = start + Duration::from_secs_f64(len as f64 / self.rate as f64);
+            *next_free
+        };
+        tokio::time::sleep_until(deadline.into()).await;
+    }
+}
+
+/// Read the configured proxy URL from the environment, if any.
+fn proxy_from_env() -> Option<String> {
+    PROXY_ENV_VARS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+}
+
 #[async_trait]
 impl HttpClient for ReqwestClient {
     async fn get(&self, url: &str) -> Result<String> {
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| Error::Network(e.to_string()))?;
+        let body = self.get_bytes(url).await?;
+        String::from_utf8(body)
+            .map_err(|e| Error::Network(format!("Invalid UTF-8 in response from {}: {}", url, e)))
+    }
+
+    async fn get_bytes(&self, url: &str) -> Result<Vec<u8>> {
+        self.ensure_online(url)?;
+
+        let response = self.send(self.get_request(url), url, false).await?;
+        let status = response.status();
+
+        let body = match &self.throttle {
+            Some(throttle) => self.read_throttled(response, throttle).await?,
+            None => response
+                .bytes()
+                .await
+                .map(|b| b.to_vec())
+                .map_err(|e| self.network_error(e))?,
+        };
+
+        self.log_request(url, &format!("{} ({} bytes)", status, body.len()));
+        Ok(body)
+    }
+
+    async fn download(&self, url: &str, dest: &Path) -> Result<()> {
+        self.download_with(url, dest, DownloadOptions::default()).await?;
+        Ok(())
+    }
+
+    /// Stream `url` to `dest`.
+    ///
+    /// The body goes to `<dest>.part` first and is only renamed to `dest` once
+    /// its size matches what the server announced. Checksum verification is
+    /// left to callers, which know the expected hash.
+    ///
+    /// With `options.resume`, a `.part` file left over from an interrupted
+    /// download is continued with a `Range` request. The server's strong ETag
+    /// or `Last-Modified` is kept next to it and sent as `If-Range`, so a file
+    /// that changed since then is downloaded afresh instead of spliced onto
+    /// the old bytes; a partial file with no validator is discarded. A server
+    /// that ignores ranges gets a full download instead.
+    async fn download_with(&self, url: &str, dest: &Path, options: DownloadOptions) -> Result<u64> {
+        self.ensure_online(url)?;
+
+        let part_path = part_path(dest);
+        let validator_path = validator_path(dest);
+        let validator = if options.resume {
+            tokio::fs::read_to_string(&validator_path)
+                .await
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        } else {
+            None
+        };
+        let offset = match (&validator, tokio::fs::metadata(&part_path).await) {
+            (Some(_), Ok(metadata)) if metadata.is_file() => metadata.len(),
+            _ => 0,
+        };
+        if offset == 0 {
+            // Nothing we can safely resume from
+            let _ = tokio::fs::remove_file(&part_path).await;
+            let _ = tokio::fs::remove_file(&validator_path).await;
+        }
+
+        let mut request = self.get_request(url);
+        let ranged = offset > 0;
+        if let Some(validator) = validator.as_deref().filter(|_| ranged) {
+            request = request
+                .header(RANGE, format!("bytes={}-", offset))
+                .header(IF_RANGE, validator);
+        }
+        let mut response = self.send(request, url, ranged).await?;
+
+        if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            self.log_request(url, &response.status().to_string());
+            // The partial file already holds everything; start over to be safe
+            tokio::fs::remove_file(&part_path).await?;
+            return self.download_with(url, dest, options).await;
+        }
+        let status = response.status();
+
+        let resumed = offset > 0
+            && response.status() == StatusCode::PARTIAL_CONTENT
+            && content_range_start(&response) == Some(offset);
 
-        if !response.status().is_success() {
+        // A partial response we cannot splice onto the existing file
+        if response.status() == StatusCode::PARTIAL_CONTENT && !resumed {
+            if offset > 0 {
+                tokio::fs::remove_file(&part_path).await?;
+                return self.download_with(url, dest, options).await;
+            }
             return Err(Error::Network(format!(
-                "HTTP {} for {}",
-                response.status(),
+                "Unexpected partial response for {}",
                 url
             )));
         }
 
-        response
-            .text()
-            .await
-            .map_err(|e| Error::Network(e.to_string()))
-    }
+        let expected_len = if resumed {
+            content_range_total(&response)
+        } else {
+            response.content_length()
+        };
+        if let Some((len, max_size)) = expected_len.zip(options.max_size)
+            && len > max_size
+        {
+            self.log_request(url, &format!("{} (over the size limit)", status));
+            return Err(size_limit_error(url, len, max_size));
+        }
 
-    async fn get_bytes(&self, url: &str) -> Result<Vec<u8>> {
-        let response = self
-            .client
-            .get(url)
-            .send()
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // Remember what we are downloading so an interrupted transfer can resume
+        if options.resume && !resumed {
+            match response_validator(&response) {
+                Some(validator) => tokio::fs::write(&validator_path, validator).await?,
+                None => {
+                    let _ = tokio::fs::remove_file(&validator_path).await;
+                }
+            }
+        }
+
+        // Write chunks as they arrive instead of buffering the whole body
+        let mut file = if resumed {
+            OpenOptions::new().append(true).open(&part_path).await?
+        } else {
+            File::create(&part_path).await?
+        };
+        let mut written = if resumed { offset } else { 0 };
+        while let Some(chunk) = response
+            .chunk()
             .await
-            .map_err(|e| Error::Network(e.to_string()))?;
+            .map_err(|e| self.network_error(e))?
+        {
+            written += chunk.len() as u64;
+            if let Some(max_size) = options.max_size.filter(|max| written > *max) {
+                drop(file);
+                let _ = tokio::fs::remove_file(&part_path).await;
+                let _ = tokio::fs::remove_file(&validator_path).await;
+                self.log_request(url, &format!("{} (over the size limit)", status));
+                return Err(size_limit_error(url, written, max_size));
+            }
+            file.write_all(&chunk).await?;
+            if let Some(throttle) = &self.throttle {
+                throttle.consumed(chunk.len()).await;
+            }
+        }
+        file.flush().await?;
+        drop(file);
 
-        if !response.status().is_success() {
+        // Keep the partial file on a short read so the next attempt can resume
+        let actual_len = tokio::fs::metadata(&part_path).await?.len();
+        self.log_request(
+            url,
+            &format!("{} ({} bytes)", status, actual_len - if resumed { offset } else { 0 }),
+        );
+        if let Some(expected) = expected_len.filter(|expected| *expected != actual_len) {
+            if !options.resume {
+                let _ = tokio::fs::remove_file(&part_path).await;
+            }
             return Err(Error::Network(format!(
-                "HTTP {} for {}",
-                response.status(),
-                url
+                "Incomplete download from {}: got {} of {} bytes",
+                url, actual_len, expected
             )));
         }
 
+        tokio::fs::rename(&part_path, dest).await?;
+        let _ = tokio::fs::remove_file(&validator_path).await;
+        Ok(actual_len)
+    }
+}
+
+/// Hide credentials and sensitive query parameters in `url` for logging.
+fn redact_url(url: &str) -> String {
+    let Ok(mut parsed) = reqwest::Url::parse(url) else {
+        // Unparseable URLs are logged without their query string
+        return url.split('?').next().unwrap_or_default().to_string();
+    };
+
+    if !parsed.username().is_empty() || parsed.password().is_some() {
+        let _ = parsed.set_username("***");
+        let _ = parsed.set_password(None);
+    }
+
+    if parsed.query().is_some() {
+        let pairs: Vec<(String, String)> = parsed
+            .query_pairs()
+            .map(|(key, value)| {
+                let sensitive = SENSITIVE_QUERY_PARAMS
+                    .iter()
+                    .any(|param| key.eq_ignore_ascii_case(param));
+                let value = if sensitive { "***".into() } else { value.into_owned() };
+                (key.into_owned(), value)
+            })
+            .collect();
+        parsed.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    parsed.to_string()
+}
+
+/// Path of the in-progress file for a download to `dest`.
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// Parse `Content-Range: bytes <start>-<end>/<total>` into its parts.
+fn content_range(response: &reqwest::Response) -> Option<(u64, Option<u64>)> {
+    let value = response.headers().get(CONTENT_RANGE)?.to_str().ok()?;
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _end) = range.split_once('-')?;
+    Some((start.trim().parse().ok()?, total.trim().parse().ok()))
+}
+
+/// Path of the file holding the `If-Range` validator for a download to `dest`.
+fn validator_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part.validator");
+    dest.with_file_name(name)
+}
+
+/// A validator usable with `If-Range`: a strong ETag, else `Last-Modified`.
+///
+/// Weak ETags (`W/"..."`) are not allowed in `If-Range`.
+fn response_validator(response: &reqwest::Response) -> Option<String> {
+    let header = |name| {
         response
-            .bytes()
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    header(ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(LAST_MODIFIED))
+}
+
+fn content_range_start(response: &reqwest::Response) -> Option<u64> {
+    content_range(response).map(|(start, _)| start)
+}
+
+fn content_range_total(response: &reqwest::Response) -> Option<u64> {
+    content_range(response).and_then(|(_, total)| total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_with_proxy_valid_url() {
+        let client = ReqwestClient::with_proxy("http://proxy.example.com:8080").unwrap();
+        assert_eq!(
+            client.proxy.as_deref(),
+            Some("http://proxy.example.com:8080")
+        );
+    }
+
+    #[test]
+    fn test_with_proxy_invalid_url() {
+        let result = ReqwestClient::with_proxy("not a url");
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_with_proxy_routes_requests() {
+        let proxy = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/file.txt"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("via proxy"))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+
+        let client = ReqwestClient::with_proxy(&proxy.uri()).unwrap();
+        let body = client.get("http://upstream.invalid/file.txt").await.unwrap();
+
+        assert_eq!(body, "via proxy");
+    }
+
+    #[tokio::test]
+    async fn test_user_agent_default_and_override() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/default"))
+            .and(header("user-agent", DEFAULT_USER_AGENT))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/custom"))
+            .and(header("user-agent", "mirror-bot/1.0"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert!(DEFAULT_USER_AGENT.ends_with(env!("CARGO_PKG_VERSION")));
+        let client = ReqwestClient::new();
+        client.get(&format!("{}/default", server.uri())).await.unwrap();
+        let client = client.with_user_agent("mirror-bot/1.0");
+        client.get(&format!("{}/custom", server.uri())).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_proxy_error() {
+        // Port 1 is reserved and nothing should be listening on it
+        let client = ReqwestClient::with_proxy("http://127.0.0.1:1").unwrap();
+        let result = client.get("http://upstream.invalid/file.txt").await;
+
+        match result {
+            Err(Error::Network(msg)) => {
+                assert!(msg.contains("Failed to connect through proxy http://127.0.0.1:1"));
+            }
+            other => panic!("Expected Network error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offline_client_refuses_requests() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("online"))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = ReqwestClient::offline();
+        let url = format!("{}/file.txt", server.uri());
+
+        match client.get(&url).await {
+            Err(Error::Network(msg)) => assert!(msg.contains("offline mode")),
+            other => panic!("Expected Network error, got {:?}", other),
+        }
+        assert!(client.get_bytes(&url).await.is_err());
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dest = temp.path().join("file.txt");
+        assert!(client.download(&url, &dest).await.is_err());
+        assert!(!dest.exists());
+    }
+
+    #[tokio::test]
+    async fn test_range_not_satisfiable_is_an_error_without_a_range_request() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/file.txt"))
+            .respond_with(ResponseTemplate::new(416).set_body_string("not content"))
+            .mount(&server)
+            .await;
+
+        let client = ReqwestClient::new();
+        let url = format!("{}/file.txt", server.uri());
+        assert!(matches!(client.get(&url).await, Err(Error::Network(_))));
+        assert!(matches!(client.get_bytes(&url).await, Err(Error::Network(_))));
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dest = temp.path().join("file.txt");
+        assert!(client.download(&url, &dest).await.is_err());
+        assert!(!dest.exists());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_download_takes_minimum_time() {
+        let server = MockServer::start().await;
+        let body = vec![b'x'; 20_000];
+
+        Mock::given(method("GET"))
+            .and(path("/big.bin"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+            .mount(&server)
+            .await;
+
+        // 20 KB at 50 KB/s must take at least 400ms
+        let client = ReqwestClient::new().with_rate_limit(Some(50_000));
+        let temp = tempfile::TempDir::new().unwrap();
+        let dest = temp.path().join("big.bin");
+
+        let start = Instant::now();
+        client
+            .download(&format!("{}/big.bin", server.uri()), &dest)
             .await
-            .map(|b| b.to_vec())
-            .map_err(|e| Error::Network(e.to_string()))
+            .unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(400));
+        assert_eq!(std::fs::read(&dest).unwrap(), body);
     }
 
-    async fn download(&self, url: &str, dest: &Path) -> Result<()> {
-        let bytes = self.get_bytes(url).await?;
+    #[tokio::test]
+    async fn test_rate_limit_is_shared_by_concurrent_downloads() {
+        let server = MockServer::start().await;
+        let body = vec![b'x'; 10_000];
 
-        if let Some(parent) = dest.parent() {
-            tokio::fs::create_dir_all(parent).await?;
+        for name in ["/a.bin", "/b.bin"] {
+            Mock::given(method("GET"))
+                .and(path(name))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+                .mount(&server)
+                .await;
         }
 
-        let mut file = File::create(dest).await?;
-        file.write_all(&bytes).await?;
-        file.flush().await?;
-        Ok(())
+        // 2 x 10 KB at 50 KB/s overall must take at least 400ms, not 200ms
+        let client = ReqwestClient::new().with_rate_limit(Some(50_000));
+        let temp = tempfile::TempDir::new().unwrap();
+        let (a, b) = (temp.path().join("a.bin"), temp.path().join("b.bin"));
+        let (url_a, url_b) = (format!("{}/a.bin", server.uri()), format!("{}/b.bin", server.uri()));
+        let other = client.clone();
+
+        let start = Instant::now();
+        let (first, second) = tokio::join!(client.download(&url_a, &a), other.download(&url_b, &b));
+        first.unwrap();
+        second.unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(400));
+        assert_eq!(std::fs::read(&b).unwrap(), body);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_get_takes_minimum_time() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/file.txt"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("a".repeat(10_000)))
+            .mount(&server)
+            .await;
+
+        // 10 KB at 50 KB/s must take at least 200ms
+        let client = ReqwestClient::new().with_rate_limit(Some(50_000));
+
+        let start = Instant::now();
+        let text = client
+            .get(&format!("{}/file.txt", server.uri()))
+            .await
+            .unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(text.len(), 10_000);
+    }
+
+    const RESUME: DownloadOptions = DownloadOptions {
+        max_size: None,
+        resume: true,
+    };
+
+    #[tokio::test]
+    async fn test_download_resumes_partial_file() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/archive.tar.gz"))
+            .and(header("range", "bytes=6-"))
+            .and(header("if-range", "\"v1\""))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 6-10/11")
+                    .set_body_string("world"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dest = temp.path().join("archive.tar.gz");
+        std::fs::write(temp.path().join("archive.tar.gz.part"), "hello ").unwrap();
+        std::fs::write(temp.path().join("archive.tar.gz.part.validator"), "\"v1\"").unwrap();
+
+        let client = ReqwestClient::new();
+        client
+            .download_with(&format!("{}/archive.tar.gz", server.uri()), &dest, RESUME)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "hello world");
+        assert!(!temp.path().join("archive.tar.gz.part").exists());
+        assert!(!temp.path().join("archive.tar.gz.part.validator").exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_discards_unverifiable_partial_file() {
+        let server = MockServer::start().await;
+
+        // No Range header may be sent for a partial file we cannot validate
+        Mock::given(method("GET"))
+            .and(path("/archive.tar.gz"))
+            .and(header_exists("range"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/archive.tar.gz"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello world"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dest = temp.path().join("archive.tar.gz");
+        let part = temp.path().join("archive.tar.gz.part");
+        let url = format!("{}/archive.tar.gz", server.uri());
+        let client = ReqwestClient::new();
+
+        // Planted without a validator
+        std::fs::write(&part, "evil! ").unwrap();
+        client.download_with(&url, &dest, RESUME).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "hello world");
+
+        // With a validator, but the caller did not ask to resume
+        std::fs::write(&part, "evil! ").unwrap();
+        std::fs::write(temp.path().join("archive.tar.gz.part.validator"), "\"v1\"").unwrap();
+        client.download(&url, &dest).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn test_download_restarts_when_file_changed() {
+        let server = MockServer::start().await;
+
+        // The file changed, so the server ignores the range and sends it all
+        Mock::given(method("GET"))
+            .and(path("/archive.tar.gz"))
+            .and(header("if-range", "\"v1\""))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v2\"")
+                    .set_body_string("brand new"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dest = temp.path().join("archive.tar.gz");
+        std::fs::write(temp.path().join("archive.tar.gz.part"), "hello ").unwrap();
+        std::fs::write(temp.path().join("archive.tar.gz.part.validator"), "\"v1\"").unwrap();
+
+        let client = ReqwestClient::new();
+        client
+            .download_with(&format!("{}/archive.tar.gz", server.uri()), &dest, RESUME)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "brand new");
+    }
+
+    #[tokio::test]
+    async fn test_download_restarts_when_ranges_unsupported() {
+        let server = MockServer::start().await;
+
+        // Server ignores the Range header and sends the whole body
+        Mock::given(method("GET"))
+            .and(path("/archive.tar.gz"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello world"))
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dest = temp.path().join("archive.tar.gz");
+        std::fs::write(temp.path().join("archive.tar.gz.part"), "stale").unwrap();
+
+        let client = ReqwestClient::new();
+        client
+            .download(&format!("{}/archive.tar.gz", server.uri()), &dest)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn test_download_keeps_part_file_on_size_mismatch() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/archive.tar.gz"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 6-10/20")
+                    .set_body_string("world"),
+            )
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dest = temp.path().join("archive.tar.gz");
+        let part = temp.path().join("archive.tar.gz.part");
+        std::fs::write(&part, "hello ").unwrap();
+        std::fs::write(temp.path().join("archive.tar.gz.part.validator"), "\"v1\"").unwrap();
+
+        let client = ReqwestClient::new();
+        let result = client
+            .download_with(&format!("{}/archive.tar.gz", server.uri()), &dest, RESUME)
+            .await;
+
+        assert!(matches!(result, Err(Error::Network(msg)) if msg.contains("11 of 20 bytes")));
+        assert!(!dest.exists());
+        assert_eq!(std::fs::read_to_string(&part).unwrap(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn test_download_enforces_size_limit() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/big.bin"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![b'x'; 64]))
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dest = temp.path().join("big.bin");
+        let url = format!("{}/big.bin", server.uri());
+        let client = ReqwestClient::new();
+        let capped = |max_size| DownloadOptions {
+            max_size: Some(max_size),
+            resume: false,
+        };
+
+        // Content-Length gives it away before anything is written
+        let result = client.download_with(&url, &dest, capped(32)).await;
+        assert!(matches!(result, Err(Error::Network(msg)) if msg.contains("limit of 32 bytes")));
+        assert!(!dest.exists());
+        assert!(!temp.path().join("big.bin.part").exists());
+
+        client.download_with(&url, &dest, capped(64)).await.unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap().len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_request_log_redacts_secrets() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/manifest.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+            .mount(&server)
+            .await;
+
+        let lines = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let sink = lines.clone();
+        let client = ReqwestClient::new()
+            .with_request_log(move |line| sink.lock().unwrap().push(line.to_string()));
+
+        let url = server
+            .uri()
+            .replacen("http://", "http://deploy:hunter2@", 1);
+        client
+            .get(&format!("{}/manifest.json?access_token=ghp_secret123&ref=main", url))
+            .await
+            .unwrap();
+        let missing = format!("{}/missing.json?token=ghp_secret123", server.uri());
+        assert!(client.get(&missing).await.is_err());
+
+        let lines = lines.lock().unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("GET http://***@"));
+        assert!(lines[0].contains("access_token=***&ref=main"));
+        assert!(lines[0].ends_with("-> 200 OK (2 bytes)"));
+        assert!(lines[1].contains("404"));
+        for line in lines.iter() {
+            assert!(!line.contains("ghp_secret123"), "token leaked: {}", line);
+            assert!(!line.contains("hunter2"), "password leaked: {}", line);
+        }
+    }
+
+    #[test]
+    fn test_zero_rate_limit_disables_throttling() {
+        let client = ReqwestClient::new().with_rate_limit(Some(0));
+        assert!(client.throttle.is_none());
     }
 }
@@ -1,20 +1,89 @@
 //! Colored terminal logger implementation.
 
-use colored::Colorize;
+use std::fs::{File, OpenOptions};
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use colored::{ColoredString, Colorize};
 
 use crate::core::infra::Logger;
+use crate::core::types::{Error, Result};
+
+/// When to emit ANSI color codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Color only when writing to a terminal and not disabled by
+    /// `NO_COLOR` or `CLICOLOR=0`.
+    #[default]
+    Auto,
+    /// Always color, even when piped.
+    Always,
+    /// Never color.
+    Never,
+}
+
+impl ColorChoice {
+    /// Resolve whether a stream should be colored.
+    fn enabled(self, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+                let clicolor_off = std::env::var("CLICOLOR").is_ok_and(|v| v == "0");
+                is_terminal && !no_color && !clicolor_off
+            }
+        }
+    }
+}
 
 /// Logger implementation with colored terminal output.
 #[derive(Debug, Clone)]
 pub struct ColoredLogger {
     /// Verbosity level (0 = errors only, 1 = info, 2 = debug).
     verbosity: u8,
+    /// Whether stdout output is colored.
+    color_stdout: bool,
+    /// Whether stderr output is colored.
+    color_stderr: bool,
 }
 
 impl ColoredLogger {
     /// Create a new ColoredLogger with the specified verbosity level.
+    ///
+    /// Color is detected automatically (see [`ColorChoice::Auto`]).
     pub fn new(verbosity: u8) -> Self {
-        Self { verbosity }
+        Self::with_color(verbosity, ColorChoice::Auto)
+    }
+
+    /// Create a new ColoredLogger with an explicit color choice.
+    pub fn with_color(verbosity: u8, color: ColorChoice) -> Self {
+        if color == ColorChoice::Always {
+            // The colored crate has its own env detection; make it agree
+            colored::control::set_override(true);
+        }
+
+        Self {
+            verbosity,
+            color_stdout: color.enabled(std::io::stdout().is_terminal()),
+            color_stderr: color.enabled(std::io::stderr().is_terminal()),
+        }
+    }
+
+    /// Format a log line, coloring the label only when enabled.
+    fn format_line(
+        label: &str,
+        paint: fn(&str) -> ColoredString,
+        msg: &str,
+        color: bool,
+    ) -> String {
+        if color {
+            format!("{} {}", paint(label), msg)
+        } else {
+            format!("{} {}", label, msg)
+        }
     }
 }
 
@@ -27,29 +96,306 @@ impl Default for ColoredLogger {
 impl Logger for ColoredLogger {
     fn info(&self, msg: &str) {
         if self.verbosity >= 1 {
-            println!("{} {}", "[INFO]".blue(), msg);
+            println!("{}", Self::format_line("[INFO]", |s| s.blue(), msg, self.color_stdout));
         }
     }
 
     fn warn(&self, msg: &str) {
         if self.verbosity >= 1 {
-            println!("{} {}", "[WARN]".yellow(), msg);
+            println!("{}", Self::format_line("[WARN]", |s| s.yellow(), msg, self.color_stdout));
         }
     }
 
     fn error(&self, msg: &str) {
-        eprintln!("{} {}", "[ERROR]".red(), msg);
+        eprintln!("{}", Self::format_line("[ERROR]", |s| s.red(), msg, self.color_stderr));
     }
 
     fn debug(&self, msg: &str) {
         if self.verbosity >= 2 {
-            println!("{} {}", "[DEBUG]".dimmed(), msg);
+            println!("{}", Self::format_line("[DEBUG]", |s| s.dimmed(), msg, self.color_stdout));
         }
     }
 
     fn success(&self, msg: &str) {
         if self.verbosity >= 1 {
-            println!("{} {}", "[OK]".green(), msg);
+            println!("{}", Self::format_line("[OK]", |s| s.green(), msg, self.color_stdout));
+        }
+    }
+}
+
+/// Logger wrapper that tees every message to a log file.
+///
+/// Console output is delegated to the inner logger (and its verbosity),
+/// while the file receives all levels with a timestamp. The file is
+/// opened in append mode and flushed after each line. Clones share the file.
+#[derive(Debug, Clone)]
+pub struct TeeLogger<L: Logger> {
+    inner: L,
+    file: Option<Arc<Mutex<File>>>,
+}
+
+impl<L: Logger> TeeLogger<L> {
+    /// Wrap `inner`, additionally appending to `log_file` when given.
+    pub fn new(inner: L, log_file: Option<&Path>) -> Result<Self> {
+        let file = match log_file {
+            Some(path) => {
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                Some(Arc::new(Mutex::new(file)))
+            }
+            None => None,
+        };
+
+        Ok(Self { inner, file })
+    }
+
+    /// Append a single line to the log file, ignoring write failures.
+    fn write_line(&self, level: &str, msg: &str) {
+        if let Some(file) = &self.file
+            && let Ok(mut file) = file.lock()
+        {
+            let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ");
+            let _ = writeln!(file, "{} [{}] {}", timestamp, level, msg);
+            let _ = file.flush();
+        }
+    }
+}
+
+impl<L: Logger> Logger for TeeLogger<L> {
+    fn info(&self, msg: &str) {
+        self.write_line("INFO", msg);
+        self.inner.info(msg);
+    }
+
+    fn warn(&self, msg: &str) {
+        self.write_line("WARN", msg);
+        self.inner.warn(msg);
+    }
+
+    fn error(&self, msg: &str) {
+        self.write_line("ERROR", msg);
+        self.inner.error(msg);
+    }
+
+    fn debug(&self, msg: &str) {
+        self.write_line("DEBUG", msg);
+        self.inner.debug(msg);
+    }
+
+    fn success(&self, msg: &str) {
+        self.write_line("OK", msg);
+        self.inner.success(msg);
+    }
+
+    fn checkpoint(&self) -> Result<()> {
+        self.inner.checkpoint()
+    }
+}
+
+/// When `--strict` turns warnings into a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrictMode {
+    /// Warnings are only logged.
+    #[default]
+    Off,
+    /// The command runs to completion, then fails if anything warned.
+    AtEnd,
+    /// The command stops at its next checkpoint after the first warning.
+    Immediate,
+}
+
+/// Logger wrapper that counts warnings for `--strict`.
+///
+/// Clones share one count, so every copy handed to a command contributes.
+#[derive(Debug, Clone)]
+pub struct StrictLogger<L: Logger> {
+    inner: L,
+    mode: StrictMode,
+    warnings: Arc<AtomicUsize>,
+}
+
+impl<L: Logger> StrictLogger<L> {
+    /// Wrap `inner`, counting its warnings.
+    pub fn new(inner: L, mode: StrictMode) -> Self {
+        Self {
+            inner,
+            mode,
+            warnings: Arc::new(AtomicUsize::new(0)),
         }
     }
+
+    /// The configured strict mode.
+    pub fn mode(&self) -> StrictMode {
+        self.mode
+    }
+
+    /// Number of warnings logged so far.
+    pub fn warnings(&self) -> usize {
+        self.warnings.load(Ordering::SeqCst)
+    }
+
+    /// Turn a successful command result into [`Error::Warnings`] if anything warned.
+    ///
+    /// The command's own error always wins, and without strict mode this is a no-op.
+    pub fn finish(&self, result: Result<()>) -> Result<()> {
+        result?;
+        match self.warnings() {
+            count if count > 0 && self.mode != StrictMode::Off => Err(Error::Warnings(count)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<L: Logger> Logger for StrictLogger<L> {
+    fn info(&self, msg: &str) {
+        self.inner.info(msg);
+    }
+
+    fn warn(&self, msg: &str) {
+        self.inner.warn(msg);
+        self.warnings.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn error(&self, msg: &str) {
+        self.inner.error(msg);
+    }
+
+    fn debug(&self, msg: &str) {
+        self.inner.debug(msg);
+    }
+
+    fn success(&self, msg: &str) {
+        self.inner.success(msg);
+    }
+
+    fn checkpoint(&self) -> Result<()> {
+        self.inner.checkpoint()?;
+        match self.warnings() {
+            count if count > 0 && self.mode == StrictMode::Immediate => {
+                Err(Error::Warnings(count))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_color_never_has_no_escape_sequences() {
+        let logger = ColoredLogger::with_color(1, ColorChoice::Never);
+        assert!(!logger.color_stdout);
+        assert!(!logger.color_stderr);
+
+        let line = ColoredLogger::format_line("[INFO]", |s| s.blue(), "hello", logger.color_stdout);
+        assert_eq!(line, "[INFO] hello");
+        assert!(!line.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_color_always_has_escape_sequences() {
+        // Forced on regardless of whether the test runner has a terminal
+        let logger = ColoredLogger::with_color(1, ColorChoice::Always);
+        assert!(logger.color_stdout);
+        assert!(logger.color_stderr);
+
+        let line = ColoredLogger::format_line("[INFO]", |s| s.blue(), "hello", logger.color_stdout);
+        assert!(line.starts_with("\x1b["));
+        assert!(line.contains("[INFO]"));
+        assert!(line.ends_with(" hello"));
+    }
+
+    #[test]
+    fn test_color_choice_resolution() {
+        assert!(ColorChoice::Always.enabled(false));
+        assert!(!ColorChoice::Never.enabled(true));
+        // Never a terminal in auto mode means no color
+        assert!(!ColorChoice::Auto.enabled(false));
+    }
+
+    #[test]
+    fn test_tee_logger_writes_all_levels() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("aiassisted.log");
+
+        // Console verbosity 0 must not filter the file output
+        let logger = TeeLogger::new(ColoredLogger::new(0), Some(&log_path)).unwrap();
+        logger.info("info message");
+        logger.debug("debug message");
+        logger.success("success message");
+
+        let contents = std::fs::read_to_string(&log_path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("[INFO] info message"));
+        assert!(lines[1].ends_with("[DEBUG] debug message"));
+        assert!(lines[2].ends_with("[OK] success message"));
+    }
+
+    #[test]
+    fn test_tee_logger_appends() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("logs/aiassisted.log");
+
+        TeeLogger::new(ColoredLogger::new(0), Some(&log_path))
+            .unwrap()
+            .info("first run");
+        TeeLogger::new(ColoredLogger::new(0), Some(&log_path))
+            .unwrap()
+            .info("second run");
+
+        let contents = std::fs::read_to_string(&log_path).unwrap();
+        assert!(contents.contains("first run"));
+        assert!(contents.contains("second run"));
+    }
+
+    #[test]
+    fn test_tee_logger_without_file() {
+        let logger = TeeLogger::new(ColoredLogger::new(0), None).unwrap();
+        assert!(logger.file.is_none());
+        logger.info("console only");
+    }
+
+    #[test]
+    fn test_tee_logger_invalid_path() {
+        let temp_dir = TempDir::new().unwrap();
+        // A directory cannot be opened as a log file
+        let result = TeeLogger::new(ColoredLogger::new(0), Some(temp_dir.path()));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_strict_logger_finish_by_mode() {
+        let off = StrictLogger::new(ColoredLogger::new(0), StrictMode::Off);
+        off.warn("ignored");
+        assert_eq!(off.warnings(), 1);
+        assert!(off.finish(Ok(())).is_ok());
+
+        let strict = StrictLogger::new(ColoredLogger::new(0), StrictMode::AtEnd);
+        assert!(strict.finish(Ok(())).is_ok());
+        strict.clone().warn("counted through a clone");
+        strict.info("not a warning");
+        assert!(matches!(strict.finish(Ok(())), Err(Error::Warnings(1))));
+
+        // The command's own failure is reported instead
+        let result = strict.finish(Err(Error::NotFound("x".into())));
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn test_strict_logger_checkpoint_by_mode() {
+        let at_end = StrictLogger::new(ColoredLogger::new(0), StrictMode::AtEnd);
+        at_end.warn("reported when the command finishes");
+        assert!(at_end.checkpoint().is_ok());
+
+        let immediate = StrictLogger::new(ColoredLogger::new(0), StrictMode::Immediate);
+        assert!(immediate.checkpoint().is_ok());
+        immediate.clone().warn("stop at the next checkpoint");
+        assert!(matches!(immediate.checkpoint(), Err(Error::Warnings(1))));
+    }
 }
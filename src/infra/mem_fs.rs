@@ -0,0 +1,364 @@
+//! In-memory file system implementation.
+//!
+//! Available with the `test-util` feature. Files and directories live in a
+//! shared map, so clones of a [`MemFileSystem`] see the same tree.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Error as IoError, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+
+use crate::core::infra::FileSystem;
+use crate::core::types::Result;
+
+#[derive(Debug, Default)]
+struct Tree {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+    dirs: BTreeSet<PathBuf>,
+}
+
+impl Tree {
+    /// Record `path` and all of its ancestors as directories.
+    fn add_dirs(&mut self, path: &Path) {
+        for ancestor in path.ancestors() {
+            if ancestor.as_os_str().is_empty() {
+                break;
+            }
+            self.dirs.insert(ancestor.to_path_buf());
+        }
+    }
+
+    /// Ensure the parent of `path` exists as a directory.
+    fn add_parent(&mut self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            self.add_dirs(parent);
+        }
+    }
+
+    fn file(&self, path: &Path) -> Result<&Vec<u8>> {
+        self.files.get(path).ok_or_else(|| not_found(path))
+    }
+}
+
+fn not_found(path: &Path) -> crate::core::types::Error {
+    IoError::new(
+        ErrorKind::NotFound,
+        format!("{}: not found", path.display()),
+    )
+    .into()
+}
+
+/// File system implementation backed by memory.
+#[derive(Debug, Clone, Default)]
+pub struct MemFileSystem {
+    tree: Arc<Mutex<Tree>>,
+}
+
+impl MemFileSystem {
+    /// Create an empty in-memory file system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tree(&self) -> MutexGuard<'_, Tree> {
+        self.tree.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl FileSystem for MemFileSystem {
+    async fn read(&self, path: &Path) -> Result<String> {
+        let bytes = self.read_bytes(path).await?;
+        String::from_utf8(bytes).map_err(|e| {
+            IoError::new(ErrorKind::InvalidData, format!("{}: {}", path.display(), e)).into()
+        })
+    }
+
+    async fn write(&self, path: &Path, content: &str) -> Result<()> {
+        self.write_bytes(path, content.as_bytes()).await
+    }
+
+    async fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        self.tree().file(path).cloned()
+    }
+
+    async fn write_bytes(&self, path: &Path, content: &[u8]) -> Result<()> {
+        let mut tree = self.tree();
+        if tree.dirs.contains(path) {
+            return Err(IoError::new(
+                ErrorKind::IsADirectory,
+                format!("{}: is a directory", path.display()),
+            )
+            .into());
+        }
+        tree.add_parent(path);
+        tree.files.insert(path.to_path_buf(), content.to_vec());
+        Ok(())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        let tree = self.tree();
+        tree.files.contains_key(path) || tree.dirs.contains(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.tree().dirs.contains(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.tree().files.contains_key(path)
+    }
+
+    async fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        // There are no symlinks or working directory, so resolve lexically from the root
+        let mut resolved = PathBuf::from("/");
+        for component in path.components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+            }
+        }
+
+        if !self.exists(&resolved) && resolved != Path::new("/") {
+            return Err(not_found(path));
+        }
+        Ok(resolved)
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> Result<()> {
+        self.tree().add_dirs(path);
+        Ok(())
+    }
+
+    async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let tree = self.tree();
+        if !tree.dirs.contains(path) {
+            return Err(not_found(path));
+        }
+
+        let is_child = |p: &&PathBuf| p.parent() == Some(path);
+        let mut entries: Vec<PathBuf> = tree
+            .dirs
+            .iter()
+            .filter(is_child)
+            .chain(tree.files.keys().filter(is_child))
+            .cloned()
+            .collect();
+        entries.sort();
+        Ok(entries)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        let mut tree = self.tree();
+        let content = tree.file(from)?.clone();
+        tree.add_parent(to);
+        tree.files.insert(to.to_path_buf(), content);
+        Ok(())
+    }
+
+    async fn remove_file(&self, path: &Path) -> Result<()> {
+        self.tree()
+            .files
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| not_found(path))
+    }
+
+    async fn remove_dir_all(&self, path: &Path) -> Result<()> {
+        let mut tree = self.tree();
+        if !tree.dirs.contains(path) {
+            return Err(not_found(path));
+        }
+        tree.dirs.retain(|p| !p.starts_with(path));
+        tree.files.retain(|p, _| !p.starts_with(path));
+        Ok(())
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        let mut tree = self.tree();
+
+        if let Some(content) = tree.files.remove(from) {
+            tree.add_parent(to);
+            tree.files.insert(to.to_path_buf(), content);
+            return Ok(());
+        }
+
+        if !tree.dirs.contains(from) {
+            return Err(not_found(from));
+        }
+
+        let moved = |p: &Path| to.join(p.strip_prefix(from).unwrap_or(p));
+        let dirs: Vec<PathBuf> = tree
+            .dirs
+            .iter()
+            .filter(|p| p.starts_with(from))
+            .cloned()
+            .collect();
+        let files: Vec<PathBuf> = tree
+            .files
+            .keys()
+            .filter(|p| p.starts_with(from))
+            .cloned()
+            .collect();
+
+        tree.add_parent(to);
+        for dir in dirs {
+            tree.dirs.remove(&dir);
+            tree.dirs.insert(moved(&dir));
+        }
+        for file in files {
+            if let Some(content) = tree.files.remove(&file) {
+                tree.files.insert(moved(&file), content);
+            }
+        }
+        Ok(())
+    }
+
+    async fn walk_dir(&self, root: &Path) -> Result<Vec<PathBuf>> {
+        // BTreeMap keys are already sorted, matching StdFileSystem's order
+        Ok(self
+            .tree()
+            .files
+            .keys()
+            .filter(|p| p.starts_with(root))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_creates_parent_dirs() {
+        let fs = MemFileSystem::new();
+        fs.write(Path::new("/project/a/b.txt"), "hello")
+            .await
+            .unwrap();
+
+        assert!(fs.is_file(Path::new("/project/a/b.txt")));
+        assert!(fs.is_dir(Path::new("/project/a")));
+        assert!(fs.is_dir(Path::new("/project")));
+        assert_eq!(
+            fs.read(Path::new("/project/a/b.txt")).await.unwrap(),
+            "hello"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_missing_file() {
+        let fs = MemFileSystem::new();
+        assert!(fs.read(Path::new("/missing.txt")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bytes_roundtrip() {
+        let fs = MemFileSystem::new();
+        let content = [0x89, b'P', b'N', b'G', 0xff, 0x00];
+        fs.write_bytes(Path::new("/logo.png"), &content)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs.read_bytes(Path::new("/logo.png")).await.unwrap(),
+            content
+        );
+        assert!(fs.read(Path::new("/logo.png")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_dir_returns_direct_children() {
+        let fs = MemFileSystem::new();
+        fs.write(Path::new("/root/file.txt"), "").await.unwrap();
+        fs.write(Path::new("/root/sub/nested.txt"), "")
+            .await
+            .unwrap();
+        fs.create_dir_all(Path::new("/root/empty")).await.unwrap();
+
+        let entries = fs.list_dir(Path::new("/root")).await.unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                PathBuf::from("/root/empty"),
+                PathBuf::from("/root/file.txt"),
+                PathBuf::from("/root/sub"),
+            ]
+        );
+        assert!(fs.list_dir(Path::new("/nope")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_copy_and_remove_file() {
+        let fs = MemFileSystem::new();
+        fs.write(Path::new("/a.txt"), "copy me").await.unwrap();
+        fs.copy(Path::new("/a.txt"), Path::new("/dir/b.txt"))
+            .await
+            .unwrap();
+
+        assert_eq!(fs.read(Path::new("/dir/b.txt")).await.unwrap(), "copy me");
+
+        fs.remove_file(Path::new("/a.txt")).await.unwrap();
+        assert!(!fs.exists(Path::new("/a.txt")));
+        assert!(fs.remove_file(Path::new("/a.txt")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remove_dir_all() {
+        let fs = MemFileSystem::new();
+        fs.write(Path::new("/root/dir/a.txt"), "").await.unwrap();
+        fs.write(Path::new("/root/dir2/b.txt"), "").await.unwrap();
+
+        fs.remove_dir_all(Path::new("/root/dir")).await.unwrap();
+
+        assert!(!fs.exists(Path::new("/root/dir")));
+        assert!(!fs.exists(Path::new("/root/dir/a.txt")));
+        // A sibling sharing the name prefix is untouched
+        assert!(fs.exists(Path::new("/root/dir2/b.txt")));
+    }
+
+    #[tokio::test]
+    async fn test_rename_directory_moves_contents() {
+        let fs = MemFileSystem::new();
+        fs.write(Path::new("/old/sub/a.txt"), "a").await.unwrap();
+
+        fs.rename(Path::new("/old"), Path::new("/new"))
+            .await
+            .unwrap();
+
+        assert!(!fs.exists(Path::new("/old")));
+        assert!(fs.is_dir(Path::new("/new/sub")));
+        assert_eq!(fs.read(Path::new("/new/sub/a.txt")).await.unwrap(), "a");
+    }
+
+    #[tokio::test]
+    async fn test_canonicalize_resolves_dot_components() {
+        let fs = MemFileSystem::new();
+        fs.create_dir_all(Path::new("/project/sub")).await.unwrap();
+
+        assert_eq!(
+            fs.canonicalize(Path::new("/project/sub/../.")).await.unwrap(),
+            PathBuf::from("/project")
+        );
+        assert!(fs.canonicalize(Path::new("/project/missing")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_walk_dir_is_sorted() {
+        let fs = MemFileSystem::new();
+        fs.write(Path::new("/root/b/z.txt"), "").await.unwrap();
+        fs.write(Path::new("/root/a.txt"), "").await.unwrap();
+        fs.write(Path::new("/other/c.txt"), "").await.unwrap();
+
+        let files = fs.walk_dir(Path::new("/root")).await.unwrap();
+        assert_eq!(
+            files,
+            vec![PathBuf::from("/root/a.txt"), PathBuf::from("/root/b/z.txt")]
+        );
+    }
+}
@@ -9,13 +9,20 @@
 //! - [`ReqwestClient`] - HTTP client using reqwest
 //! - [`Sha2Checksum`] - SHA256 checksum using sha2
 //! - [`ColoredLogger`] - Colored terminal output
+//! - [`TeeLogger`] - Logger wrapper that also appends to a log file
+//! - [`StrictLogger`] - Logger wrapper that counts warnings for `--strict`
+//! - `MemFileSystem` - In-memory file system (`test-util` feature)
 
 mod checksum;
 mod fs;
 mod http;
 mod logger;
+#[cfg(any(test, feature = "test-util"))]
+mod mem_fs;
 
 pub use checksum::Sha2Checksum;
 pub use fs::StdFileSystem;
-pub use http::ReqwestClient;
-pub use logger::ColoredLogger;
+pub use http::{DEFAULT_USER_AGENT, ReqwestClient};
+pub use logger::{ColorChoice, ColoredLogger, StrictLogger, StrictMode, TeeLogger};
+#[cfg(any(test, feature = "test-util"))]
+pub use mem_fs::MemFileSystem;
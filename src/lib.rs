@@ -15,4 +15,4 @@ pub mod skills;
 
 // Re-export commonly used types
 pub use core::types::{Error, ManifestEntry, Result};
-pub use content::manifest::Manifest;
+pub use content::manifest::{Manifest, ManifestDiff};
@@ -1,24 +1,43 @@
 //! aiassisted CLI - Embed AI assistant guidelines and templates into projects.
 
+use std::path::{Path, PathBuf};
+
 use clap::Parser;
 
 // Binary-only module for CLI argument parsing
 mod cli;
 
 // Import from library crate using package name
-use cli::{Cli, Commands, ConfigCommands, SkillsCommands, AgentsCommands};
-use aiassisted::agents::{AgentsListCommand, AgentsSetupCommand, AgentsUpdateCommand};
+use cli::{Cli, Commands, ConfigCommands, ContentCommands, ManifestCommands, SkillsCommands, AgentsCommands};
+use aiassisted::agents::{
+    AgentsListCommand, AgentsRemoveCommand, AgentsSetupCommand, AgentsUpdateCommand,
+    AgentsValidateCommand,
+};
 use aiassisted::config::{
     EditCommand as ConfigEditCommand, GetCommand as ConfigGetCommand,
     PathCommand as ConfigPathCommand, ResetCommand as ConfigResetCommand,
-    ShowCommand as ConfigShowCommand, TomlConfigStore,
+    ShowCommand as ConfigShowCommand, TomlConfigStore, ValidateCommand as ConfigValidateCommand,
+};
+use aiassisted::content::{
+    ChangelogCommand, CheckCommand, ContentListCommand, ContentSource, InstallCommand,
+    ManifestGenerateCommand, RepairCommand, UpdateCommand, WatchCommand,
 };
-use aiassisted::content::{CheckCommand, InstallCommand, UpdateCommand};
+use aiassisted::content::github::{clamp_concurrency, default_cache_dir};
+use aiassisted::content::manifest::Manifest;
+use aiassisted::core::config::ConfigStore;
 use aiassisted::core::infra::{Checksum, FileSystem, HttpClient, Logger};
-use aiassisted::infra::{ColoredLogger, ReqwestClient, Sha2Checksum, StdFileSystem};
+use aiassisted::core::project::resolve_project_path;
+use aiassisted::core::types::{AppConfig, Result};
+use aiassisted::infra::{
+    ColoredLogger, ReqwestClient, Sha2Checksum, StdFileSystem, StrictLogger, StrictMode, TeeLogger,
+};
 use aiassisted::migration::MigrateCommand;
+use aiassisted::selfupdate::version::version_report;
 use aiassisted::selfupdate::{GithubReleasesProvider, SelfUpdateCommand};
-use aiassisted::skills::{SetupSkillsCommand, SkillsListCommand, SkillsUpdateCommand};
+use aiassisted::skills::{
+    SetupSkillsCommand, SkillsDiffCommand, SkillsDisableCommand, SkillsEnableCommand,
+    SkillsExportCommand, SkillsImportCommand, SkillsListCommand, SkillsUpdateCommand,
+};
 
 /// Application context holding all infrastructure dependencies.
 /// Uses static dispatch (generics) for zero-cost abstractions.
@@ -52,187 +71,490 @@ where
     }
 }
 
+/// Load the user config for settings that commands fall back on
+/// (output directory overrides, default tool).
+///
+/// Falls back to the built-in defaults if the config cannot be loaded.
+async fn load_user_config<L: Logger>(config_path: Option<PathBuf>, logger: &L) -> AppConfig {
+    let config = match TomlConfigStore::with_override(StdFileSystem::new(), config_path) {
+        Ok(store) => store.load().await,
+        Err(e) => Err(e),
+    };
+
+    config.unwrap_or_else(|e| {
+        logger.warn(&format!("Ignoring user config: {}", e));
+        AppConfig::default()
+    })
+}
+
+/// Attach the `--manifest` override, if any, to `source`.
+///
+/// Only commands that fetch content call this, so the rest never read the
+/// file or wait on stdin.
+async fn with_manifest_override<F: FileSystem>(
+    fs: &F,
+    source: ContentSource,
+    manifest: Option<&Path>,
+) -> Result<ContentSource> {
+    let manifest = match manifest {
+        None => return Ok(source),
+        Some(path) if path.as_os_str() == "-" => Manifest::load_reader(std::io::stdin().lock())?,
+        Some(path) => Manifest::load_local(fs, path).await?,
+    };
+    Ok(source.with_manifest(manifest))
+}
+
+/// The current directory, resolved like any other project path.
+async fn current_project_path<F: FileSystem>(fs: &F) -> Result<PathBuf> {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    resolve_project_path(fs, &cwd, false).await
+}
+
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
     let verbosity = cli.verbose.max(1); // Default to 1 if not specified
+    let verbose = cli.verbose > 0;
+    let config_path = cli.config.clone();
+    let refresh_detection = cli.refresh_detection;
 
     // Create infrastructure with concrete types (static dispatch)
     let fs = StdFileSystem::new();
-    let http = ReqwestClient::new();
     let checksum = Sha2Checksum::new();
-    let logger = ColoredLogger::new(verbosity);
+    let console = ColoredLogger::with_color(verbosity, cli.color.into());
+    let logger = match TeeLogger::new(console.clone(), cli.log_file.as_deref()) {
+        Ok(logger) => StrictLogger::new(logger, cli.strict.map_or(StrictMode::Off, Into::into)),
+        Err(e) => {
+            console.error(&format!("Error: failed to open log file: {}", e));
+            std::process::exit(e.exit_code());
+        }
+    };
+    let http = if cli.offline {
+        ReqwestClient::offline()
+    } else {
+        ReqwestClient::new()
+    }
+    .with_rate_limit(cli.rate_limit)
+    .with_request_log({
+        // Shown on the console at -vv and always written to --log-file
+        let logger = logger.clone();
+        move |line| logger.debug(line)
+    });
+
+    let source = ContentSource::new(cli.manifest_url.as_deref(), cli.content_base.as_deref())
+        .map(|source| {
+            if !cli.no_cache
+                && let Some(cache_dir) = default_cache_dir()
+            {
+                source.with_cache(cache_dir)
+            } else {
+                source
+            }
+        });
+    let source = match source {
+        Ok(source) => source,
+        Err(e) => {
+            logger.error(&format!("Error: {}", e));
+            std::process::exit(e.exit_code());
+        }
+    };
+    let manifest = cli.manifest.clone();
 
     let ctx = AppContext::new(fs, http, checksum, logger);
+    let concurrency = clamp_concurrency(cli.concurrency);
+    let keep_going = cli.keep_going;
+    let offline = cli.offline;
 
-    let result = match cli.command {
-        Commands::Install(args) => {
-            let cmd = InstallCommand { path: args.path };
-            cmd.execute(&ctx.fs, &ctx.http, &ctx.checksum, &ctx.logger)
-                .await
-        }
+    let run = async {
+        match cli.command {
+            Commands::Install(args) => async {
+                // A dry run must not create the directory it previews
+                let path = if args.dry_run && !ctx.fs.exists(&args.path) {
+                    args.path
+                } else {
+                    resolve_project_path(&ctx.fs, &args.path, true).await?
+                };
+                let cmd = InstallCommand {
+                    path,
+                    dry_run: args.dry_run,
+                    concurrency,
+                    keep_going,
+                    source: with_manifest_override(&ctx.fs, source, manifest.as_deref()).await?,
+                };
+                cmd.execute(&ctx.fs, &ctx.http, &ctx.checksum, &ctx.logger)
+                    .await
+                    .map(|_| ())
+            }
+            .await,
 
-        Commands::Update(args) => {
-            let cmd = UpdateCommand {
-                path: args.path,
-                force: args.force,
-            };
-            cmd.execute(&ctx.fs, &ctx.http, &ctx.checksum, &ctx.logger)
-                .await
-        }
+            Commands::Update(args) => async {
+                let cmd = UpdateCommand {
+                    path: resolve_project_path(&ctx.fs, &args.path, false).await?,
+                    force: args.force,
+                    dry_run: args.dry_run,
+                    prune: args.prune,
+                    concurrency,
+                    keep_going,
+                    backup: !args.no_backup,
+                    keep_backups: args.keep_backups,
+                    rollback: args.rollback,
+                    yes: args.yes,
+                    source: with_manifest_override(&ctx.fs, source, manifest.as_deref()).await?,
+                };
+                cmd.execute(&ctx.fs, &ctx.http, &ctx.checksum, &ctx.logger)
+                    .await
+                    .map(|_| ())
+            }
+            .await,
 
-        Commands::Check(args) => {
-            let cmd = CheckCommand { path: args.path };
-            cmd.execute(&ctx.fs, &ctx.http, &ctx.logger).await
-        }
+            Commands::Check(args) if args.watch => async {
+                let cmd = WatchCommand {
+                    path: resolve_project_path(&ctx.fs, &args.path, false).await?,
+                    interval: args.interval,
+                    fail_on_change: args.fail_on_change,
+                };
+                let ctrl_c = async {
+                    // Without a handler, Ctrl-C still ends the process the default way
+                    if tokio::signal::ctrl_c().await.is_err() {
+                        std::future::pending::<()>().await;
+                    }
+                };
+                cmd.execute(&ctx.fs, &ctx.checksum, &ctx.logger, ctrl_c).await
+            }
+            .await,
 
-        Commands::SetupSkills(args) => {
-            // Deprecation warning
-            ctx.logger.warn("'setup-skills' is deprecated. Use 'aiassisted skills setup' instead.");
+            Commands::Check(args) => async {
+                let cmd = CheckCommand {
+                    path: resolve_project_path(&ctx.fs, &args.path, false).await?,
+                    verbose,
+                    format: args.format.into(),
+                    quiet: args.quiet,
+                    source: with_manifest_override(&ctx.fs, source, manifest.as_deref()).await?,
+                    offline,
+                };
+                cmd.execute(&ctx.fs, &ctx.http, &ctx.checksum, &ctx.logger)
+                    .await
+                    .map(|_| ())
+            }
+            .await,
 
-            let tool: aiassisted::core::ToolType = args.tool.into();
-            let cmd = SetupSkillsCommand {
-                tool,
-                dry_run: args.dry_run,
-                force: args.force,
-            };
-            let project_path =
-                std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+            Commands::Repair(args) => async {
+                let cmd = RepairCommand {
+                    path: resolve_project_path(&ctx.fs, &args.path, false).await?,
+                    concurrency,
+                    keep_going,
+                    source: with_manifest_override(&ctx.fs, source, manifest.as_deref()).await?,
+                };
+                cmd.execute(&ctx.fs, &ctx.http, &ctx.checksum, &ctx.logger)
+                    .await
+            }
+            .await,
 
-            cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
-        }
+            Commands::Changelog(args) => async {
+                let cmd = ChangelogCommand {
+                    path: resolve_project_path(&ctx.fs, &args.path, false).await?,
+                    since: args.since,
+                    source: with_manifest_override(&ctx.fs, source, manifest.as_deref()).await?,
+                };
+                cmd.execute(&ctx.fs, &ctx.http, &ctx.logger).await
+            }
+            .await,
 
-        Commands::Skills(args) => {
-            let project_path =
-                std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+            Commands::Content(args) => match args.command {
+                ContentCommands::List {
+                    path,
+                    verify,
+                    format,
+                } => async {
+                    let cmd = ContentListCommand {
+                        path: resolve_project_path(&ctx.fs, &path, false).await?,
+                        verify,
+                        verbose,
+                        format: format.into(),
+                    };
+                    cmd.execute(&ctx.fs, &ctx.checksum, &ctx.logger)
+                        .await
+                        .map(|_| ())
+                }
+                .await,
+            },
 
-            match args.command {
-                SkillsCommands::Setup {
-                    tool,
-                    dry_run,
-                    force,
+            Commands::Manifest(args) => match args.command {
+                ManifestCommands::Generate {
+                    manifest_version,
+                    path,
                 } => {
-                    let tool: aiassisted::core::ToolType = tool.into();
-                    let cmd = SetupSkillsCommand {
+                    let cmd = ManifestGenerateCommand {
+                        path,
+                        version: manifest_version,
+                    };
+                    cmd.execute(&ctx.fs, &ctx.checksum, &ctx.logger).await
+                }
+            },
+
+            Commands::SetupSkills(args) => async {
+                // Deprecation warning
+                ctx.logger.warn("'setup-skills' is deprecated. Use 'aiassisted skills setup' instead.");
+
+                let tool: aiassisted::core::ToolType = args.tool.into();
+                let config = load_user_config(config_path.clone(), &ctx.logger).await;
+                let cmd = SetupSkillsCommand {
+                    tools: vec![tool],
+                    dry_run: args.dry_run,
+                    force: args.force,
+                    output_dirs: config.output_dirs,
+                    default_tool: config.default_tool,
+                    refresh_detection,
+                    follow_symlinks: false,
+                };
+                let project_path = current_project_path(&ctx.fs).await?;
+
+                cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+            }
+            .await,
+
+            Commands::Skills(args) => async {
+                let project_path = current_project_path(&ctx.fs).await?;
+
+                match args.command {
+                    SkillsCommands::Setup {
                         tool,
                         dry_run,
                         force,
-                    };
-                    cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
-                }
-                SkillsCommands::List { tool } => {
-                    let tool: aiassisted::core::ToolType = tool.into();
-                    let cmd = SkillsListCommand { tool };
-                    cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
-                }
-                SkillsCommands::Update {
-                    tool,
-                    dry_run,
-                    force,
-                } => {
-                    let tool: aiassisted::core::ToolType = tool.into();
-                    let cmd = SkillsUpdateCommand {
+                        follow_symlinks,
+                    } => {
+                        let config = load_user_config(config_path.clone(), &ctx.logger).await;
+                        let cmd = SetupSkillsCommand {
+                            tools: tool.into(),
+                            dry_run,
+                            force,
+                            output_dirs: config.output_dirs,
+                            default_tool: config.default_tool,
+                            refresh_detection,
+                            follow_symlinks,
+                        };
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
+                    SkillsCommands::List { tool } => {
+                        let tool: aiassisted::core::ToolType = tool.into();
+                        let config = load_user_config(config_path.clone(), &ctx.logger).await;
+                        let cmd = SkillsListCommand {
+                            tool,
+                            output_dirs: config.output_dirs,
+                            default_tool: config.default_tool,
+                            refresh_detection,
+                        };
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
+                    SkillsCommands::Update {
                         tool,
                         dry_run,
                         force,
-                    };
-                    cmd.execute(&ctx.fs, &ctx.checksum, &ctx.logger, &project_path)
-                        .await
+                        verify,
+                        follow_symlinks,
+                    } => {
+                        let tool: aiassisted::core::ToolType = tool.into();
+                        let config = load_user_config(config_path.clone(), &ctx.logger).await;
+                        let cmd = SkillsUpdateCommand {
+                            tool,
+                            dry_run,
+                            force,
+                            verify,
+                            output_dirs: config.output_dirs,
+                            default_tool: config.default_tool,
+                            refresh_detection,
+                            follow_symlinks,
+                        };
+                        cmd.execute(&ctx.fs, &ctx.checksum, &ctx.logger, &project_path)
+                            .await
+                    }
+                    SkillsCommands::Diff {
+                        tool,
+                        detailed,
+                        follow_symlinks,
+                    } => {
+                        let tool: aiassisted::core::ToolType = tool.into();
+                        let config = load_user_config(config_path.clone(), &ctx.logger).await;
+                        let cmd = SkillsDiffCommand {
+                            tool,
+                            detailed,
+                            output_dirs: config.output_dirs,
+                            default_tool: config.default_tool,
+                            refresh_detection,
+                            follow_symlinks,
+                        };
+                        cmd.execute(&ctx.fs, &ctx.checksum, &ctx.logger, &project_path)
+                            .await
+                    }
+                    SkillsCommands::Enable { name } => {
+                        let cmd = SkillsEnableCommand { name };
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
+                    SkillsCommands::Disable { name } => {
+                        let cmd = SkillsDisableCommand { name };
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
+                    SkillsCommands::Export { name, archive } => {
+                        let cmd = SkillsExportCommand { name, archive };
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
+                    SkillsCommands::Import { archive, force, yes } => {
+                        let cmd = SkillsImportCommand { archive, force, yes };
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
                 }
             }
-        }
+            .await,
 
-        Commands::Agents(args) => {
-            let project_path =
-                std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+            Commands::Agents(args) => async {
+                let project_path = current_project_path(&ctx.fs).await?;
 
-            match args.command {
-                None => {
-                    // Default: list agents
-                    let cmd = AgentsListCommand;
-                    cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
-                }
-                Some(AgentsCommands::Setup {
-                    platform,
-                    dry_run,
-                    force,
-                }) => {
-                    let platform: aiassisted::agents::Platform = platform.into();
-                    let cmd = AgentsSetupCommand {
+                match args.command {
+                    None => {
+                        // Default: list agents
+                        let cmd = AgentsListCommand;
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
+                    Some(AgentsCommands::Setup {
                         platform,
                         dry_run,
                         force,
-                    };
-                    cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
-                }
-                Some(AgentsCommands::Update {
-                    platform,
-                    dry_run,
-                    force,
-                }) => {
-                    let platform: aiassisted::agents::Platform = platform.into();
-                    let cmd = AgentsUpdateCommand {
+                        name,
+                    }) => {
+                        let platforms: Vec<aiassisted::agents::Platform> = platform.into();
+                        let cmd = AgentsSetupCommand {
+                            platforms,
+                            dry_run,
+                            force,
+                            output_dirs: load_user_config(config_path.clone(), &ctx.logger)
+                                .await
+                                .output_dirs,
+                            name,
+                        };
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
+                    Some(AgentsCommands::Validate { format }) => {
+                        let cmd = AgentsValidateCommand {
+                            format: format.into(),
+                        };
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
+                    Some(AgentsCommands::Update {
                         platform,
                         dry_run,
                         force,
-                    };
-                    cmd.execute(&ctx.fs, &ctx.checksum, &ctx.logger, &project_path)
-                        .await
+                        name,
+                        verify,
+                    }) => {
+                        let platform: aiassisted::agents::Platform = platform.into();
+                        let cmd = AgentsUpdateCommand {
+                            platform,
+                            dry_run,
+                            force,
+                            name,
+                            verify,
+                            output_dirs: load_user_config(config_path.clone(), &ctx.logger)
+                                .await
+                                .output_dirs,
+                        };
+                        cmd.execute(&ctx.fs, &ctx.checksum, &ctx.logger, &project_path)
+                            .await
+                    }
+                    Some(AgentsCommands::Remove {
+                        name,
+                        platform,
+                        all,
+                        dry_run,
+                        yes,
+                    }) => {
+                        let cmd = AgentsRemoveCommand {
+                            platform: platform.into(),
+                            name,
+                            all,
+                            dry_run,
+                            yes,
+                            output_dirs: load_user_config(config_path.clone(), &ctx.logger)
+                                .await
+                                .output_dirs,
+                        };
+                        cmd.execute(&ctx.fs, &ctx.logger, &project_path).await
+                    }
                 }
             }
-        }
+            .await,
 
-        Commands::Config(args) => async {
-            // Create config store
-            let config_store = TomlConfigStore::new(StdFileSystem::new())?;
+            Commands::Config(args) => async {
+                // Create config store
+                let config_store = TomlConfigStore::with_override(StdFileSystem::new(), config_path.clone())?;
 
-            match args.command {
-                ConfigCommands::Show => {
-                    let cmd = ConfigShowCommand;
-                    cmd.execute(&config_store, &ctx.logger).await
-                }
-                ConfigCommands::Get { key } => {
-                    let cmd = ConfigGetCommand { key };
-                    cmd.execute(&config_store, &ctx.logger).await
-                }
-                ConfigCommands::Edit => {
-                    let cmd = ConfigEditCommand;
-                    cmd.execute(&config_store, &ctx.logger).await
-                }
-                ConfigCommands::Reset { force } => {
-                    let cmd = ConfigResetCommand { force };
-                    cmd.execute(&config_store, &ctx.logger).await
-                }
-                ConfigCommands::Path => {
-                    let cmd = ConfigPathCommand;
-                    cmd.execute(&config_store).await
+                match args.command {
+                    ConfigCommands::Show => {
+                        let cmd = ConfigShowCommand;
+                        cmd.execute(&config_store, &ctx.logger).await
+                    }
+                    ConfigCommands::Get { key } => {
+                        let cmd = ConfigGetCommand { key };
+                        cmd.execute(&config_store, &ctx.logger).await
+                    }
+                    ConfigCommands::Edit => {
+                        let cmd = ConfigEditCommand;
+                        cmd.execute(&ctx.fs, &config_store, &ctx.logger).await
+                    }
+                    ConfigCommands::Reset { force } => {
+                        let cmd = ConfigResetCommand { force };
+                        cmd.execute(&config_store, &ctx.logger).await
+                    }
+                    ConfigCommands::Path => {
+                        let cmd = ConfigPathCommand;
+                        cmd.execute(&config_store).await
+                    }
+                    ConfigCommands::Validate => {
+                        let cmd = ConfigValidateCommand;
+                        cmd.execute(&ctx.fs, &config_store, &ctx.logger).await
+                    }
                 }
             }
-        }
-        .await,
+            .await,
 
-        Commands::SelfUpdate => {
-            let provider = GithubReleasesProvider::new(ctx.http);
-            let command = SelfUpdateCommand;
-            command.execute(&provider, &ctx.logger).await
-        }
+            Commands::SelfUpdate { temp_dir } => {
+                let temp_dir = match temp_dir {
+                    Some(dir) => Some(dir),
+                    None => load_user_config(config_path.clone(), &ctx.logger).await.temp_dir,
+                };
+                let provider = GithubReleasesProvider::new(ctx.http);
+                let command = SelfUpdateCommand {
+                    temp_dir,
+                    ..SelfUpdateCommand::default()
+                };
+                command.execute(&provider, &ctx.logger).await
+            }
 
-        Commands::Migrate => async {
-            let config_store = TomlConfigStore::new(StdFileSystem::new())?;
-            let cmd = MigrateCommand;
-            cmd.execute(&ctx.fs, &config_store, &ctx.logger).await.map(|_| ())
-        }
-        .await,
+            Commands::Migrate { retain } => async {
+                let config_store = TomlConfigStore::with_override(StdFileSystem::new(), config_path.clone())?;
+                let cmd = MigrateCommand { retain };
+                cmd.execute(&ctx.fs, &config_store, &ctx.logger).await.map(|_| ())
+            }
+            .await,
 
-        Commands::Version => {
-            println!("aiassisted {}", env!("CARGO_PKG_VERSION"));
-            Ok(())
+            Commands::Completions { shell } => {
+                cli::write_completions(shell, &mut std::io::stdout());
+                Ok(())
+            }
+
+            Commands::Version => {
+                println!("{}", version_report(verbose));
+                Ok(())
+            }
         }
     };
 
+    // With --strict=immediate, commands stop at their next checkpoint instead
+    let result = ctx.logger.finish(run.await);
+
     // Handle errors
     if let Err(e) = result {
         ctx.logger.error(&format!("Error: {}", e));
-        std::process::exit(1);
+        // See Error::exit_code for the mapping of error categories to codes
+        std::process::exit(e.exit_code());
     }
 }
@@ -4,10 +4,20 @@ use std::path::{Path, PathBuf};
 
 use crate::core::config::ConfigStore;
 use crate::core::infra::{FileSystem, Logger};
+use crate::core::project::ensure_removable;
 use crate::core::types::Result;
 
 use super::shell_config::ShellConfig;
 
+/// Prefix of the timestamped backups of `~/.aiassisted/source`.
+const BACKUP_PREFIX: &str = "source.backup.";
+
+/// Timestamp format of migration backup names.
+const BACKUP_TIMESTAMP: &str = "%Y%m%d_%H%M%S";
+
+/// Number of migration backups kept by default.
+pub const DEFAULT_RETAIN_BACKUPS: usize = 3;
+
 /// Migration report showing what was done.
 #[derive(Debug)]
 pub struct MigrationReport {
@@ -15,10 +25,24 @@ pub struct MigrationReport {
     pub old_install_found: bool,
     pub backup_path: Option<PathBuf>,
     pub config_migrated: bool,
+    /// Older backups removed to honour `--retain`
+    pub pruned_backups: Vec<PathBuf>,
 }
 
 /// Migrate command - migrates from shell-based to Rust version.
-pub struct MigrateCommand;
+pub struct MigrateCommand {
+    /// Number of `source.backup.<timestamp>` directories to keep; the backup
+    /// made by this run is always kept
+    pub retain: usize,
+}
+
+impl Default for MigrateCommand {
+    fn default() -> Self {
+        Self {
+            retain: DEFAULT_RETAIN_BACKUPS,
+        }
+    }
+}
 
 impl MigrateCommand {
     /// Execute the migrate command.
@@ -48,6 +72,7 @@ impl MigrateCommand {
             old_install_found: fs.exists(&old_source_dir) && fs.is_dir(&old_source_dir),
             backup_path: None,
             config_migrated: false,
+            pruned_backups: Vec::new(),
         };
 
         // Check if there's anything to migrate
@@ -68,7 +93,7 @@ impl MigrateCommand {
         if report.old_config_found {
             logger.info("Migrating configuration...");
             let old_config_contents = fs.read(&old_config_path).await?;
-            let shell_config = ShellConfig::parse(&old_config_contents)?;
+            let shell_config = ShellConfig::parse_at(&old_config_contents, &old_config_path)?;
             let new_config = shell_config.to_app_config();
 
             config_store.save(&new_config).await?;
@@ -84,10 +109,10 @@ impl MigrateCommand {
         // Backup old installation if found
         if report.old_install_found {
             logger.info("Backing up old installation...");
-            let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
+            let timestamp = chrono::Utc::now().format(BACKUP_TIMESTAMP).to_string();
             let backup_dir = home_dir
                 .join(".aiassisted")
-                .join(format!("source.backup.{}", timestamp));
+                .join(format!("{}{}", BACKUP_PREFIX, timestamp));
 
             self.copy_dir_recursive(fs, &old_source_dir, &backup_dir)
                 .await?;
@@ -100,7 +125,11 @@ impl MigrateCommand {
 
             // Remove old git repository (best effort - don't fail if it doesn't work)
             logger.info("Removing old git repository...");
-            match self.remove_dir_recursive(fs, &old_source_dir).await {
+            let removal = match ensure_removable(&old_source_dir, &home_dir) {
+                Ok(()) => self.remove_dir_recursive(fs, &old_source_dir).await,
+                Err(e) => Err(e),
+            };
+            match removal {
                 Ok(_) => {
                     logger.success("Old installation removed");
                 }
@@ -122,6 +151,7 @@ impl MigrateCommand {
         }
 
         // Remove old config file
+        logger.checkpoint()?;
         if report.old_config_found {
             logger.info("Removing old config file...");
             fs.write(&old_config_path, "").await?; // Empty it first
@@ -130,6 +160,14 @@ impl MigrateCommand {
             logger.debug("Old config file cleared");
         }
 
+        if report.backup_path.is_some() {
+            let backups_root = home_dir.join(".aiassisted");
+            report.pruned_backups = prune_backups(fs, &backups_root, self.retain.max(1)).await?;
+            for pruned in &report.pruned_backups {
+                logger.info(&format!("Removed old backup: {}", pruned.display()));
+            }
+        }
+
         logger.success("Migration completed successfully!");
         self.print_report(logger, &report);
 
@@ -137,6 +175,8 @@ impl MigrateCommand {
     }
 
     /// Recursively copy a directory.
+    ///
+    /// Symlinks are skipped, never followed or recreated.
     #[allow(clippy::only_used_in_recursion)]
     fn copy_dir_recursive<'a, F: FileSystem>(
         &'a self,
@@ -156,6 +196,9 @@ impl MigrateCommand {
                     ))?;
                 let dest = to.join(file_name);
 
+                if fs.is_symlink(&entry) {
+                    continue;
+                }
                 if fs.is_dir(&entry) {
                     self.copy_dir_recursive(fs, &entry, &dest).await?;
                 } else {
@@ -177,6 +220,10 @@ impl MigrateCommand {
         Box::pin(async move {
             let entries = fs.list_dir(path).await?;
             for entry in entries {
+                // Never touch what a symlink points to
+                if fs.is_symlink(&entry) {
+                    continue;
+                }
                 if fs.is_dir(&entry) {
                     self.remove_dir_recursive(fs, &entry).await?;
                 } else {
@@ -206,6 +253,44 @@ impl MigrateCommand {
     }
 }
 
+/// Whether `name` is a backup made by migration (`source.backup.<timestamp>`).
+fn is_backup_name(name: &str) -> bool {
+    name.strip_prefix(BACKUP_PREFIX).is_some_and(|timestamp| {
+        chrono::NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP).is_ok()
+    })
+}
+
+/// Remove the oldest migration backups in `root` so that at most `retain` remain.
+///
+/// Only directories named like a backup are considered. Returns the removed
+/// backup directories.
+async fn prune_backups<F: FileSystem>(fs: &F, root: &Path, retain: usize) -> Result<Vec<PathBuf>> {
+    let mut backups: Vec<PathBuf> = fs
+        .list_dir(root)
+        .await?
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(is_backup_name)
+                && fs.is_dir(path)
+                && !fs.is_symlink(path)
+        })
+        .collect();
+    // Timestamps sort chronologically
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(retain);
+    let mut removed = Vec::new();
+    for backup in backups.into_iter().take(excess) {
+        ensure_removable(&backup, root)?;
+        fs.remove_dir_all(&backup).await?;
+        removed.push(backup);
+    }
+
+    Ok(removed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,6 +311,9 @@ mod tests {
             async fn create_dir_all(&self, path: &Path) -> Result<()>;
             async fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
             async fn copy(&self, from: &Path, to: &Path) -> Result<()>;
+            async fn remove_file(&self, path: &Path) -> Result<()>;
+            async fn remove_dir_all(&self, path: &Path) -> Result<()>;
+            async fn rename(&self, from: &Path, to: &Path) -> Result<()>;
         }
     }
 
@@ -264,7 +352,7 @@ mod tests {
         mock_fs.expect_exists().returning(|_| false);
         mock_logger.expect_info().returning(|_| ());
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let report = cmd
             .execute(&mock_fs, &mock_config, &mock_logger)
             .await
@@ -326,7 +414,7 @@ prefer_project = true
         mock_logger.expect_success().returning(|_| ());
         mock_logger.expect_debug().returning(|_| ());
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let report = cmd
             .execute(&mock_fs, &mock_config, &mock_logger)
             .await
@@ -374,7 +462,7 @@ prefer_project = true
         mock_logger.expect_info().returning(|_| ());
         mock_logger.expect_success().returning(|_| ());
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let report = cmd
             .execute(&mock_fs, &mock_config, &mock_logger)
             .await
@@ -436,7 +524,7 @@ prefer_project = false
         mock_logger.expect_success().returning(|_| ());
         mock_logger.expect_debug().returning(|_| ());
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let report = cmd
             .execute(&mock_fs, &mock_config, &mock_logger)
             .await
@@ -471,12 +559,30 @@ prefer_project = false
 
         mock_logger.expect_info().returning(|_| ());
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let result = cmd.execute(&mock_fs, &mock_config, &mock_logger).await;
 
         assert!(result.is_err());
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_copy_dir_recursive_skips_symlinked_dirs() {
+        let fs = crate::infra::StdFileSystem::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        fs.write(&source.join("sub/file.txt"), "data").await.unwrap();
+        // Points back at the source root: following it would never end
+        std::os::unix::fs::symlink(&source, source.join("sub/loop")).unwrap();
+
+        let dest = temp_dir.path().join("dest");
+        let cmd = MigrateCommand::default();
+        cmd.copy_dir_recursive(&fs, &source, &dest).await.unwrap();
+
+        assert_eq!(fs.read(&dest.join("sub/file.txt")).await.unwrap(), "data");
+        assert!(!dest.join("sub/loop").exists());
+    }
+
     #[tokio::test]
     async fn test_copy_dir_recursive_empty() {
         let mut mock_fs = MockFileSystem::new();
@@ -489,7 +595,7 @@ prefer_project = false
             .expect_list_dir()
             .returning(|_| Ok(vec![]));
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let result = cmd
             .copy_dir_recursive(
                 &mock_fs,
@@ -521,7 +627,7 @@ prefer_project = false
         mock_fs.expect_is_dir().returning(|_| false);
         mock_fs.expect_copy().returning(|_, _| Ok(()));
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let result = cmd
             .copy_dir_recursive(
                 &mock_fs,
@@ -554,7 +660,7 @@ prefer_project = false
 
         mock_fs.expect_is_dir().returning(|_| true);
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let result = cmd
             .copy_dir_recursive(
                 &mock_fs,
@@ -574,7 +680,7 @@ prefer_project = false
             .expect_list_dir()
             .returning(|_| Ok(vec![]));
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let result = cmd
             .remove_dir_recursive(&mock_fs, Path::new("/path"))
             .await;
@@ -598,11 +704,52 @@ prefer_project = false
         mock_fs.expect_is_dir().returning(|_| false);
         mock_fs.expect_write().returning(|_, _| Ok(()));
 
-        let cmd = MigrateCommand;
+        let cmd = MigrateCommand::default();
         let result = cmd
             .remove_dir_recursive(&mock_fs, Path::new("/path"))
             .await;
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_prune_backups_keeps_newest_matching() {
+        use crate::infra::StdFileSystem;
+
+        let fs = StdFileSystem::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for name in [
+            "source.backup.20240101_000000",
+            "source.backup.20240102_000000",
+            "source.backup.20240103_000000",
+            "source.backup.20240104_000000",
+            "source.backup.notes",
+            "source",
+        ] {
+            fs.create_dir_all(&root.join(name)).await.unwrap();
+        }
+        // A file with a backup name is not a backup
+        fs.write(&root.join("source.backup.20230101_000000"), "x").await.unwrap();
+
+        let removed = prune_backups(&fs, root, 2).await.unwrap();
+
+        assert_eq!(
+            removed,
+            vec![
+                root.join("source.backup.20240101_000000"),
+                root.join("source.backup.20240102_000000"),
+            ]
+        );
+        for kept in [
+            "source.backup.20240103_000000",
+            "source.backup.20240104_000000",
+            "source.backup.notes",
+            "source",
+            "source.backup.20230101_000000",
+        ] {
+            assert!(fs.exists(&root.join(kept)), "{}", kept);
+        }
+        assert!(prune_backups(&fs, root, 2).await.unwrap().is_empty());
+    }
 }
@@ -6,4 +6,4 @@
 pub mod commands;
 pub mod shell_config;
 
-pub use commands::MigrateCommand;
+pub use commands::{MigrateCommand, DEFAULT_RETAIN_BACKUPS};
@@ -3,6 +3,8 @@
 //! The old shell-based version used a different TOML structure at
 //! `~/.aiassisted/config.toml` with multiple sections.
 
+use std::path::Path;
+
 use serde::Deserialize;
 
 use crate::core::types::{AppConfig, Error, Result, ToolType};
@@ -57,8 +59,12 @@ fn default_true() -> bool {
 impl ShellConfig {
     /// Parse old TOML config from string.
     pub fn parse(contents: &str) -> Result<Self> {
-        toml::from_str(contents)
-            .map_err(|e| Error::Parse(format!("Failed to parse shell config: {}", e)))
+        Self::parse_at(contents, Path::new("shell config"))
+    }
+
+    /// Parse old TOML config read from `path`, naming it in error messages.
+    pub fn parse_at(contents: &str, path: &Path) -> Result<Self> {
+        toml::from_str(contents).map_err(|e| Error::toml(path.display(), contents, &e))
     }
 
     /// Convert old config to new AppConfig.
@@ -82,6 +88,7 @@ impl ShellConfig {
             verbosity: self.general.verbosity,
             auto_update: self.install.auto_update,
             prefer_project: self.templates.prefer_project,
+            ..Default::default()
         }
     }
 }
@@ -2,17 +2,76 @@
 
 use std::env;
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use flate2::read::GzDecoder;
 use tar::Archive;
 
+use crate::core::archive::validate_entry_path;
 use crate::core::infra::Logger;
 use crate::core::selfupdate::ReleaseProvider;
-use crate::core::types::{Error, Result};
+use crate::core::types::{Error, ReleaseInfo, Result};
+
+/// Default maximum size of a downloaded release archive (100 MB).
+pub const DEFAULT_MAX_DOWNLOAD_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Default maximum size of the extracted binary (200 MB).
+pub const DEFAULT_MAX_BINARY_SIZE: u64 = 200 * 1024 * 1024;
+
+/// Default timeout for downloading a release archive.
+pub const DEFAULT_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Number of release-note lines shown before the update starts.
+const NOTES_PREVIEW_LINES: usize = 12;
 
 /// Self-update command for updating the CLI binary.
-pub struct SelfUpdateCommand;
+#[derive(Debug, Clone)]
+pub struct SelfUpdateCommand {
+    /// Maximum size of the downloaded archive in bytes.
+    pub max_download_size: u64,
+    /// Maximum size of the extracted binary in bytes.
+    pub max_binary_size: u64,
+    /// Maximum time allowed for the download.
+    pub download_timeout: Duration,
+    /// Where the archive is downloaded and the binary extracted
+    /// (`--temp-dir` or `temp_dir` in config); `None` uses the system temp
+    /// directory, which honors `TMPDIR`.
+    pub temp_dir: Option<PathBuf>,
+}
+
+impl Default for SelfUpdateCommand {
+    fn default() -> Self {
+        Self {
+            max_download_size: DEFAULT_MAX_DOWNLOAD_SIZE,
+            max_binary_size: DEFAULT_MAX_BINARY_SIZE,
+            download_timeout: DEFAULT_DOWNLOAD_TIMEOUT,
+            temp_dir: None,
+        }
+    }
+}
+
+/// The first lines of a release's notes, with a pointer to the full notes if cut short.
+///
+/// Blank lines at either end are dropped; `None` if the release has no notes.
+fn notes_preview(release: &ReleaseInfo) -> Option<Vec<String>> {
+    let notes = release.notes.as_deref()?.trim();
+    if notes.is_empty() {
+        return None;
+    }
+
+    let lines: Vec<&str> = notes.lines().map(str::trim_end).collect();
+    let mut preview: Vec<String> =
+        lines.iter().take(NOTES_PREVIEW_LINES).map(|l| l.to_string()).collect();
+    if lines.len() > NOTES_PREVIEW_LINES {
+        preview.push(match &release.notes_url {
+            Some(url) => format!("... see full notes at {}", url),
+            None => format!("... ({} more lines)", lines.len() - NOTES_PREVIEW_LINES),
+        });
+    }
+    Some(preview)
+}
 
 impl SelfUpdateCommand {
     /// Execute the self-update command.
@@ -38,19 +97,28 @@ impl SelfUpdateCommand {
         // Get latest release info
         let release = provider.get_latest().await?;
         logger.info(&format!("New version available: {}", release.version));
+        if let Some(preview) = notes_preview(&release) {
+            logger.info("Release notes:");
+            for line in preview {
+                logger.info(&format!("  {}", line));
+            }
+        }
 
-        // Download to temp directory
-        let temp_dir = env::temp_dir();
-        let archive_path = temp_dir.join(format!("aiassisted-{}.archive", release.version));
+        // Download into a private directory that is removed when we are done
+        let temp_dir = self.prepare_temp_dir()?;
+        let archive_path = temp_dir
+            .path()
+            .join(format!("aiassisted-{}.archive", release.version));
 
         logger.info(&format!(
             "Downloading {} ...",
             release.download_url.rsplit('/').next().unwrap_or("binary")
         ));
-        provider.download_release(&release, &archive_path).await?;
+        self.download(provider, &release, &archive_path).await?;
 
         // Extract binary
-        let binary_path = Self::extract_binary(&archive_path)?;
+        let binary_path =
+            Self::extract_binary(&archive_path, temp_dir.path(), self.max_binary_size)?;
         logger.info("Binary extracted successfully");
 
         // Replace current binary
@@ -58,9 +126,168 @@ impl SelfUpdateCommand {
 
         logger.success(&format!("Updated to version {}", release.version));
 
-        // Cleanup
-        let _ = fs::remove_file(&archive_path);
-        let _ = fs::remove_file(&binary_path);
+        Ok(())
+    }
+
+    /// A fresh private directory inside the temp directory, checked to be usable.
+    ///
+    /// The base directory must be writable and, on Unix, allow executing
+    /// files: updates failed halfway on small or `noexec` tmpfs mounts before
+    /// this was checked. Everything is written to a new subdirectory only we
+    /// can access (mode 0700 on Unix), so nobody sharing the base directory
+    /// can plant or swap the archive, the binary or the probe.
+    fn prepare_temp_dir(&self) -> Result<tempfile::TempDir> {
+        let dir = self.temp_dir.clone().unwrap_or_else(env::temp_dir);
+        let unusable = |reason: String| {
+            Error::Config(format!(
+                "Temporary directory {} {}; pass --temp-dir or set temp_dir in config",
+                dir.display(),
+                reason
+            ))
+        };
+
+        fs::create_dir_all(&dir).map_err(|e| unusable(format!("cannot be created: {}", e)))?;
+        let mut builder = tempfile::Builder::new();
+        builder.prefix("aiassisted-update-");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(fs::Permissions::from_mode(0o700));
+        }
+        let private = builder
+            .tempdir_in(&dir)
+            .map_err(|e| unusable(format!("is not writable: {}", e)))?;
+
+        let probe = private.path().join("probe.sh");
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&probe)
+            .and_then(|mut file| file.write_all(b"#!/bin/sh\nexit 0\n"))
+            .map_err(|e| unusable(format!("is not writable: {}", e)))?;
+
+        let executable = Self::can_execute(&probe);
+        let _ = fs::remove_file(&probe);
+        if !executable {
+            return Err(unusable("does not allow executing files (mounted noexec?)".to_string()));
+        }
+
+        Ok(private)
+    }
+
+    /// Whether `probe`, a shell script, may be executed where it is.
+    ///
+    /// Only a permission error counts against it; anything else (no
+    /// `/bin/sh`, a busy file) says nothing about the mount.
+    #[cfg(unix)]
+    fn can_execute(probe: &Path) -> bool {
+        if Self::make_executable(probe).is_err() {
+            return false;
+        }
+        !matches!(
+            std::process::Command::new(probe).status(),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied
+        )
+    }
+
+    #[cfg(not(unix))]
+    fn can_execute(_probe: &Path) -> bool {
+        true
+    }
+
+    /// Download the release archive, enforcing the timeout and size limit.
+    ///
+    /// The provider stops the transfer once the archive outgrows the limit;
+    /// the size is checked again here in case it could not.
+    async fn download<R: ReleaseProvider>(
+        &self,
+        provider: &R,
+        release: &ReleaseInfo,
+        archive_path: &Path,
+    ) -> Result<()> {
+        tokio::time::timeout(
+            self.download_timeout,
+            provider.download_release(release, archive_path, self.max_download_size),
+        )
+        .await
+        .map_err(|_| {
+            Error::Network(format!(
+                "Download timed out after {} seconds",
+                self.download_timeout.as_secs()
+            ))
+        })??;
+
+        let size = fs::metadata(archive_path).map_err(Error::from)?.len();
+        if size > self.max_download_size {
+            let _ = fs::remove_file(archive_path);
+            return Err(Error::Network(format!(
+                "Downloaded archive is {} bytes, exceeding the limit of {} bytes",
+                size, self.max_download_size
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Resolve where an archive entry will be written, ensuring it stays within `root`.
+    ///
+    /// Both paths are canonicalized so that symlinks cannot redirect the write
+    /// outside the extraction directory.
+    fn contained_destination(root: &Path, filename: &Path, entry_name: &str) -> Result<PathBuf> {
+        let escape_error = || {
+            Error::Parse(format!(
+                "Archive entry escapes extraction directory: {}",
+                entry_name
+            ))
+        };
+
+        let root = root.canonicalize().map_err(Error::from)?;
+        let dest_path = root.join(filename);
+
+        // Resolve the final destination if something already exists there
+        // (possibly a symlink), otherwise resolve its parent directory.
+        let resolved = if fs::symlink_metadata(&dest_path).is_ok() {
+            dest_path.canonicalize().map_err(|_| escape_error())?
+        } else {
+            dest_path
+                .parent()
+                .ok_or_else(escape_error)?
+                .canonicalize()
+                .map_err(Error::from)?
+                .join(dest_path.file_name().ok_or_else(escape_error)?)
+        };
+
+        if !resolved.starts_with(&root) {
+            return Err(escape_error());
+        }
+
+        Ok(dest_path)
+    }
+
+    /// Reject binaries larger than `max_size`.
+    fn check_binary_size(size: u64, max_size: u64) -> Result<()> {
+        if size > max_size {
+            return Err(Error::Parse(format!(
+                "Binary in archive is {} bytes, exceeding the limit of {} bytes",
+                size, max_size
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Copy at most `max_size` bytes from `reader` to `dest`, failing if there is more.
+    fn write_capped<R: Read>(reader: &mut R, dest: &Path, max_size: u64) -> Result<()> {
+        let mut outfile = fs::File::create(dest).map_err(Error::from)?;
+
+        let written = std::io::copy(&mut reader.take(max_size + 1), &mut outfile)
+            .map_err(Error::from)?;
+
+        if written > max_size {
+            drop(outfile);
+            let _ = fs::remove_file(dest);
+            return Self::check_binary_size(written, max_size);
+        }
 
         Ok(())
     }
@@ -68,15 +295,15 @@ impl SelfUpdateCommand {
     /// Extract the binary from the downloaded archive.
     ///
     /// Handles both .tar.gz and .zip formats.
-    fn extract_binary(archive_path: &Path) -> Result<PathBuf> {
+    fn extract_binary(archive_path: &Path, temp_dir: &Path, max_size: u64) -> Result<PathBuf> {
         let extension = archive_path
             .extension()
             .and_then(|e| e.to_str())
             .unwrap_or("");
 
         match extension {
-            "gz" | "archive" => Self::extract_tar_gz(archive_path),
-            "zip" => Self::extract_zip(archive_path),
+            "gz" | "archive" => Self::extract_tar_gz(archive_path, temp_dir, max_size),
+            "zip" => Self::extract_zip(archive_path, temp_dir, max_size),
             _ => Err(Error::Parse(format!(
                 "Unsupported archive format: {}",
                 extension
@@ -85,15 +312,13 @@ impl SelfUpdateCommand {
     }
 
     /// Extract binary from .tar.gz archive.
-    fn extract_tar_gz(archive_path: &Path) -> Result<PathBuf> {
+    fn extract_tar_gz(archive_path: &Path, temp_dir: &Path, max_size: u64) -> Result<PathBuf> {
         let file = fs::File::open(archive_path)
             .map_err(Error::from)?;
 
         let decoder = GzDecoder::new(file);
         let mut archive = Archive::new(decoder);
 
-        let temp_dir = env::temp_dir();
-
         for entry in archive
             .entries()
             .map_err(Error::from)?
@@ -103,7 +328,10 @@ impl SelfUpdateCommand {
 
             let path = entry
                 .path()
-                .map_err(Error::from)?;
+                .map_err(Error::from)?
+                .into_owned();
+
+            validate_entry_path(&path)?;
 
             // Look for the binary file (skip directories and other files)
             let is_file = entry.header().entry_type().is_file();
@@ -114,10 +342,14 @@ impl SelfUpdateCommand {
                     && !filename_str.ends_with(".md")
                     && !filename_str.ends_with(".txt")
                 {
-                    let dest_path = temp_dir.join(filename);
-                    entry
-                        .unpack(&dest_path)
-                        .map_err(Error::from)?;
+                    Self::check_binary_size(entry.header().size().map_err(Error::from)?, max_size)?;
+
+                    let dest_path = Self::contained_destination(
+                        temp_dir,
+                        Path::new(filename),
+                        &path.to_string_lossy(),
+                    )?;
+                    Self::write_capped(&mut entry, &dest_path, max_size)?;
 
                     // Make executable on Unix
                     #[cfg(unix)]
@@ -134,32 +366,31 @@ impl SelfUpdateCommand {
     }
 
     /// Extract binary from .zip archive.
-    fn extract_zip(archive_path: &Path) -> Result<PathBuf> {
+    fn extract_zip(archive_path: &Path, temp_dir: &Path, max_size: u64) -> Result<PathBuf> {
         let file = fs::File::open(archive_path)
             .map_err(Error::from)?;
 
         let mut archive =
             zip::ZipArchive::new(file).map_err(|e| Error::Parse(format!("Failed to read zip: {}", e)))?;
 
-        let temp_dir = env::temp_dir();
-
         for i in 0..archive.len() {
             let mut entry = archive
                 .by_index(i)
                 .map_err(|e| Error::Parse(format!("Failed to read zip entry: {}", e)))?;
 
+            validate_entry_path(Path::new(entry.name()))?;
+
             if let Some(filename) = entry.name().rsplit('/').next().filter(|f|
                 f.starts_with("aiassisted")
                     && !f.ends_with(".md")
                     && !f.ends_with(".txt")
                     && !entry.is_dir()
             ) {
-                let dest_path = temp_dir.join(filename);
-                let mut outfile = fs::File::create(&dest_path)
-                    .map_err(Error::from)?;
+                Self::check_binary_size(entry.size(), max_size)?;
 
-                std::io::copy(&mut entry, &mut outfile)
-                    .map_err(Error::from)?;
+                let dest_path =
+                    Self::contained_destination(temp_dir, Path::new(filename), entry.name())?;
+                Self::write_capped(&mut entry, &dest_path, max_size)?;
 
                 // Make executable on Unix
                 #[cfg(unix)]
@@ -242,7 +473,6 @@ mod tests {
     use crate::core::types::ReleaseInfo;
     use async_trait::async_trait;
     use mockall::mock;
-    use std::io::Write;
     use tempfile::TempDir;
 
     #[derive(Debug, Clone, Default)]
@@ -263,7 +493,12 @@ mod tests {
         impl ReleaseProvider for ReleaseProvider {
             async fn get_latest(&self) -> Result<ReleaseInfo>;
             async fn is_update_available(&self, current_version: &str) -> Result<bool>;
-            async fn download_release(&self, release: &ReleaseInfo, dest: &Path) -> Result<()>;
+            async fn download_release(
+                &self,
+                release: &ReleaseInfo,
+                dest: &Path,
+                max_size: u64,
+            ) -> Result<()>;
         }
     }
 
@@ -277,12 +512,156 @@ mod tests {
             .times(1)
             .returning(|_| Ok(false));
 
-        let command = SelfUpdateCommand;
+        let command = SelfUpdateCommand::default();
         let result = command.execute(&mock_provider, &logger).await;
 
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_download_exceeds_size_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("big.archive");
+        let mut mock_provider = MockReleaseProvider::new();
+
+        mock_provider
+            .expect_download_release()
+            .withf(|_, _, max_size| *max_size == 32)
+            .times(1)
+            .returning(|_, dest, _| {
+                fs::write(dest, vec![0u8; 64]).unwrap();
+                Ok(())
+            });
+
+        let command = SelfUpdateCommand {
+            max_download_size: 32,
+            ..SelfUpdateCommand::default()
+        };
+        let release = ReleaseInfo {
+            version: "v9.9.9".to_string(),
+            download_url: "https://example.com/aiassisted.tar.gz".to_string(),
+            checksum: None,
+            notes: None,
+            notes_url: None,
+        };
+
+        let result = command
+            .download(&mock_provider, &release, &archive_path)
+            .await;
+
+        match result {
+            Err(Error::Network(msg)) => assert!(msg.contains("exceeding the limit")),
+            other => panic!("Expected Network error, got {:?}", other),
+        }
+        assert!(!archive_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_execute_downloads_into_custom_temp_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let custom = temp_dir.path().join("update-tmp");
+        let mut mock_provider = MockReleaseProvider::new();
+        let downloaded_to = std::sync::Arc::new(std::sync::Mutex::new(None));
+
+        mock_provider
+            .expect_is_update_available()
+            .returning(|_| Ok(true));
+        mock_provider.expect_get_latest().returning(|| {
+            Ok(ReleaseInfo {
+                version: "v9.9.9".to_string(),
+                download_url: "https://example.com/aiassisted.tar.gz".to_string(),
+                checksum: None,
+                notes: None,
+                notes_url: None,
+            })
+        });
+        let recorded = downloaded_to.clone();
+        mock_provider
+            .expect_download_release()
+            .times(1)
+            .returning(move |_, dest, _| {
+                *recorded.lock().unwrap() = Some(dest.to_path_buf());
+                // Not a valid archive, so the update stops before touching the binary
+                fs::write(dest, b"not an archive").unwrap();
+                Ok(())
+            });
+
+        let command = SelfUpdateCommand {
+            temp_dir: Some(custom.clone()),
+            ..SelfUpdateCommand::default()
+        };
+        assert!(command.execute(&mock_provider, &TestLogger).await.is_err());
+
+        // In a private subdirectory of the custom one
+        let archive = downloaded_to.lock().unwrap().clone().unwrap();
+        let private = archive.parent().unwrap();
+        assert_eq!(private.parent(), Some(custom.as_path()));
+        // The private directory, download and probe are cleaned up
+        assert_eq!(fs::read_dir(&custom).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_prepare_temp_dir_rejects_unusable_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("not-a-dir");
+        fs::write(&file, "").unwrap();
+
+        let command = SelfUpdateCommand {
+            temp_dir: Some(file.join("tmp")),
+            ..SelfUpdateCommand::default()
+        };
+        match command.prepare_temp_dir() {
+            Err(Error::Config(msg)) => assert!(msg.contains("--temp-dir"), "{}", msg),
+            other => panic!("Expected Config error, got {:?}", other),
+        }
+
+        let created = temp_dir.path().join("created");
+        let command = SelfUpdateCommand {
+            temp_dir: Some(created.clone()),
+            ..SelfUpdateCommand::default()
+        };
+        let first = command.prepare_temp_dir().unwrap();
+        let second = command.prepare_temp_dir().unwrap();
+        assert_eq!(first.path().parent(), Some(created.as_path()));
+        assert_ne!(first.path(), second.path());
+        // Only the private directories are left; the probes are gone
+        assert_eq!(fs::read_dir(first.path()).unwrap().count(), 0);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(first.path()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+    }
+
+    #[test]
+    fn test_notes_preview() {
+        let mut release = ReleaseInfo {
+            version: "v9.9.9".to_string(),
+            download_url: "https://example.com/aiassisted.tar.gz".to_string(),
+            checksum: None,
+            notes: Some("\n## What's new\n- Faster installs\n\n".to_string()),
+            notes_url: Some("https://example.com/releases/v9.9.9".to_string()),
+        };
+        assert_eq!(
+            notes_preview(&release).unwrap(),
+            vec!["## What's new", "- Faster installs"]
+        );
+
+        let long: Vec<String> = (1..=20).map(|i| format!("- change {}", i)).collect();
+        release.notes = Some(long.join("\n"));
+        let preview = notes_preview(&release).unwrap();
+        assert_eq!(preview.len(), NOTES_PREVIEW_LINES + 1);
+        assert_eq!(preview[0], "- change 1");
+        assert_eq!(
+            preview.last().unwrap(),
+            "... see full notes at https://example.com/releases/v9.9.9"
+        );
+
+        release.notes = None;
+        assert!(notes_preview(&release).is_none());
+    }
+
     #[tokio::test]
     async fn test_extract_tar_gz_creates_binary() {
         let temp_dir = TempDir::new().unwrap();
@@ -304,11 +683,12 @@ mod tests {
         encoder.finish().unwrap();
 
         // Extract should succeed
-        let result = SelfUpdateCommand::extract_tar_gz(&archive_path);
+        let result = SelfUpdateCommand::extract_tar_gz(&archive_path, temp_dir.path(), DEFAULT_MAX_BINARY_SIZE);
         assert!(result.is_ok());
 
         let extracted = result.unwrap();
         assert!(extracted.exists());
+        assert!(extracted.starts_with(temp_dir.path().canonicalize().unwrap()));
         assert_eq!(fs::read_to_string(&extracted).unwrap(), "test");
     }
 
@@ -331,7 +711,7 @@ mod tests {
         let encoder = tar.into_inner().unwrap();
         encoder.finish().unwrap();
 
-        let result = SelfUpdateCommand::extract_tar_gz(&archive_path);
+        let result = SelfUpdateCommand::extract_tar_gz(&archive_path, temp_dir.path(), DEFAULT_MAX_BINARY_SIZE);
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), Error::NotFound(_)));
     }
@@ -353,11 +733,12 @@ mod tests {
         zip.finish().unwrap();
 
         // Extract should succeed
-        let result = SelfUpdateCommand::extract_zip(&archive_path);
+        let result = SelfUpdateCommand::extract_zip(&archive_path, temp_dir.path(), DEFAULT_MAX_BINARY_SIZE);
         assert!(result.is_ok());
 
         let extracted = result.unwrap();
         assert!(extracted.exists());
+        assert!(extracted.starts_with(temp_dir.path().canonicalize().unwrap()));
         assert_eq!(fs::read_to_string(&extracted).unwrap(), "test");
     }
 
@@ -377,11 +758,174 @@ mod tests {
         zip.write_all(b"test").unwrap();
         zip.finish().unwrap();
 
-        let result = SelfUpdateCommand::extract_zip(&archive_path);
+        let result = SelfUpdateCommand::extract_zip(&archive_path, temp_dir.path(), DEFAULT_MAX_BINARY_SIZE);
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), Error::NotFound(_)));
     }
 
+    #[tokio::test]
+    async fn test_extract_tar_gz_rejects_path_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("test.tar.gz");
+
+        let file = fs::File::create(&archive_path).unwrap();
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut tar = tar::Builder::new(encoder);
+
+        // set_path refuses "..", so write the raw name bytes directly
+        let mut header = tar::Header::new_gnu();
+        let name = b"../aiassisted";
+        header.as_gnu_mut().unwrap().name[..name.len()].copy_from_slice(name);
+        header.set_size(4);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tar.append(&header, b"test" as &[u8]).unwrap();
+        let encoder = tar.into_inner().unwrap();
+        encoder.finish().unwrap();
+
+        let result = SelfUpdateCommand::extract_tar_gz(&archive_path, temp_dir.path(), DEFAULT_MAX_BINARY_SIZE);
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_gz_rejects_oversized_binary() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("test.tar.gz");
+
+        let file = fs::File::create(&archive_path).unwrap();
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut tar = tar::Builder::new(encoder);
+
+        let mut header = tar::Header::new_gnu();
+        header.set_path("aiassisted").unwrap();
+        header.set_size(4);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tar.append(&header, b"test" as &[u8]).unwrap();
+        let encoder = tar.into_inner().unwrap();
+        encoder.finish().unwrap();
+
+        let result = SelfUpdateCommand::extract_tar_gz(&archive_path, temp_dir.path(), 2);
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip_rejects_path_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("test.zip");
+
+        let file = fs::File::create(&archive_path).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+
+        zip.start_file("../../aiassisted.exe", options).unwrap();
+        zip.write_all(b"test").unwrap();
+        zip.finish().unwrap();
+
+        let result = SelfUpdateCommand::extract_zip(&archive_path, temp_dir.path(), DEFAULT_MAX_BINARY_SIZE);
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip_rejects_oversized_binary() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("test.zip");
+
+        let file = fs::File::create(&archive_path).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+
+        zip.start_file("aiassisted.exe", options).unwrap();
+        zip.write_all(b"test").unwrap();
+        zip.finish().unwrap();
+
+        let result = SelfUpdateCommand::extract_zip(&archive_path, temp_dir.path(), 2);
+        assert!(matches!(result, Err(Error::Parse(_))));
+    }
+
+    #[tokio::test]
+    async fn test_extract_tar_gz_malicious_archive_names_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("evil.tar.gz");
+
+        let file = fs::File::create(&archive_path).unwrap();
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut tar = tar::Builder::new(encoder);
+
+        // A harmless file followed by one that tries to climb out of temp
+        let mut header = tar::Header::new_gnu();
+        header.set_path("README.md").unwrap();
+        header.set_size(4);
+        header.set_cksum();
+        tar.append(&header, b"test" as &[u8]).unwrap();
+
+        let mut header = tar::Header::new_gnu();
+        let name = b"../../etc/aiassisted";
+        header.as_gnu_mut().unwrap().name[..name.len()].copy_from_slice(name);
+        header.set_size(4);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tar.append(&header, b"evil" as &[u8]).unwrap();
+        let encoder = tar.into_inner().unwrap();
+        encoder.finish().unwrap();
+
+        match SelfUpdateCommand::extract_tar_gz(&archive_path, temp_dir.path(), DEFAULT_MAX_BINARY_SIZE) {
+            Err(Error::Parse(msg)) => assert!(msg.contains("../../etc/aiassisted")),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_contained_destination_inside_root() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let dest = SelfUpdateCommand::contained_destination(
+            temp_dir.path(),
+            Path::new("aiassisted"),
+            "aiassisted",
+        )
+        .unwrap();
+
+        assert_eq!(
+            dest,
+            temp_dir.path().canonicalize().unwrap().join("aiassisted")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_contained_destination_rejects_symlink_escape() {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+
+        // A pre-existing symlink in the extraction root pointing elsewhere
+        std::os::unix::fs::symlink(
+            outside.path().join("target"),
+            root.path().join("aiassisted"),
+        )
+        .unwrap();
+        fs::write(outside.path().join("target"), "original").unwrap();
+
+        let result = SelfUpdateCommand::contained_destination(
+            root.path(),
+            Path::new("aiassisted"),
+            "aiassisted",
+        );
+
+        match result {
+            Err(Error::Parse(msg)) => assert!(msg.contains("aiassisted")),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+        assert_eq!(
+            fs::read_to_string(outside.path().join("target")).unwrap(),
+            "original"
+        );
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn test_make_executable() {
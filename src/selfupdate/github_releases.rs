@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use std::path::Path;
 
-use crate::core::infra::HttpClient;
+use crate::core::infra::{DownloadOptions, HttpClient};
 use crate::core::selfupdate::ReleaseProvider;
 use crate::core::types::{Error, ReleaseInfo, Result};
 
@@ -19,6 +19,11 @@ const GITHUB_API_BASE: &str = "https://api.github.com";
 struct GitHubRelease {
     tag_name: String,
     assets: Vec<GitHubAsset>,
+    /// Markdown release notes; null or absent when none were written.
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    html_url: Option<String>,
 }
 
 /// GitHub Release Asset.
@@ -86,6 +91,8 @@ impl<H: HttpClient> ReleaseProvider for GithubReleasesProvider<H> {
             version: release.tag_name.clone(),
             download_url: asset.browser_download_url.clone(),
             checksum: None, // GitHub doesn't provide checksums in the API response
+            notes: release.body.clone().filter(|body| !body.trim().is_empty()),
+            notes_url: release.html_url.clone(),
         })
     }
 
@@ -94,11 +101,22 @@ impl<H: HttpClient> ReleaseProvider for GithubReleasesProvider<H> {
         Ok(version::is_newer_version(current_version, &latest.version))
     }
 
-    async fn download_release(&self, release: &ReleaseInfo, dest: &Path) -> Result<()> {
+    async fn download_release(
+        &self,
+        release: &ReleaseInfo,
+        dest: &Path,
+        max_size: u64,
+    ) -> Result<()> {
+        // Release archives have no checksum to verify a resumed download against
+        let options = DownloadOptions {
+            max_size: Some(max_size),
+            resume: false,
+        };
         self.http
-            .download(&release.download_url, dest)
+            .download_with(&release.download_url, dest, options)
             .await
-            .map_err(|e| Error::Network(format!("Failed to download release: {}", e)))
+            .map_err(|e| Error::Network(format!("Failed to download release: {}", e)))?;
+        Ok(())
     }
 }
 
@@ -118,6 +136,12 @@ mod tests {
             async fn get(&self, url: &str) -> Result<String>;
             async fn get_bytes(&self, url: &str) -> Result<Vec<u8>>;
             async fn download(&self, url: &str, dest: &Path) -> Result<()>;
+            async fn download_with(
+                &self,
+                url: &str,
+                dest: &Path,
+                options: DownloadOptions,
+            ) -> Result<u64>;
         }
     }
 
@@ -154,6 +178,47 @@ mod tests {
         assert_eq!(result.version, "v1.2.3");
         assert!(result.download_url.contains("v1.2.3"));
         assert!(result.checksum.is_none());
+        assert!(result.notes.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_with_release_notes() {
+        let mut mock_http = MockHttpClient::new();
+
+        let response = r###"{
+            "tag_name": "v1.3.0",
+            "html_url": "https://github.com/rstlix0x0/aiassisted/releases/tag/v1.3.0",
+            "body": "## Changes\r\n- Add --verify to skills update\r\n",
+            "assets": [
+                {
+                    "name": "aiassisted-x86_64-unknown-linux-gnu.tar.gz",
+                    "browser_download_url": "https://example.com/aiassisted.tar.gz"
+                }
+            ]
+        }"###;
+
+        mock_http
+            .expect_get()
+            .returning(move |_| Ok(response.to_string()));
+
+        let provider = GithubReleasesProvider {
+            http: mock_http,
+            platform: Platform {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+            },
+        };
+
+        let result = provider.get_latest().await.unwrap();
+
+        assert_eq!(
+            result.notes.as_deref(),
+            Some("## Changes\r\n- Add --verify to skills update\r\n")
+        );
+        assert_eq!(
+            result.notes_url.as_deref(),
+            Some("https://github.com/rstlix0x0/aiassisted/releases/tag/v1.3.0")
+        );
     }
 
     #[tokio::test]
@@ -336,10 +401,12 @@ mod tests {
         let mut mock_http = MockHttpClient::new();
 
         mock_http
-            .expect_download()
-            .withf(|url, _| url.contains("github.com"))
+            .expect_download_with()
+            .withf(|url, _, options| {
+                url.contains("github.com") && options.max_size == Some(1024) && !options.resume
+            })
             .times(1)
-            .returning(|_, _| Ok(()));
+            .returning(|_, _, _| Ok(1024));
 
         let provider = GithubReleasesProvider {
             http: mock_http,
@@ -354,10 +421,12 @@ mod tests {
             download_url: "https://github.com/example/repo/releases/download/v1.2.3/binary.tar.gz"
                 .to_string(),
             checksum: None,
+            notes: None,
+            notes_url: None,
         };
 
         let dest = PathBuf::from("/tmp/binary.tar.gz");
-        let result = provider.download_release(&release, &dest).await;
+        let result = provider.download_release(&release, &dest, 1024).await;
 
         assert!(result.is_ok());
     }
@@ -367,9 +436,9 @@ mod tests {
         let mut mock_http = MockHttpClient::new();
 
         mock_http
-            .expect_download()
+            .expect_download_with()
             .times(1)
-            .returning(|_, _| Err(Error::Network("Download failed".to_string())));
+            .returning(|_, _, _| Err(Error::Network("Download failed".to_string())));
 
         let provider = GithubReleasesProvider {
             http: mock_http,
@@ -384,10 +453,12 @@ mod tests {
             download_url: "https://github.com/example/repo/releases/download/v1.2.3/binary.tar.gz"
                 .to_string(),
             checksum: None,
+            notes: None,
+            notes_url: None,
         };
 
         let dest = PathBuf::from("/tmp/binary.tar.gz");
-        let result = provider.download_release(&release, &dest).await;
+        let result = provider.download_release(&release, &dest, 1024).await;
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), Error::Network(_)));
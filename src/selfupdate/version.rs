@@ -1,4 +1,31 @@
-//! Semantic version comparison for self-update checks.
+//! Semantic version comparison for self-update checks, and the
+//! `aiassisted version` report.
+
+use super::platform::Platform;
+
+/// Output of `aiassisted version`.
+///
+/// Plain output is just `aiassisted <version>`, which scripts parse. With
+/// `verbose` (`version -v`) it adds the build metadata captured by `build.rs`
+/// and the platform detected at runtime, for bug reports.
+pub fn version_report(verbose: bool) -> String {
+    let mut report = format!("aiassisted {}", env!("CARGO_PKG_VERSION"));
+    if !verbose {
+        return report;
+    }
+
+    let platform = Platform::detect();
+    for (label, value) in [
+        ("commit", env!("AIASSISTED_GIT_SHA").to_string()),
+        ("built", env!("AIASSISTED_BUILD_DATE").to_string()),
+        ("rustc", env!("AIASSISTED_RUSTC_VERSION").to_string()),
+        ("target", env!("AIASSISTED_TARGET").to_string()),
+        ("platform", format!("{}/{}", platform.os, platform.arch)),
+    ] {
+        report.push_str(&format!("\n{:<9} {}", format!("{}:", label), value));
+    }
+    report
+}
 
 /// Parse a version string and compare it with another version.
 ///
@@ -36,6 +63,19 @@ fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_version_report() {
+        let plain = version_report(false);
+        assert_eq!(plain, format!("aiassisted {}", env!("CARGO_PKG_VERSION")));
+
+        let verbose = version_report(true);
+        assert!(verbose.starts_with(&plain));
+        assert!(verbose.contains(env!("CARGO_PKG_VERSION")));
+        for label in ["commit:", "built:", "rustc:", "target:", "platform:"] {
+            assert!(verbose.contains(label), "{}", label);
+        }
+    }
+
     #[test]
     fn test_parse_version_with_v_prefix() {
         assert_eq!(parse_version("v1.2.3"), Some((1, 2, 3)));
@@ -1,17 +1,38 @@
 //! Skills domain commands
 
+use crate::core::archive::{pack_tar_gz, unpack_tar_gz};
+use crate::core::confirm::confirm;
 use crate::core::infra::{Checksum, FileSystem, Logger};
-use crate::core::types::{Result, ToolType};
+use crate::core::project::ensure_removable;
+use crate::core::types::{Error, Result, ToolDirs, ToolType};
 use crate::skills::copier::SkillCopier;
 use crate::skills::diff::{FileStatus, SkillDiffer, SkillStatus};
-use crate::skills::discovery::ToolDetector;
-use std::path::Path;
+use crate::skills::discovery::{SkillMetadata, ToolDetector};
+use crate::skills::selection::SkillSelection;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
 
 /// Command to set up skills by copying from .aiassisted/skills/
 pub struct SetupSkillsCommand {
-    pub tool: ToolType,
+    /// Tools to set up, in order (`Auto` is resolved per project)
+    pub tools: Vec<ToolType>,
     pub dry_run: bool,
     pub force: bool,
+    /// Per-tool output directory overrides from config
+    pub output_dirs: BTreeMap<String, ToolDirs>,
+    /// Tool to use when auto-detection finds nothing (from config)
+    pub default_tool: ToolType,
+    /// Ignore the cached detection result (`--refresh-detection`)
+    pub refresh_detection: bool,
+    /// Follow symlinks inside skills, skipping loops (`--follow-symlinks`)
+    pub follow_symlinks: bool,
+}
+
+/// Per-tool outcome of a skills setup run
+#[derive(Debug, Clone, Copy, Default)]
+struct SetupCounts {
+    copied: usize,
+    skipped: usize,
 }
 
 impl SetupSkillsCommand {
@@ -21,17 +42,55 @@ impl SetupSkillsCommand {
         logger: &L,
         project_path: &Path,
     ) -> Result<()> {
-        let detector = ToolDetector::new(fs, project_path);
-        let copier = SkillCopier::new(fs);
+        let detector = ToolDetector::new(fs, project_path)
+            .with_output_dirs(self.output_dirs.clone())
+            .with_default_tool(self.default_tool)
+            .with_detection_cache(self.refresh_detection);
+        let selection = SkillSelection::load(fs, project_path).await?;
 
-        // Resolve tool type
-        let tool = if self.tool == ToolType::Auto {
-            let detected = detector.detect();
-            logger.info(&format!("Auto-detected tool: {}", detected));
-            detected
-        } else {
-            self.tool
-        };
+        let mut results = Vec::new();
+        for (index, requested) in self.tools.iter().enumerate() {
+            logger.checkpoint()?;
+            if index > 0 {
+                logger.info("");
+            }
+
+            // Resolve tool type
+            let tool = detector.resolve_cached(*requested).await?;
+            if *requested == ToolType::Auto {
+                logger.info(&format!("Auto-detected tool: {}", tool));
+            }
+
+            let counts = self.setup_tool(fs, logger, &detector, &selection, tool).await?;
+            results.push((tool, counts));
+        }
+
+        // Aggregated summary across tools
+        if results.len() > 1 {
+            logger.info("");
+            logger.info("Summary by tool:");
+            for (tool, counts) in &results {
+                let verb = if self.dry_run { "would be copied" } else { "copied" };
+                logger.info(&format!(
+                    "  {}: {} {}, {} skipped",
+                    tool, counts.copied, verb, counts.skipped
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy skills into a single tool's skills directory
+    async fn setup_tool<F: FileSystem, L: Logger>(
+        &self,
+        fs: &F,
+        logger: &L,
+        detector: &ToolDetector<'_, F>,
+        selection: &SkillSelection,
+        tool: ToolType,
+    ) -> Result<SetupCounts> {
+        let copier = SkillCopier::new(fs).with_follow_symlinks(self.follow_symlinks);
 
         logger.info(&format!("Setting up skills for {}", tool));
 
@@ -40,15 +99,16 @@ impl SetupSkillsCommand {
         let target_dir = detector.skills_dir(tool);
 
         // Discover available skills
-        let skills = copier.discover_skills(&source_dir).await?;
+        let mut skills = copier.discover_skills(&source_dir).await?;
 
         if skills.is_empty() {
             logger.warn("No skills found in .aiassisted/skills/");
             logger.info("Run 'aiassisted install' to install skills first");
-            return Ok(());
+            return Ok(SetupCounts::default());
         }
 
         logger.info(&format!("Found {} skill(s)", skills.len()));
+        retain_enabled(&mut skills, selection, |skill| &skill.name, logger);
 
         // Create target directory if needed
         if !self.dry_run {
@@ -60,6 +120,7 @@ impl SetupSkillsCommand {
         let mut skipped = 0;
 
         for skill in &skills {
+            logger.checkpoint()?;
             if self.dry_run {
                 logger.info(&format!(
                     "Would copy: {} -> {}/{}",
@@ -100,13 +161,232 @@ impl SetupSkillsCommand {
             }
         }
 
+        Ok(SetupCounts { copied, skipped })
+    }
+}
+
+/// Drop skills disabled in `.aiassisted/skills.toml`, logging how many
+fn retain_enabled<T, L: Logger>(
+    skills: &mut Vec<T>,
+    selection: &SkillSelection,
+    name: impl Fn(&T) -> &String,
+    logger: &L,
+) {
+    let before = skills.len();
+    skills.retain(|skill| selection.is_enabled(name(skill)));
+    let disabled = before - skills.len();
+    if disabled > 0 {
+        logger.info(&format!(
+            "Skipping {} disabled skill(s) (see {})",
+            disabled,
+            crate::skills::selection::SELECTION_FILE
+        ));
+    }
+}
+
+/// Command to enable a skill in `.aiassisted/skills.toml`
+pub struct SkillsEnableCommand {
+    pub name: String,
+}
+
+impl SkillsEnableCommand {
+    pub async fn execute<F: FileSystem, L: Logger>(
+        &self,
+        fs: &F,
+        logger: &L,
+        project_path: &Path,
+    ) -> Result<()> {
+        set_skill_enabled(fs, logger, project_path, &self.name, true).await
+    }
+}
+
+/// Command to disable a skill in `.aiassisted/skills.toml`
+pub struct SkillsDisableCommand {
+    pub name: String,
+}
+
+impl SkillsDisableCommand {
+    pub async fn execute<F: FileSystem, L: Logger>(
+        &self,
+        fs: &F,
+        logger: &L,
+        project_path: &Path,
+    ) -> Result<()> {
+        set_skill_enabled(fs, logger, project_path, &self.name, false).await
+    }
+}
+
+/// Shared body of `skills enable` and `skills disable`
+async fn set_skill_enabled<F: FileSystem, L: Logger>(
+    fs: &F,
+    logger: &L,
+    project_path: &Path,
+    name: &str,
+    enable: bool,
+) -> Result<()> {
+    let source_dir = ToolDetector::new(fs, project_path).skills_source_dir();
+    if !fs.exists(&source_dir.join(name).join("SKILL.md")) {
+        return Err(Error::NotFound(format!(
+            "Skill '{}' not found in .aiassisted/skills/",
+            name
+        )));
+    }
+
+    let mut selection = SkillSelection::load(fs, project_path).await?;
+    let state = if enable { "enabled" } else { "disabled" };
+    let changed = if enable {
+        selection.enable(name)
+    } else {
+        selection.disable(name)
+    };
+
+    if !changed {
+        logger.info(&format!("Skill '{}' is already {}", name, state));
+        return Ok(());
+    }
+
+    selection.save(fs, project_path).await?;
+    logger.success(&format!("Skill '{}' {}", name, state));
+    Ok(())
+}
+
+/// Largest skill archive `skills import` will unpack (16 MB)
+pub const MAX_SKILL_ARCHIVE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Command to bundle one skill from `.aiassisted/skills/` into a tar.gz
+pub struct SkillsExportCommand {
+    pub name: String,
+    pub archive: PathBuf,
+}
+
+impl SkillsExportCommand {
+    pub async fn execute<F: FileSystem, L: Logger>(
+        &self,
+        fs: &F,
+        logger: &L,
+        project_path: &Path,
+    ) -> Result<()> {
+        let skill_dir = ToolDetector::new(fs, project_path)
+            .skills_source_dir()
+            .join(&self.name);
+        if !fs.exists(&skill_dir.join("SKILL.md")) {
+            return Err(Error::NotFound(format!(
+                "Skill '{}' not found in .aiassisted/skills/",
+                self.name
+            )));
+        }
+
+        let mut paths = fs.walk_dir(&skill_dir).await?;
+        paths.sort();
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let Ok(relative) = path.strip_prefix(&skill_dir) else {
+                continue;
+            };
+            let contents = fs.read_bytes(&path).await?;
+            files.push((Path::new(&self.name).join(relative), contents));
+        }
+
+        fs.write_bytes(&self.archive, &pack_tar_gz(&files)?).await?;
+        logger.success(&format!(
+            "Exported skill '{}' ({} file(s)) to {}",
+            self.name,
+            files.len(),
+            self.archive.display()
+        ));
+        Ok(())
+    }
+}
+
+/// Command to unpack a `skills export` archive into `.aiassisted/skills/`
+pub struct SkillsImportCommand {
+    pub archive: PathBuf,
+    /// Replace a skill of the same name
+    pub force: bool,
+    /// Replace it without asking (`--yes`)
+    pub yes: bool,
+}
+
+impl SkillsImportCommand {
+    pub async fn execute<F: FileSystem, L: Logger>(
+        &self,
+        fs: &F,
+        logger: &L,
+        project_path: &Path,
+    ) -> Result<()> {
+        let bytes = fs.read_bytes(&self.archive).await?;
+        let files = unpack_tar_gz(&bytes, MAX_SKILL_ARCHIVE_SIZE)?;
+        let name = archived_skill_name(&files, &self.archive)?;
+
+        let source_dir = ToolDetector::new(fs, project_path).skills_source_dir();
+        let skill_dir = source_dir.join(&name);
+        if fs.exists(&skill_dir) {
+            if !self.force {
+                return Err(Error::Config(format!(
+                    "Skill '{}' already exists in .aiassisted/skills/; pass --force to replace it",
+                    name
+                )));
+            }
+            let question = format!("Replace the existing skill '{}'?", name);
+            if !confirm(&question, self.yes)? {
+                logger.info("Import cancelled");
+                return Ok(());
+            }
+            // Replace rather than merge, so files dropped from the skill go too
+            ensure_removable(&skill_dir, project_path)?;
+            fs.remove_dir_all(&skill_dir).await?;
+        }
+
+        for (path, contents) in &files {
+            let dest = source_dir.join(path);
+            if let Some(parent) = dest.parent() {
+                fs.create_dir_all(parent).await?;
+            }
+            fs.write_bytes(&dest, contents).await?;
+        }
+
+        logger.success(&format!(
+            "Imported skill '{}' ({} file(s)) into .aiassisted/skills/",
+            name,
+            files.len()
+        ));
         Ok(())
     }
 }
 
+/// Name of the one skill directory an archive holds, which must contain a SKILL.md
+fn archived_skill_name(files: &[(PathBuf, Vec<u8>)], archive: &Path) -> Result<String> {
+    let invalid = |reason: &str| Error::Parse(format!("{}: {}", archive.display(), reason));
+
+    let mut names = files.iter().map(|(path, _)| match path.components().next() {
+        Some(Component::Normal(name)) if path.components().count() > 1 => {
+            Some(name.to_string_lossy().into_owned())
+        }
+        _ => None,
+    });
+    let Some(Some(name)) = names.next() else {
+        return Err(invalid("expected a single skill directory"));
+    };
+    if !names.all(|other| other.as_deref() == Some(name.as_str())) {
+        return Err(invalid("expected a single skill directory"));
+    }
+
+    let skill_md = Path::new(&name).join("SKILL.md");
+    if !files.iter().any(|(path, _)| *path == skill_md) {
+        return Err(invalid(&format!("no {} in archive", skill_md.display())));
+    }
+    Ok(name)
+}
+
 /// Command to list available skills
 pub struct SkillsListCommand {
     pub tool: ToolType,
+    /// Per-tool output directory overrides from config
+    pub output_dirs: BTreeMap<String, ToolDirs>,
+    /// Tool to use when auto-detection finds nothing (from config)
+    pub default_tool: ToolType,
+    /// Ignore the cached detection result (`--refresh-detection`)
+    pub refresh_detection: bool,
 }
 
 impl SkillsListCommand {
@@ -116,15 +396,14 @@ impl SkillsListCommand {
         logger: &L,
         project_path: &Path,
     ) -> Result<()> {
-        let detector = ToolDetector::new(fs, project_path);
+        let detector = ToolDetector::new(fs, project_path)
+            .with_output_dirs(self.output_dirs.clone())
+            .with_default_tool(self.default_tool)
+            .with_detection_cache(self.refresh_detection);
         let copier = SkillCopier::new(fs);
 
         // Resolve tool type
-        let tool = if self.tool == ToolType::Auto {
-            detector.detect()
-        } else {
-            self.tool
-        };
+        let tool = detector.resolve_cached(self.tool).await?;
 
         let source_dir = detector.skills_source_dir();
         let target_dir = detector.skills_dir(tool);
@@ -144,8 +423,21 @@ impl SkillsListCommand {
                     logger.info("");
                     for skill in &skills {
                         let installed = fs.exists(&target_dir.join(&skill.name));
-                        let status = if installed { "[installed]" } else { "" };
-                        logger.info(&format!("  - {} {}", skill.name, status));
+                        let status = if installed { " [installed]" } else { "" };
+
+                        // Read SKILL.md frontmatter for name and description
+                        let metadata = match fs.read(&skill.source_path.join("SKILL.md")).await {
+                            Ok(content) => SkillMetadata::parse(&content, &skill.name),
+                            Err(_) => SkillMetadata::parse("", &skill.name),
+                        };
+
+                        match metadata.description {
+                            Some(description) => logger.info(&format!(
+                                "  - {} - {}{}",
+                                metadata.name, description, status
+                            )),
+                            None => logger.info(&format!("  - {}{}", metadata.name, status)),
+                        }
                     }
                 }
             }
@@ -164,6 +456,16 @@ pub struct SkillsUpdateCommand {
     pub tool: ToolType,
     pub dry_run: bool,
     pub force: bool,
+    /// Re-hash written files afterwards and fail if any differ from the source
+    pub verify: bool,
+    /// Per-tool output directory overrides from config
+    pub output_dirs: BTreeMap<String, ToolDirs>,
+    /// Tool to use when auto-detection finds nothing (from config)
+    pub default_tool: ToolType,
+    /// Ignore the cached detection result (`--refresh-detection`)
+    pub refresh_detection: bool,
+    /// Follow symlinks inside skills, skipping loops (`--follow-symlinks`)
+    pub follow_symlinks: bool,
 }
 
 impl SkillsUpdateCommand {
@@ -174,17 +476,17 @@ impl SkillsUpdateCommand {
         logger: &L,
         project_path: &Path,
     ) -> Result<()> {
-        let detector = ToolDetector::new(fs, project_path);
-        let differ = SkillDiffer::new(fs, checksum);
+        let detector = ToolDetector::new(fs, project_path)
+            .with_output_dirs(self.output_dirs.clone())
+            .with_default_tool(self.default_tool)
+            .with_detection_cache(self.refresh_detection);
+        let differ = SkillDiffer::new(fs, checksum).with_follow_symlinks(self.follow_symlinks);
 
         // Resolve tool type
-        let tool = if self.tool == ToolType::Auto {
-            let detected = detector.detect();
-            logger.info(&format!("Auto-detected tool: {}", detected));
-            detected
-        } else {
-            self.tool
-        };
+        let tool = detector.resolve_cached(self.tool).await?;
+        if self.tool == ToolType::Auto {
+            logger.info(&format!("Auto-detected tool: {}", tool));
+        }
 
         // Get source and target directories
         let source_dir = detector.skills_source_dir();
@@ -210,7 +512,9 @@ impl SkillsUpdateCommand {
         logger.info("Analyzing skills...");
 
         // Compute diff
-        let diff = differ.compute_diff(&source_dir, &target_dir).await?;
+        let mut diff = differ.compute_diff(&source_dir, &target_dir).await?;
+        let selection = SkillSelection::load(fs, project_path).await?;
+        retain_enabled(&mut diff.skills, &selection, |skill| &skill.name, logger);
 
         // Summary
         logger.info(&format!(
@@ -291,8 +595,10 @@ impl SkillsUpdateCommand {
         } else {
             logger.info("");
 
-            let mut updated = 0;
+            // Only the files listed above are written; unchanged files keep their mtimes
             for file in &files_to_update {
+                logger.checkpoint()?;
+
                 // Ensure parent directory exists
                 if let Some(parent) = file.target_path.parent() {
                     fs.create_dir_all(parent).await?;
@@ -300,18 +606,58 @@ impl SkillsUpdateCommand {
 
                 // Copy file
                 fs.copy(&file.source_path, &file.target_path).await?;
-                updated += 1;
             }
 
+            let count = |status| files_to_update.iter().filter(|f| f.status == status).count();
+            let (new, modified) = (count(FileStatus::New), count(FileStatus::Modified));
+            let rewritten = files_to_update.len() - new - modified;
             logger.success(&format!(
-                "Updated {} file(s) across {} skill(s)",
-                updated,
+                "Updated {} file(s) across {} skill(s): {} new, {} modified{}",
+                files_to_update.len(),
                 diff.skills
                     .iter()
                     .filter(|s| matches!(s.status, SkillStatus::New | SkillStatus::Updated))
-                    .count()
+                    .count(),
+                new,
+                modified,
+                if rewritten > 0 {
+                    format!(", {} unchanged rewritten (forced)", rewritten)
+                } else {
+                    String::new()
+                }
             ));
 
+            let untouched: usize = diff.skills.iter().map(|s| s.unchanged_count()).sum();
+            if !self.force && untouched > 0 {
+                logger.info(&format!("Left {} unchanged file(s) untouched", untouched));
+            }
+
+            if self.verify {
+                let mut mismatches = Vec::new();
+                for file in &files_to_update {
+                    let result = differ.verify_file(&file.source_path, &file.target_path)?;
+                    if let Some(mismatch) = result {
+                        logger.error(&format!(
+                            "  ! {} (expected {}, actual {})",
+                            file.target_path.display(),
+                            mismatch.0,
+                            mismatch.1
+                        ));
+                        mismatches.push(mismatch);
+                    }
+                }
+
+                if let Some((expected, actual)) = mismatches.first().cloned() {
+                    logger.error(&format!(
+                        "{} of {} file(s) failed verification after update",
+                        mismatches.len(),
+                        files_to_update.len()
+                    ));
+                    return Err(Error::ChecksumMismatch { expected, actual });
+                }
+                logger.success(&format!("Verified {} file(s)", files_to_update.len()));
+            }
+
             // Note about removed skills
             if diff.removed_skills_count() > 0 {
                 logger.info(&format!(
@@ -324,3 +670,597 @@ impl SkillsUpdateCommand {
         Ok(())
     }
 }
+
+/// Command to preview differences between source and installed skills
+pub struct SkillsDiffCommand {
+    pub tool: ToolType,
+    pub detailed: bool,
+    /// Per-tool output directory overrides from config
+    pub output_dirs: BTreeMap<String, ToolDirs>,
+    /// Tool to use when auto-detection finds nothing (from config)
+    pub default_tool: ToolType,
+    /// Ignore the cached detection result (`--refresh-detection`)
+    pub refresh_detection: bool,
+    /// Follow symlinks inside skills, skipping loops (`--follow-symlinks`)
+    pub follow_symlinks: bool,
+}
+
+impl SkillsDiffCommand {
+    pub async fn execute<F: FileSystem, C: Checksum, L: Logger>(
+        &self,
+        fs: &F,
+        checksum: &C,
+        logger: &L,
+        project_path: &Path,
+    ) -> Result<()> {
+        let detector = ToolDetector::new(fs, project_path)
+            .with_output_dirs(self.output_dirs.clone())
+            .with_default_tool(self.default_tool)
+            .with_detection_cache(self.refresh_detection);
+        let differ = SkillDiffer::new(fs, checksum).with_follow_symlinks(self.follow_symlinks);
+
+        // Resolve tool type
+        let tool = detector.resolve_cached(self.tool).await?;
+
+        let source_dir = detector.skills_source_dir();
+        let target_dir = detector.skills_dir(tool);
+
+        logger.info(&format!("Source: {}", source_dir.display()));
+        logger.info(&format!("Target: {}", target_dir.display()));
+
+        // Check source exists
+        if !fs.exists(&source_dir) {
+            logger.warn("No skills found in .aiassisted/skills/");
+            logger.info("Run 'aiassisted install' to install skills first");
+            return Ok(());
+        }
+
+        let diff = differ.compute_diff(&source_dir, &target_dir).await?;
+
+        logger.info(&format!(
+            "Summary: {} new, {} updated, {} unchanged, {} removed",
+            diff.new_skills_count(),
+            diff.updated_skills_count(),
+            diff.unchanged_skills_count(),
+            diff.removed_skills_count()
+        ));
+        logger.info("");
+
+        for skill in &diff.skills {
+            let (indicator, label) = match skill.status {
+                SkillStatus::New => ("+", "new"),
+                SkillStatus::Updated => ("~", "updated"),
+                SkillStatus::Unchanged => ("=", "unchanged"),
+                SkillStatus::Removed => ("-", "removed"),
+            };
+            logger.info(&format!("  {} {} ({})", indicator, skill.name, label));
+
+            if self.detailed {
+                for file in &skill.files {
+                    let file_indicator = match file.status {
+                        FileStatus::New => "+",
+                        FileStatus::Modified => "~",
+                        FileStatus::Unchanged => "=",
+                        FileStatus::Removed => "-",
+                    };
+                    logger.info(&format!(
+                        "      {} {}",
+                        file_indicator,
+                        file.relative_path.display()
+                    ));
+                }
+            }
+        }
+
+        logger.info("");
+        if diff.has_changes() {
+            logger.info("Run 'aiassisted skills update' to apply changes");
+        } else {
+            logger.success("All skills are up to date!");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::{Sha2Checksum, StdFileSystem, StrictLogger, StrictMode};
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    struct NullLogger;
+
+    impl Logger for NullLogger {
+        fn info(&self, _msg: &str) {}
+        fn warn(&self, _msg: &str) {}
+        fn error(&self, _msg: &str) {}
+        fn debug(&self, _msg: &str) {}
+        fn success(&self, _msg: &str) {}
+    }
+
+    /// Logger that keeps info messages for assertions
+    #[derive(Default)]
+    struct RecordingLogger {
+        infos: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl Logger for RecordingLogger {
+        fn info(&self, msg: &str) {
+            self.infos.lock().unwrap().push(msg.to_string());
+        }
+        fn warn(&self, _msg: &str) {}
+        fn error(&self, _msg: &str) {}
+        fn debug(&self, _msg: &str) {}
+        fn success(&self, _msg: &str) {}
+    }
+
+    fn mtime(path: &Path) -> SystemTime {
+        std::fs::metadata(path).unwrap().modified().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update_copies_only_modified_files() {
+        let fs = StdFileSystem::new();
+        let checksum = Sha2Checksum::new();
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let source = project.join(".aiassisted/skills/git-commit");
+        fs.write(&source.join("SKILL.md"), "# Commit").await.unwrap();
+        fs.write(&source.join("references/style.md"), "style").await.unwrap();
+        fs.write(&source.join("references/examples.md"), "examples").await.unwrap();
+
+        SetupSkillsCommand {
+            tools: vec![ToolType::Claude],
+            dry_run: false,
+            force: false,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        }
+        .execute(&fs, &NullLogger, project)
+        .await
+        .unwrap();
+
+        // Backdate every installed file so any rewrite shows up as a new mtime
+        let target = project.join(".claude/skills/git-commit");
+        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        for file in ["SKILL.md", "references/style.md", "references/examples.md"] {
+            let handle = std::fs::File::options().write(true).open(target.join(file)).unwrap();
+            handle.set_modified(old).unwrap();
+        }
+
+        fs.write(&source.join("references/style.md"), "new style").await.unwrap();
+
+        SkillsUpdateCommand {
+            tool: ToolType::Claude,
+            dry_run: false,
+            force: false,
+            verify: false,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        }
+        .execute(&fs, &checksum, &NullLogger, project)
+        .await
+        .unwrap();
+
+        assert_eq!(fs.read(&target.join("references/style.md")).await.unwrap(), "new style");
+        assert_ne!(mtime(&target.join("references/style.md")), old);
+        assert_eq!(mtime(&target.join("SKILL.md")), old);
+        assert_eq!(mtime(&target.join("references/examples.md")), old);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_diff_detailed_lists_added_modified_and_removed_files() {
+        let fs = StdFileSystem::new();
+        let checksum = Sha2Checksum::new();
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let source = project.join(".aiassisted/skills/git-commit");
+        fs.write(&source.join("SKILL.md"), "# Commit").await.unwrap();
+        fs.write(&source.join("references/style.md"), "style").await.unwrap();
+        fs.write(&source.join("references/old.md"), "old").await.unwrap();
+
+        SetupSkillsCommand {
+            tools: vec![ToolType::Claude],
+            dry_run: false,
+            force: false,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        }
+        .execute(&fs, &NullLogger, project)
+        .await
+        .unwrap();
+
+        fs.write(&source.join("references/style.md"), "new style").await.unwrap();
+        fs.write(&source.join("references/new.md"), "new").await.unwrap();
+        fs.remove_file(&source.join("references/old.md")).await.unwrap();
+
+        let diff = |detailed| SkillsDiffCommand {
+            tool: ToolType::Claude,
+            detailed,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        };
+        let logger = RecordingLogger::default();
+        diff(true).execute(&fs, &checksum, &logger, project).await.unwrap();
+        let infos = logger.infos.into_inner().unwrap();
+
+        assert!(infos.contains(&"  ~ git-commit (updated)".to_string()));
+        for line in [
+            "      + references/new.md",
+            "      ~ references/style.md",
+            "      - references/old.md",
+            "      = SKILL.md",
+        ] {
+            assert!(infos.contains(&line.to_string()), "missing {:?} in {:#?}", line, infos);
+        }
+
+        // Without --detailed only the skill line is printed
+        let logger = RecordingLogger::default();
+        diff(false).execute(&fs, &checksum, &logger, project).await.unwrap();
+        let infos = logger.infos.into_inner().unwrap();
+        assert!(infos.contains(&"  ~ git-commit (updated)".to_string()));
+        assert!(!infos.iter().any(|line| line.starts_with("      ")));
+    }
+
+    #[tokio::test]
+    async fn test_list_update_and_diff_use_output_dir_override() {
+        let fs = StdFileSystem::new();
+        let checksum = Sha2Checksum::new();
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let source = project.join(".aiassisted/skills/git-commit");
+        fs.write(&source.join("SKILL.md"), "# Commit").await.unwrap();
+        fs.write(&source.join("references/style.md"), "style").await.unwrap();
+
+        let mut output_dirs = BTreeMap::new();
+        output_dirs.insert(
+            "claude".to_string(),
+            ToolDirs {
+                skills: Some(PathBuf::from("tools/claude-skills")),
+                ..Default::default()
+            },
+        );
+        SetupSkillsCommand {
+            tools: vec![ToolType::Claude],
+            dry_run: false,
+            force: false,
+            output_dirs: output_dirs.clone(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        }
+        .execute(&fs, &NullLogger, project)
+        .await
+        .unwrap();
+        let target = project.join("tools/claude-skills/git-commit");
+        assert!(fs.exists(&target.join("SKILL.md")));
+        assert!(!fs.exists(&project.join(".claude")));
+
+        let logger = RecordingLogger::default();
+        SkillsListCommand {
+            tool: ToolType::Claude,
+            output_dirs: output_dirs.clone(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+        }
+        .execute(&fs, &logger, project)
+        .await
+        .unwrap();
+        let infos = logger.infos.into_inner().unwrap();
+        assert!(infos.contains(&"  - git-commit [installed]".to_string()), "{:#?}", infos);
+
+        fs.write(&source.join("references/style.md"), "new style").await.unwrap();
+        let logger = RecordingLogger::default();
+        SkillsDiffCommand {
+            tool: ToolType::Claude,
+            detailed: true,
+            output_dirs: output_dirs.clone(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        }
+        .execute(&fs, &checksum, &logger, project)
+        .await
+        .unwrap();
+        let infos = logger.infos.into_inner().unwrap();
+        assert!(infos.contains(&"  ~ git-commit (updated)".to_string()), "{:#?}", infos);
+        assert!(infos.contains(&"      ~ references/style.md".to_string()));
+
+        SkillsUpdateCommand {
+            tool: ToolType::Claude,
+            dry_run: false,
+            force: false,
+            verify: true,
+            output_dirs,
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        }
+        .execute(&fs, &checksum, &NullLogger, project)
+        .await
+        .unwrap();
+        assert_eq!(fs.read(&target.join("references/style.md")).await.unwrap(), "new style");
+        assert!(!fs.exists(&project.join(".claude")));
+    }
+
+    #[tokio::test]
+    async fn test_symlinked_skill_setup_and_update_following_symlinks() {
+        let fs = StdFileSystem::new();
+        let checksum = Sha2Checksum::new();
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        let shared = temp_dir.path().join("shared/review");
+        fs.write(&shared.join("SKILL.md"), "# Review").await.unwrap();
+        fs.write(&temp_dir.path().join("refs/style.md"), "style").await.unwrap();
+        std::os::unix::fs::symlink(temp_dir.path().join("refs"), shared.join("references"))
+            .unwrap();
+        // Canonical skill linked into the project, plus a link back up the tree
+        fs.create_dir_all(&project.join(".aiassisted/skills")).await.unwrap();
+        std::os::unix::fs::symlink(&shared, project.join(".aiassisted/skills/review")).unwrap();
+        std::os::unix::fs::symlink(&shared, shared.join("loop")).unwrap();
+
+        SetupSkillsCommand {
+            tools: vec![ToolType::Claude],
+            dry_run: false,
+            force: false,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: true,
+        }
+        .execute(&fs, &NullLogger, &project)
+        .await
+        .unwrap();
+        let target = project.join(".claude/skills/review");
+        assert_eq!(fs.read(&target.join("references/style.md")).await.unwrap(), "style");
+        assert!(!target.join("loop").exists());
+
+        fs.write(&temp_dir.path().join("refs/style.md"), "new style").await.unwrap();
+        let update = SkillsUpdateCommand {
+            tool: ToolType::Claude,
+            dry_run: false,
+            force: false,
+            verify: true,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: true,
+        };
+        update.execute(&fs, &checksum, &NullLogger, &project).await.unwrap();
+        assert_eq!(fs.read(&target.join("references/style.md")).await.unwrap(), "new style");
+
+        // Without following, the linked references look removed from the source
+        let diff = SkillDiffer::new(&fs, &checksum)
+            .compute_diff(&project.join(".aiassisted/skills"), &project.join(".claude/skills"))
+            .await
+            .unwrap();
+        let style = diff.skills[0]
+            .files
+            .iter()
+            .find(|f| f.relative_path == Path::new("references/style.md"))
+            .unwrap();
+        assert_eq!(style.status, FileStatus::Removed);
+    }
+
+    #[tokio::test]
+    async fn test_setup_all_tools() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let source = project.join(".aiassisted/skills/git-commit");
+        fs.write(&source.join("SKILL.md"), "# Commit").await.unwrap();
+
+        let setup = SetupSkillsCommand {
+            tools: ToolType::ALL.to_vec(),
+            dry_run: true,
+            force: false,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        };
+        setup.execute(&fs, &NullLogger, project).await.unwrap();
+        assert!(!fs.exists(&project.join(".claude/skills")));
+        assert!(!fs.exists(&project.join(".opencode/skills")));
+
+        SetupSkillsCommand {
+            dry_run: false,
+            ..setup
+        }
+        .execute(&fs, &NullLogger, project)
+        .await
+        .unwrap();
+        for dir in [".claude/skills", ".opencode/skills"] {
+            let skill = project.join(dir).join("git-commit/SKILL.md");
+            assert_eq!(fs.read(&skill).await.unwrap(), "# Commit", "{}", dir);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_setup_skipping_existing_fails_under_strict() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let source = project.join(".aiassisted/skills/git-commit");
+        fs.write(&source.join("SKILL.md"), "# Commit").await.unwrap();
+
+        let setup = SetupSkillsCommand {
+            tools: vec![ToolType::Claude],
+            dry_run: false,
+            force: false,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        };
+        let logger = StrictLogger::new(NullLogger, StrictMode::AtEnd);
+        let result = setup.execute(&fs, &logger, project).await;
+        assert!(logger.finish(result).is_ok());
+
+        // The second run skips the existing skill with a warning
+        let result = setup.execute(&fs, &logger, project).await;
+        assert!(result.is_ok());
+        let err = logger.finish(result).unwrap_err();
+        assert!(matches!(err, Error::Warnings(1)));
+        assert_ne!(err.exit_code(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_setup_stops_at_next_checkpoint_under_strict_immediate() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let source = project.join(".aiassisted/skills/git-commit");
+        fs.write(&source.join("SKILL.md"), "# Commit").await.unwrap();
+        let existing = project.join(".claude/skills/git-commit/SKILL.md");
+        fs.write(&existing, "# Mine").await.unwrap();
+
+        let setup = SetupSkillsCommand {
+            tools: vec![ToolType::Claude, ToolType::OpenCode],
+            dry_run: false,
+            force: false,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        };
+        let logger = StrictLogger::new(NullLogger, StrictMode::Immediate);
+        let result = setup.execute(&fs, &logger, project).await;
+
+        // Skipping the Claude skill warns, so OpenCode is never started
+        assert!(matches!(result, Err(Error::Warnings(1))));
+        assert_eq!(fs.read(&existing).await.unwrap(), "# Mine");
+        assert!(!fs.exists(&project.join(".opencode/skills")));
+    }
+
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let author = temp_dir.path().join("author");
+        let teammate = temp_dir.path().join("teammate");
+        let source = author.join(".aiassisted/skills/review-rust");
+        fs.write(&source.join("SKILL.md"), "# Review").await.unwrap();
+        fs.write(&source.join("references/checklist.md"), "- tests").await.unwrap();
+        fs.write_bytes(&source.join("assets/logo.png"), &[0x89, b'P', 0, 0xff])
+            .await
+            .unwrap();
+        let archive = temp_dir.path().join("review-rust.tar.gz");
+
+        let result = SkillsExportCommand { name: "missing".to_string(), archive: archive.clone() }
+            .execute(&fs, &NullLogger, &author)
+            .await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+
+        SkillsExportCommand { name: "review-rust".to_string(), archive: archive.clone() }
+            .execute(&fs, &NullLogger, &author)
+            .await
+            .unwrap();
+
+        let import = SkillsImportCommand { archive: archive.clone(), force: false, yes: false };
+        import.execute(&fs, &NullLogger, &teammate).await.unwrap();
+        let imported = teammate.join(".aiassisted/skills/review-rust");
+        for file in ["SKILL.md", "references/checklist.md", "assets/logo.png"] {
+            assert_eq!(
+                fs.read_bytes(&imported.join(file)).await.unwrap(),
+                fs.read_bytes(&source.join(file)).await.unwrap(),
+                "{}",
+                file
+            );
+        }
+
+        // An existing skill is only replaced with --force, dropping stale files
+        fs.write(&imported.join("local-notes.md"), "mine").await.unwrap();
+        let result = import.execute(&fs, &NullLogger, &teammate).await;
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("--force")));
+        assert!(fs.exists(&imported.join("local-notes.md")));
+
+        SkillsImportCommand { archive, force: true, yes: true }
+            .execute(&fs, &NullLogger, &teammate)
+            .await
+            .unwrap();
+        assert!(!fs.exists(&imported.join("local-notes.md")));
+        assert_eq!(fs.read(&imported.join("SKILL.md")).await.unwrap(), "# Review");
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_archive_without_skill_md() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        let cases = [
+            ("no-skill-md.tar.gz", vec!["notes/README.md"]),
+            ("two-skills.tar.gz", vec!["one/SKILL.md", "two/SKILL.md"]),
+            ("loose-file.tar.gz", vec!["SKILL.md"]),
+        ];
+
+        for (name, paths) in cases {
+            let files: Vec<_> =
+                paths.iter().map(|p| (PathBuf::from(p), b"# Skill".to_vec())).collect();
+            let archive = project.join(name);
+            fs.write_bytes(&archive, &pack_tar_gz(&files).unwrap()).await.unwrap();
+
+            let result = SkillsImportCommand { archive, force: false, yes: false }
+                .execute(&fs, &NullLogger, project)
+                .await;
+            assert!(matches!(result, Err(Error::Parse(_))), "{}", name);
+        }
+        assert!(!fs.exists(&project.join(".aiassisted/skills")));
+    }
+
+    #[tokio::test]
+    async fn test_setup_skips_disabled_skills() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        for name in ["git-commit", "review-rust"] {
+            let skill = project.join(".aiassisted/skills").join(name).join("SKILL.md");
+            fs.write(&skill, "# Skill").await.unwrap();
+        }
+
+        let result = SkillsDisableCommand { name: "missing".to_string() }
+            .execute(&fs, &NullLogger, project)
+            .await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+
+        SkillsDisableCommand { name: "review-rust".to_string() }
+            .execute(&fs, &NullLogger, project)
+            .await
+            .unwrap();
+        let selection = SkillSelection::load(&fs, project).await.unwrap();
+        assert!(!selection.is_enabled("review-rust"));
+
+        SetupSkillsCommand {
+            tools: vec![ToolType::Claude],
+            dry_run: false,
+            force: false,
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            refresh_detection: false,
+            follow_symlinks: false,
+        }
+        .execute(&fs, &NullLogger, project)
+        .await
+        .unwrap();
+        assert!(fs.exists(&project.join(".claude/skills/git-commit/SKILL.md")));
+        assert!(!fs.exists(&project.join(".claude/skills/review-rust")));
+
+        SkillsEnableCommand { name: "review-rust".to_string() }
+            .execute(&fs, &NullLogger, project)
+            .await
+            .unwrap();
+        let selection = SkillSelection::load(&fs, project).await.unwrap();
+        assert_eq!(selection, SkillSelection::default());
+    }
+}
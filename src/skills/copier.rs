@@ -2,9 +2,12 @@
 
 use crate::core::infra::FileSystem;
 use crate::core::types::{Error, Result};
-use std::future::Future;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::collections::BTreeSet;
 use std::path::Path;
-use std::pin::Pin;
+
+/// Maximum number of files copied at once within a skill
+const COPY_CONCURRENCY: usize = 8;
 
 /// Information about a skill to be copied
 #[derive(Debug, Clone)]
@@ -16,11 +19,21 @@ pub struct SkillInfo {
 /// Copies skill directories from source to target
 pub struct SkillCopier<'a, F: FileSystem> {
     fs: &'a F,
+    follow_symlinks: bool,
 }
 
 impl<'a, F: FileSystem> SkillCopier<'a, F> {
     pub fn new(fs: &'a F) -> Self {
-        Self { fs }
+        Self {
+            fs,
+            follow_symlinks: false,
+        }
+    }
+
+    /// Copy what symlinks inside a skill point to (`--follow-symlinks`)
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
     }
 
     /// Discover all skills in the source directory
@@ -80,30 +93,145 @@ impl<'a, F: FileSystem> SkillCopier<'a, F> {
     }
 
     /// Recursively copy directory contents
-    fn copy_dir_recursive<'b>(
-        &'b self,
-        source: &'b Path,
-        target: &'b Path,
-    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'b>> {
-        Box::pin(async move {
-            let entries = self.fs.list_dir(source).await?;
-
-            for entry in entries {
-                let file_name = entry
-                    .file_name()
-                    .ok_or_else(|| Error::Parse("Invalid file name".to_string()))?;
-                let target_path = target.join(file_name);
-
-                if self.fs.is_dir(&entry) {
-                    self.fs.create_dir_all(&target_path).await?;
-                    self.copy_dir_recursive(&entry, &target_path).await?;
-                } else {
-                    self.fs.copy(&entry, &target_path).await?;
-                }
+    ///
+    /// Symlinks are skipped, never recreated. When following symlinks, their
+    /// targets are copied as regular files and directories instead, minus any
+    /// link that loops back on itself. Every target directory is created
+    /// before any file is copied, then files are copied concurrently,
+    /// stopping at the first error.
+    async fn copy_dir_recursive(&self, source: &Path, target: &Path) -> Result<()> {
+        let files = if self.follow_symlinks {
+            self.fs.walk_dir_following(source).await?
+        } else {
+            self.fs.walk_dir(source).await?
+        };
+
+        let mut copies = Vec::new();
+        for file in files {
+            if !self.follow_symlinks && self.fs.is_symlink(&file) {
+                continue;
             }
+            let relative = file
+                .strip_prefix(source)
+                .map_err(|_| Error::Parse("Invalid file name".to_string()))?;
+            let target_path = target.join(relative);
+            copies.push((file, target_path));
+        }
 
-            Ok(())
-        })
+        let parents: BTreeSet<&Path> = copies
+            .iter()
+            .filter_map(|(_, target_path)| target_path.parent())
+            .collect();
+        for parent in parents {
+            self.fs.create_dir_all(parent).await?;
+        }
+
+        stream::iter(&copies)
+            .map(|(file, target_path)| self.fs.copy(file, target_path))
+            .buffer_unordered(COPY_CONCURRENCY)
+            .try_collect::<Vec<()>>()
+            .await?;
+
+        Ok(())
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::{MemFileSystem, StdFileSystem};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_copy_skill_copies_every_file() {
+        let fs = MemFileSystem::new();
+        let source = Path::new("/project/.aiassisted/skills/review");
+        let mut files = vec!["SKILL.md".to_string()];
+        for i in 0..20 {
+            files.push(format!("assets/nested/{}/file{}.md", i % 3, i));
+        }
+        for file in &files {
+            fs.write(&source.join(file), file).await.unwrap();
+        }
+
+        let copier = SkillCopier::new(&fs);
+        let skill = SkillInfo {
+            name: "review".to_string(),
+            source_path: source.to_path_buf(),
+        };
+        let target_dir = Path::new("/project/.claude/skills");
+
+        assert!(copier.copy_skill(&skill, target_dir, false).await.unwrap());
+
+        let copied = fs.walk_dir(&target_dir.join("review")).await.unwrap();
+        assert_eq!(copied.len(), files.len());
+        for file in &files {
+            let target = target_dir.join("review").join(file);
+            assert_eq!(fs.read(&target).await.unwrap(), *file);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_copy_skill_skips_symlinks() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("skills/review");
+        fs.write(&source.join("SKILL.md"), "# Review").await.unwrap();
+        fs.write(&source.join("docs/guide.md"), "guide").await.unwrap();
+        fs.write(&temp_dir.path().join("outside.md"), "outside")
+            .await
+            .unwrap();
+        // A link back to the skill itself would recurse forever if followed
+        std::os::unix::fs::symlink(&source, source.join("docs/loop")).unwrap();
+        std::os::unix::fs::symlink(temp_dir.path().join("outside.md"), source.join("link.md"))
+            .unwrap();
+
+        let copier = SkillCopier::new(&fs);
+        let skill = SkillInfo {
+            name: "review".to_string(),
+            source_path: source,
+        };
+        let target_dir = temp_dir.path().join("target");
+
+        assert!(copier.copy_skill(&skill, &target_dir, false).await.unwrap());
+
+        let copied = target_dir.join("review");
+        assert!(copied.join("SKILL.md").is_file());
+        assert!(copied.join("docs/guide.md").is_file());
+        assert!(!copied.join("docs/loop").exists());
+        assert!(!copied.join("link.md").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_copy_skill_following_symlinks() {
+        let fs = StdFileSystem::new();
+        let temp_dir = TempDir::new().unwrap();
+        let shared = temp_dir.path().join("shared");
+        let source = temp_dir.path().join("skills/review");
+        fs.write(&shared.join("SKILL.md"), "# Review").await.unwrap();
+        fs.write(&shared.join("references/style.md"), "style").await.unwrap();
+        fs.create_dir_all(&source).await.unwrap();
+        std::os::unix::fs::symlink(shared.join("SKILL.md"), source.join("SKILL.md")).unwrap();
+        std::os::unix::fs::symlink(shared.join("references"), source.join("references"))
+            .unwrap();
+        std::os::unix::fs::symlink(&source, source.join("loop")).unwrap();
+
+        let copier = SkillCopier::new(&fs).with_follow_symlinks(true);
+        let skill = SkillInfo {
+            name: "review".to_string(),
+            source_path: source,
+        };
+        let target_dir = temp_dir.path().join("target");
+        assert!(copier.copy_skill(&skill, &target_dir, false).await.unwrap());
+
+        let copied = target_dir.join("review");
+        assert_eq!(fs.read(&copied.join("SKILL.md")).await.unwrap(), "# Review");
+        assert_eq!(fs.read(&copied.join("references/style.md")).await.unwrap(), "style");
+        // Copies are real files, and the loop was not followed
+        assert!(!copied.join("references").is_symlink());
+        assert!(!copied.join("loop").exists());
+    }
+}
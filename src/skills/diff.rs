@@ -1,11 +1,9 @@
 //! Skill diff computation using SHA256 checksums
 
 use crate::core::infra::{Checksum, FileSystem};
-use crate::core::types::Result;
+use crate::core::types::{Error, Result};
 use std::collections::HashMap;
-use std::future::Future;
 use std::path::{Path, PathBuf};
-use std::pin::Pin;
 
 /// Status of a file within a skill
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -122,16 +120,69 @@ impl SkillsUpdateDiff {
     }
 }
 
+/// Index skill directories by their leaf name.
+///
+/// Two skills sharing a leaf name (e.g. `git/commit` and `review/commit`)
+/// would overwrite each other in the target directory, so this returns an
+/// error listing the colliding paths instead of silently dropping one.
+fn index_skills_by_name(skills: Vec<PathBuf>) -> Result<HashMap<String, PathBuf>> {
+    let mut names: HashMap<String, PathBuf> = HashMap::new();
+
+    for path in skills {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()).map(|n| n.to_string())
+        else {
+            continue;
+        };
+
+        if let Some(existing) = names.get(&name) {
+            return Err(Error::Config(format!(
+                "Duplicate skill name '{}': {} and {}",
+                name,
+                existing.display(),
+                path.display()
+            )));
+        }
+
+        names.insert(name, path);
+    }
+
+    Ok(names)
+}
+
 /// Computes diffs between source and target skills using SHA256 checksums
 pub struct SkillDiffer<'a, F: FileSystem, C: Checksum> {
     fs: &'a F,
     checksum: &'a C,
+    follow_symlinks: bool,
 }
 
 impl<'a, F: FileSystem, C: Checksum> SkillDiffer<'a, F, C> {
     /// Create a new SkillDiffer
     pub fn new(fs: &'a F, checksum: &'a C) -> Self {
-        Self { fs, checksum }
+        Self {
+            fs,
+            checksum,
+            follow_symlinks: false,
+        }
+    }
+
+    /// Include files reached through symlinked directories (`--follow-symlinks`)
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Check that a copied file matches its source
+    /// Returns the expected and actual checksums if they differ ("missing" if not written)
+    pub fn verify_file(&self, source: &Path, target: &Path) -> Result<Option<(String, String)>> {
+        let expected = self.checksum.sha256_file(source)?;
+        let actual = if self.fs.exists(target) {
+            self.checksum.sha256_file(target)?
+        } else {
+            "missing".to_string()
+        };
+
+        Ok((expected != actual).then_some((expected, actual)))
     }
 
     /// Compute diff between source and target skills directories
@@ -144,24 +195,12 @@ impl<'a, F: FileSystem, C: Checksum> SkillDiffer<'a, F, C> {
 
         // Get source skills
         let source_skills = self.discover_skill_dirs(source_dir).await?;
-        let source_names: HashMap<String, PathBuf> = source_skills
-            .into_iter()
-            .filter_map(|p| {
-                let name = p.file_name().and_then(|n| n.to_str()).map(|n| n.to_string());
-                name.map(|n| (n, p))
-            })
-            .collect();
+        let source_names = index_skills_by_name(source_skills)?;
 
         // Get target skills (if target exists)
         let target_names: HashMap<String, PathBuf> = if self.fs.exists(target_dir) {
             let target_skills = self.discover_skill_dirs(target_dir).await.unwrap_or_default();
-            target_skills
-                .into_iter()
-                .filter_map(|p| {
-                    let name = p.file_name().and_then(|n| n.to_str()).map(|n| n.to_string());
-                    name.map(|n| (n, p))
-                })
-                .collect()
+            index_skills_by_name(target_skills)?
         } else {
             HashMap::new()
         };
@@ -337,29 +376,12 @@ impl<'a, F: FileSystem, C: Checksum> SkillDiffer<'a, F, C> {
     }
 
     /// Recursively collect all files in a directory
-    fn collect_files_recursive<'b>(
-        &'b self,
-        dir: &'b Path,
-    ) -> Pin<Box<dyn Future<Output = Result<Vec<PathBuf>>> + Send + 'b>> {
-        Box::pin(async move {
-            if !self.fs.exists(dir) {
-                return Ok(Vec::new());
-            }
-
-            let mut files = Vec::new();
-            let entries = self.fs.list_dir(dir).await?;
-
-            for entry in entries {
-                if self.fs.is_dir(&entry) {
-                    let sub_files = self.collect_files_recursive(&entry).await?;
-                    files.extend(sub_files);
-                } else {
-                    files.push(entry);
-                }
-            }
-
-            Ok(files)
-        })
+    async fn collect_files_recursive(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        if self.follow_symlinks {
+            self.fs.walk_dir_following(dir).await
+        } else {
+            self.fs.walk_dir(dir).await
+        }
     }
 }
 
@@ -367,6 +389,77 @@ impl<'a, F: FileSystem, C: Checksum> SkillDiffer<'a, F, C> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_index_skills_by_name_rejects_collisions() {
+        let skills = vec![
+            PathBuf::from("/src/git/commit"),
+            PathBuf::from("/src/review/commit"),
+        ];
+
+        let err = index_skills_by_name(skills).unwrap_err().to_string();
+        assert!(err.contains("Duplicate skill name 'commit'"));
+        assert!(err.contains("/src/git/commit"));
+        assert!(err.contains("/src/review/commit"));
+    }
+
+    #[test]
+    fn test_index_skills_by_name_unique() {
+        let skills = vec![PathBuf::from("/src/commit"), PathBuf::from("/src/review")];
+
+        let names = index_skills_by_name(skills).unwrap();
+        assert_eq!(names.len(), 2);
+        assert_eq!(names["commit"], PathBuf::from("/src/commit"));
+    }
+
+    #[tokio::test]
+    async fn test_compute_diff_nested_files() {
+        let fs = crate::infra::StdFileSystem::new();
+        let checksum = crate::infra::Sha2Checksum::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        let target = temp_dir.path().join("target");
+
+        fs.write(&source.join("commit/SKILL.md"), "skill").await.unwrap();
+        fs.write(&source.join("commit/refs/deep/notes.md"), "new").await.unwrap();
+        fs.write(&target.join("commit/SKILL.md"), "skill").await.unwrap();
+
+        let diff = SkillDiffer::new(&fs, &checksum)
+            .compute_diff(&source, &target)
+            .await
+            .unwrap();
+
+        assert_eq!(diff.skills.len(), 1);
+        assert_eq!(diff.skills[0].status, SkillStatus::Updated);
+        assert_eq!(diff.skills[0].new_count(), 1);
+        assert_eq!(diff.skills[0].unchanged_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_verify_file() {
+        let fs = crate::infra::StdFileSystem::new();
+        let checksum = crate::infra::Sha2Checksum::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.md");
+        let target = temp_dir.path().join("target.md");
+        let differ = SkillDiffer::new(&fs, &checksum);
+
+        fs.write(&source, "full content").await.unwrap();
+        assert_eq!(
+            differ.verify_file(&source, &target).unwrap(),
+            Some((checksum.sha256(b"full content"), "missing".to_string()))
+        );
+
+        // A truncated write is reported
+        fs.write(&target, "full").await.unwrap();
+        assert_eq!(
+            differ.verify_file(&source, &target).unwrap(),
+            Some((checksum.sha256(b"full content"), checksum.sha256(b"full")))
+        );
+
+        fs.write(&target, "full content").await.unwrap();
+        assert_eq!(differ.verify_file(&source, &target).unwrap(), None);
+    }
+
     #[test]
     fn test_skill_diff_counts() {
         let diff = SkillDiff {
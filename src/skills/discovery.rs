@@ -1,13 +1,97 @@
-//! Tool detection for AI assistants
+//! Tool detection for AI assistants and SKILL.md metadata parsing
 
 use crate::core::infra::FileSystem;
-use crate::core::types::ToolType;
+use crate::core::types::{Error, Result, ToolDirs, ToolType};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Skill metadata from the optional YAML frontmatter of SKILL.md
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct SkillMetadata {
+    /// Skill name (falls back to the directory name)
+    #[serde(default)]
+    pub name: String,
+    /// Short description of the skill
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Tags for grouping and searching skills
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl SkillMetadata {
+    /// Parse SKILL.md content, falling back to `dir_name` when the
+    /// frontmatter is missing, malformed, or has no name.
+    pub fn parse(content: &str, dir_name: &str) -> Self {
+        let mut metadata = Self::parse_frontmatter(content).unwrap_or_default();
+
+        if metadata.name.trim().is_empty() {
+            metadata.name = dir_name.to_string();
+        }
+
+        metadata
+    }
+
+    /// Extract and parse the frontmatter block delimited by `---` lines
+    fn parse_frontmatter(content: &str) -> Option<Self> {
+        let rest = content.trim_start().strip_prefix("---")?;
+        let end = rest.find("\n---")?;
+
+        serde_yaml::from_str(&rest[..end]).ok()
+    }
+}
+
+/// Project files whose presence points at a tool, with how strongly
+///
+/// A tool's directory is the strongest sign; its config and instruction files
+/// identify projects that have not installed anything into that directory yet.
+const TOOL_MARKERS: [(ToolType, &str, u32); 8] = [
+    (ToolType::OpenCode, ".opencode", 3),
+    (ToolType::OpenCode, "opencode.json", 2),
+    (ToolType::OpenCode, "opencode.jsonc", 2),
+    (ToolType::OpenCode, ".opencode.json", 2),
+    (ToolType::Claude, ".claude", 3),
+    (ToolType::Claude, "CLAUDE.md", 2),
+    (ToolType::Claude, "CLAUDE.local.md", 2),
+    (ToolType::Claude, ".mcp.json", 1),
+];
+
+/// Detection cache location, relative to the project root
+const DETECTION_CACHE: &str = ".aiassisted/.tool-cache";
+
+/// How long the project directory must go unmodified before detection is cached
+///
+/// File systems stamp modification times from a coarse clock, so a marker
+/// created right after detection could leave the mtime unchanged.
+const CACHE_SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// Cached detection result, stored as JSON in [`DETECTION_CACHE`]
+#[derive(Debug, Serialize, Deserialize)]
+struct DetectionCache {
+    tool: ToolType,
+    /// [`ToolDetector::cache_key`] when `tool` was detected
+    key: String,
+}
+
+/// How a detector uses the on-disk detection cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CacheMode {
+    Disabled,
+    Enabled,
+    /// Ignore any cached result, but store the fresh one
+    Refresh,
+}
 
 /// Detects which AI tool a project uses and provides output paths
 pub struct ToolDetector<'a, F: FileSystem> {
     fs: &'a F,
     project_path: PathBuf,
+    output_dirs: BTreeMap<String, ToolDirs>,
+    default_tool: ToolType,
+    cache: CacheMode,
 }
 
 impl<'a, F: FileSystem> ToolDetector<'a, F> {
@@ -15,39 +99,166 @@ impl<'a, F: FileSystem> ToolDetector<'a, F> {
         Self {
             fs,
             project_path: project_path.to_path_buf(),
+            output_dirs: BTreeMap::new(),
+            default_tool: ToolType::Auto,
+            cache: CacheMode::Disabled,
         }
     }
 
-    /// Detect which tool the project uses
-    pub fn detect(&self) -> ToolType {
-        // Check for OpenCode config
-        if self.fs.exists(&self.project_path.join(".opencode.json")) {
-            return ToolType::OpenCode;
+    /// Use per-tool output directory overrides from config
+    pub fn with_output_dirs(mut self, output_dirs: BTreeMap<String, ToolDirs>) -> Self {
+        self.output_dirs = output_dirs;
+        self
+    }
+
+    /// Tool to fall back to when auto-detection finds nothing (`default_tool` in config)
+    pub fn with_default_tool(mut self, default_tool: ToolType) -> Self {
+        self.default_tool = default_tool;
+        self
+    }
+
+    /// Reuse detection results from `.aiassisted/.tool-cache`
+    ///
+    /// With `refresh` (`--refresh-detection`) the cached result is ignored and
+    /// replaced by a fresh detection.
+    pub fn with_detection_cache(mut self, refresh: bool) -> Self {
+        self.cache = if refresh {
+            CacheMode::Refresh
+        } else {
+            CacheMode::Enabled
+        };
+        self
+    }
+
+    /// Detect which tool the project uses, if any
+    ///
+    /// The tool with the highest [`detect_all`](Self::detect_all) score wins.
+    pub fn detect(&self) -> Option<ToolType> {
+        self.detect_all().first().map(|&(tool, _)| tool)
+    }
+
+    /// Every tool with at least one marker present, highest score first
+    ///
+    /// A tool's score is the sum of its present markers' weights. Ties go to
+    /// OpenCode, which was always checked first.
+    pub fn detect_all(&self) -> Vec<(ToolType, u32)> {
+        let mut scores: Vec<(ToolType, u32)> = Vec::new();
+        for (tool, marker, weight) in TOOL_MARKERS {
+            if !self.fs.exists(&self.project_path.join(marker)) {
+                continue;
+            }
+            match scores.iter_mut().find(|(t, _)| *t == tool) {
+                Some((_, score)) => *score += weight,
+                None => scores.push((tool, weight)),
+            }
+        }
+        // Stable sort keeps marker-table order between equal scores
+        scores.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        scores
+    }
+
+    /// Key under which detection is cached: the project directory's mtime plus config
+    ///
+    /// Every marker sits directly in the project root, so adding, removing or
+    /// renaming one changes the directory's mtime without statting each marker.
+    /// `None` when the file system reports no mtime.
+    fn cache_key(&self) -> Option<String> {
+        let modified = self.fs.modified(&self.project_path)?;
+        let key = format!("{:?}\n{:?}\n{:?}", modified, self.default_tool, self.output_dirs);
+        Some(format!("{:x}", Sha256::digest(key.as_bytes())))
+    }
+
+    /// [`resolve`](Self::resolve), consulting the detection cache when enabled
+    ///
+    /// A cached tool is reused only while the [cache key](Self::cache_key) still
+    /// matches. Only detected tools are cached, never the configured default, and
+    /// the cache is only written into an existing `.aiassisted/` directory once
+    /// the project directory has been left alone for [`CACHE_SETTLE_TIME`].
+    pub async fn resolve_cached(&self, tool: ToolType) -> Result<ToolType> {
+        if tool != ToolType::Auto || self.cache == CacheMode::Disabled {
+            return self.resolve(tool);
+        }
+        let Some(key) = self.cache_key() else {
+            return self.resolve(tool);
+        };
+
+        let cache_path = self.project_path.join(DETECTION_CACHE);
+        if self.cache == CacheMode::Enabled && self.fs.is_file(&cache_path) {
+            let cached = self
+                .fs
+                .read(&cache_path)
+                .await
+                .ok()
+                .and_then(|content| serde_json::from_str::<DetectionCache>(&content).ok());
+            if let Some(cached) = cached
+                && cached.key == key
+                && cached.tool != ToolType::Auto
+            {
+                return Ok(cached.tool);
+            }
+        }
+
+        let Some(detected) = self.detect() else {
+            return self.resolve(tool);
+        };
+
+        let settled = self
+            .fs
+            .modified(&self.project_path)
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age >= CACHE_SETTLE_TIME);
+        if settled && cache_path.parent().is_some_and(|dir| self.fs.is_dir(dir)) {
+            let entry = DetectionCache {
+                tool: detected,
+                key,
+            };
+            let content = serde_json::to_string(&entry)
+                .map_err(|e| Error::Serialization(e.to_string()))?;
+            // A stale or unwritable cache only costs a re-scan next time
+            let _ = self.fs.write(&cache_path, &content).await;
         }
 
-        // Check for Claude Code indicators
-        if self.fs.exists(&self.project_path.join(".claude"))
-            || self.fs.exists(&self.project_path.join("CLAUDE.md"))
-        {
-            return ToolType::Claude;
+        Ok(detected)
+    }
+
+    /// Resolve `ToolType::Auto` to a concrete tool
+    ///
+    /// Detection wins over the configured default; with neither, this is an error
+    /// rather than a guess, so Claude files are never written into an OpenCode project.
+    pub fn resolve(&self, tool: ToolType) -> Result<ToolType> {
+        if tool != ToolType::Auto {
+            return Ok(tool);
         }
 
-        // Default to Claude
-        ToolType::Claude
+        match self.detect() {
+            Some(detected) => Ok(detected),
+            None if self.default_tool != ToolType::Auto => Ok(self.default_tool),
+            None => Err(Error::Config(
+                "could not auto-detect a tool; pass --tool or set default_tool in config"
+                    .to_string(),
+            )),
+        }
     }
 
     /// Get the skills output directory for a tool
+    ///
+    /// A config override wins; otherwise [`ToolType::skills_dir`] decides.
     pub fn skills_dir(&self, tool: ToolType) -> PathBuf {
         let tool = if tool == ToolType::Auto {
-            self.detect()
+            self.resolve(tool).unwrap_or(ToolType::Claude)
         } else {
             tool
         };
 
-        match tool {
-            ToolType::OpenCode => self.project_path.join(".opencode/skills"),
-            ToolType::Claude | ToolType::Auto => self.project_path.join(".claude/skills"),
+        let override_dir = self
+            .output_dirs
+            .get(&tool.to_string())
+            .and_then(|dirs| dirs.skills.as_ref());
+        if let Some(dir) = override_dir {
+            return self.project_path.join(dir);
         }
+
+        self.project_path.join(tool.skills_dir())
     }
 
     /// Get the skills source directory
@@ -56,3 +267,251 @@ impl<'a, F: FileSystem> ToolDetector<'a, F> {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::infra::StdFileSystem;
+
+    fn claude_skills_override(dir: &str) -> BTreeMap<String, ToolDirs> {
+        let mut output_dirs = BTreeMap::new();
+        output_dirs.insert(
+            "claude".to_string(),
+            ToolDirs {
+                skills: Some(PathBuf::from(dir)),
+                ..Default::default()
+            },
+        );
+        output_dirs
+    }
+
+    #[test]
+    fn test_skills_dir_default() {
+        let fs = StdFileSystem::new();
+        let detector = ToolDetector::new(&fs, Path::new("/project"));
+
+        assert_eq!(
+            detector.skills_dir(ToolType::Claude),
+            PathBuf::from("/project/.claude/skills")
+        );
+        assert_eq!(
+            detector.skills_dir(ToolType::OpenCode),
+            PathBuf::from("/project/.opencode/skills")
+        );
+    }
+
+    #[test]
+    fn test_skills_dir_per_tool() {
+        let fs = StdFileSystem::new();
+        let detector = ToolDetector::new(&fs, Path::new("/project"));
+
+        for tool in ToolType::ALL {
+            let expected = match tool {
+                ToolType::Claude => "/project/.claude/skills",
+                ToolType::OpenCode => "/project/.opencode/skills",
+                ToolType::Auto => unreachable!("ALL has only concrete tools"),
+            };
+            assert_eq!(detector.skills_dir(tool), PathBuf::from(expected), "{}", tool);
+        }
+    }
+
+    #[test]
+    fn test_skills_dir_override_wins() {
+        let fs = StdFileSystem::new();
+        let detector = ToolDetector::new(&fs, Path::new("/project"))
+            .with_output_dirs(claude_skills_override("tools/claude-skills"));
+
+        assert_eq!(
+            detector.skills_dir(ToolType::Claude),
+            PathBuf::from("/project/tools/claude-skills")
+        );
+        // Other tools keep their defaults
+        assert_eq!(
+            detector.skills_dir(ToolType::OpenCode),
+            PathBuf::from("/project/.opencode/skills")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_auto() {
+        let fs = crate::infra::MemFileSystem::new();
+        let detector = ToolDetector::new(&fs, Path::new("/project"));
+
+        // Nothing to detect and no default configured
+        assert_eq!(detector.detect(), None);
+        let result = detector.resolve(ToolType::Auto);
+        assert!(matches!(result, Err(Error::Config(msg)) if msg.contains("pass --tool")));
+
+        // An explicit tool is never second-guessed
+        assert_eq!(detector.resolve(ToolType::OpenCode).unwrap(), ToolType::OpenCode);
+
+        let detector = detector.with_default_tool(ToolType::OpenCode);
+        assert_eq!(detector.resolve(ToolType::Auto).unwrap(), ToolType::OpenCode);
+
+        // Detection beats the configured default
+        fs.write(Path::new("/project/CLAUDE.md"), "# Claude").await.unwrap();
+        assert_eq!(detector.resolve(ToolType::Auto).unwrap(), ToolType::Claude);
+    }
+
+    #[tokio::test]
+    async fn test_detect_from_config_file_only() {
+        let fs = crate::infra::MemFileSystem::new();
+        let detector = ToolDetector::new(&fs, Path::new("/project"));
+
+        fs.write(Path::new("/project/opencode.json"), "{}").await.unwrap();
+        assert_eq!(detector.detect(), Some(ToolType::OpenCode));
+        assert_eq!(detector.detect_all(), vec![(ToolType::OpenCode, 2)]);
+
+        let fs = crate::infra::MemFileSystem::new();
+        let detector = ToolDetector::new(&fs, Path::new("/project"));
+        fs.write(Path::new("/project/.mcp.json"), "{}").await.unwrap();
+        assert_eq!(detector.detect(), Some(ToolType::Claude));
+    }
+
+    #[tokio::test]
+    async fn test_detect_all_ranks_by_score() {
+        let fs = crate::infra::MemFileSystem::new();
+        let detector = ToolDetector::new(&fs, Path::new("/project"));
+
+        // A stray OpenCode config file loses to a Claude directory plus CLAUDE.md
+        fs.write(Path::new("/project/.opencode.json"), "{}").await.unwrap();
+        fs.create_dir_all(Path::new("/project/.claude")).await.unwrap();
+        fs.write(Path::new("/project/CLAUDE.md"), "# Claude").await.unwrap();
+        assert_eq!(
+            detector.detect_all(),
+            vec![(ToolType::Claude, 5), (ToolType::OpenCode, 2)]
+        );
+        assert_eq!(detector.detect(), Some(ToolType::Claude));
+
+        // Equal scores keep OpenCode first
+        fs.create_dir_all(Path::new("/project/.opencode")).await.unwrap();
+        assert_eq!(detector.detect(), Some(ToolType::OpenCode));
+    }
+
+    /// Backdate `dir`'s mtime past [`CACHE_SETTLE_TIME`], so detection is cached
+    #[cfg(unix)]
+    fn settle(dir: &Path) {
+        let past = SystemTime::now() - Duration::from_secs(3600);
+        std::fs::File::open(dir).unwrap().set_modified(past).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_detection_cache_hit_skips_detection() {
+        let fs = StdFileSystem::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path();
+        fs.create_dir_all(&project.join(".aiassisted")).await.unwrap();
+        fs.write(&project.join("CLAUDE.md"), "# Claude").await.unwrap();
+        settle(project);
+
+        let detector = ToolDetector::new(&fs, project).with_detection_cache(false);
+        assert_eq!(detector.resolve_cached(ToolType::Auto).await.unwrap(), ToolType::Claude);
+        let cache_path = project.join(DETECTION_CACHE);
+        assert!(fs.is_file(&cache_path));
+
+        // Plant a different tool under the same key: a hit must not re-scan
+        let planted = DetectionCache {
+            tool: ToolType::OpenCode,
+            key: detector.cache_key().unwrap(),
+        };
+        fs.write(&cache_path, &serde_json::to_string(&planted).unwrap()).await.unwrap();
+        assert_eq!(detector.resolve_cached(ToolType::Auto).await.unwrap(), ToolType::OpenCode);
+
+        // --refresh-detection ignores the cache and rewrites it
+        let refreshing = ToolDetector::new(&fs, project).with_detection_cache(true);
+        assert_eq!(refreshing.resolve_cached(ToolType::Auto).await.unwrap(), ToolType::Claude);
+        assert_eq!(detector.resolve_cached(ToolType::Auto).await.unwrap(), ToolType::Claude);
+
+        // A different config gets a different key
+        let configured = ToolDetector::new(&fs, project)
+            .with_default_tool(ToolType::OpenCode)
+            .with_detection_cache(false);
+        assert_ne!(configured.cache_key(), detector.cache_key());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_detection_cache_invalidated_by_marker_change() {
+        let fs = StdFileSystem::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path();
+        fs.create_dir_all(&project.join(".aiassisted")).await.unwrap();
+        fs.write(&project.join("CLAUDE.md"), "# Claude").await.unwrap();
+        settle(project);
+
+        let detector = ToolDetector::new(&fs, project).with_detection_cache(false);
+        assert_eq!(detector.resolve_cached(ToolType::Auto).await.unwrap(), ToolType::Claude);
+        let cached = fs.read(&project.join(DETECTION_CACHE)).await.unwrap();
+
+        fs.write(&project.join(".opencode.json"), "{}").await.unwrap();
+        assert_eq!(detector.resolve_cached(ToolType::Auto).await.unwrap(), ToolType::OpenCode);
+
+        // The directory was just modified, so the fresh result is not cached yet
+        assert_eq!(fs.read(&project.join(DETECTION_CACHE)).await.unwrap(), cached);
+    }
+
+    #[tokio::test]
+    async fn test_detection_cache_needs_modification_times() {
+        let fs = crate::infra::MemFileSystem::new();
+        fs.create_dir_all(Path::new("/project/.aiassisted")).await.unwrap();
+        fs.write(Path::new("/project/CLAUDE.md"), "# Claude").await.unwrap();
+
+        let detector = ToolDetector::new(&fs, Path::new("/project")).with_detection_cache(false);
+        assert_eq!(detector.resolve_cached(ToolType::Auto).await.unwrap(), ToolType::Claude);
+        assert!(!fs.exists(Path::new("/project/.aiassisted/.tool-cache")));
+    }
+
+    #[test]
+    fn test_parse_skill_metadata_full() {
+        let content = r#"---
+name: code-review
+description: Review code for common issues
+tags:
+  - review
+  - quality
+---
+
+# Code Review
+"#;
+
+        let metadata = SkillMetadata::parse(content, "review-dir");
+
+        assert_eq!(metadata.name, "code-review");
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("Review code for common issues")
+        );
+        assert_eq!(metadata.tags, vec!["review", "quality"]);
+    }
+
+    #[test]
+    fn test_parse_skill_metadata_without_name() {
+        let content = "---\ndescription: No name here\n---\nBody";
+
+        let metadata = SkillMetadata::parse(content, "my-skill");
+
+        assert_eq!(metadata.name, "my-skill");
+        assert_eq!(metadata.description.as_deref(), Some("No name here"));
+    }
+
+    #[test]
+    fn test_parse_skill_metadata_no_frontmatter() {
+        let metadata = SkillMetadata::parse("# Just markdown", "plain-skill");
+
+        assert_eq!(metadata.name, "plain-skill");
+        assert!(metadata.description.is_none());
+        assert!(metadata.tags.is_empty());
+    }
+
+    #[test]
+    fn test_parse_skill_metadata_invalid_yaml() {
+        let content = "---\nname: [unclosed\n---\nBody";
+
+        let metadata = SkillMetadata::parse(content, "broken-skill");
+
+        assert_eq!(metadata.name, "broken-skill");
+        assert!(metadata.description.is_none());
+    }
+}
@@ -4,8 +4,14 @@ mod commands;
 mod copier;
 mod diff;
 mod discovery;
+mod selection;
 
-pub use commands::{SetupSkillsCommand, SkillsListCommand, SkillsUpdateCommand};
+pub use commands::{
+    SetupSkillsCommand, SkillsDiffCommand, SkillsDisableCommand, SkillsEnableCommand,
+    SkillsExportCommand, SkillsImportCommand, SkillsListCommand, SkillsUpdateCommand,
+    MAX_SKILL_ARCHIVE_SIZE,
+};
 pub use copier::{SkillCopier, SkillInfo};
 pub use diff::{FileStatus, SkillDiff, SkillDiffer, SkillStatus, SkillsUpdateDiff};
-pub use discovery::ToolDetector;
+pub use discovery::{SkillMetadata, ToolDetector};
+pub use selection::{SelectionDefault, SkillSelection, SELECTION_FILE};
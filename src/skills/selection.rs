@@ -0,0 +1,155 @@
+//! Persistent skill selection stored in `.aiassisted/skills.toml`
+
+use crate::core::infra::FileSystem;
+use crate::core::types::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Selection file location, relative to the project root
+pub const SELECTION_FILE: &str = ".aiassisted/skills.toml";
+
+/// Whether skills missing from both lists are acted on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectionDefault {
+    #[default]
+    Enabled,
+    Disabled,
+}
+
+/// Which skills `skills setup` and `skills update` act on
+///
+/// ```toml
+/// default = "disabled"
+/// enabled = ["git-commit", "review-rust"]
+/// ```
+///
+/// Without a file every skill is enabled, as before selection existed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillSelection {
+    /// Applies to skills in neither list
+    #[serde(default)]
+    pub default: SelectionDefault,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub enabled: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub disabled: BTreeSet<String>,
+}
+
+impl SkillSelection {
+    /// Path of the selection file for a project
+    pub fn path(project_path: &Path) -> PathBuf {
+        project_path.join(SELECTION_FILE)
+    }
+
+    /// Load the project's selection, or the all-enabled default if there is none
+    pub async fn load<F: FileSystem>(fs: &F, project_path: &Path) -> Result<Self> {
+        let path = Self::path(project_path);
+        if !fs.exists(&path) {
+            return Ok(Self::default());
+        }
+
+        let contents = fs.read(&path).await?;
+        toml::from_str(&contents).map_err(|e| Error::toml(path.display(), &contents, &e))
+    }
+
+    /// Write the selection back to the project
+    pub async fn save<F: FileSystem>(&self, fs: &F, project_path: &Path) -> Result<()> {
+        let contents = toml::to_string_pretty(self).map_err(|e| {
+            Error::Serialization(format!("Failed to serialize skill selection: {}", e))
+        })?;
+        fs.write(&Self::path(project_path), &contents).await
+    }
+
+    /// Whether `name` should be set up and updated
+    pub fn is_enabled(&self, name: &str) -> bool {
+        if self.disabled.contains(name) {
+            return false;
+        }
+        self.enabled.contains(name) || self.default == SelectionDefault::Enabled
+    }
+
+    /// Mark `name` enabled, returning whether anything changed
+    pub fn enable(&mut self, name: &str) -> bool {
+        let was_enabled = self.is_enabled(name);
+        self.disabled.remove(name);
+        if self.default == SelectionDefault::Disabled {
+            self.enabled.insert(name.to_string());
+        }
+        !was_enabled
+    }
+
+    /// Mark `name` disabled, returning whether anything changed
+    pub fn disable(&mut self, name: &str) -> bool {
+        let was_enabled = self.is_enabled(name);
+        self.enabled.remove(name);
+        if self.default == SelectionDefault::Enabled {
+            self.disabled.insert(name.to_string());
+        }
+        was_enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::infra::MemFileSystem;
+
+    #[test]
+    fn test_default_enables_everything() {
+        let selection = SkillSelection::default();
+        assert!(selection.is_enabled("git-commit"));
+    }
+
+    #[test]
+    fn test_enable_disable_with_each_default() {
+        let mut selection = SkillSelection::default();
+        assert!(selection.disable("git-commit"));
+        assert!(!selection.disable("git-commit"));
+        assert!(!selection.is_enabled("git-commit"));
+        assert!(selection.enable("git-commit"));
+        // Back to relying on the default rather than an explicit entry
+        assert_eq!(selection, SkillSelection::default());
+
+        let mut selection = SkillSelection {
+            default: SelectionDefault::Disabled,
+            ..Default::default()
+        };
+        assert!(!selection.is_enabled("review-rust"));
+        assert!(selection.enable("review-rust"));
+        assert!(selection.is_enabled("review-rust"));
+        assert!(!selection.is_enabled("git-commit"));
+        assert!(selection.disable("review-rust"));
+        assert!(selection.enabled.is_empty() && selection.disabled.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_save_roundtrip() {
+        let fs = MemFileSystem::new();
+        let project = Path::new("/project");
+        assert_eq!(SkillSelection::load(&fs, project).await.unwrap(), SkillSelection::default());
+
+        fs.write(
+            &SkillSelection::path(project),
+            "default = \"disabled\"\nenabled = [\"git-commit\"]\n",
+        )
+        .await
+        .unwrap();
+        let mut selection = SkillSelection::load(&fs, project).await.unwrap();
+        assert!(selection.is_enabled("git-commit"));
+        assert!(!selection.is_enabled("review-rust"));
+
+        selection.enable("review-rust");
+        selection.save(&fs, project).await.unwrap();
+        let reloaded = SkillSelection::load(&fs, project).await.unwrap();
+        assert_eq!(reloaded, selection);
+
+        fs.write(&SkillSelection::path(project), "default = \"sometimes\"")
+            .await
+            .unwrap();
+        let result = SkillSelection::load(&fs, project).await;
+        assert!(matches!(result, Err(Error::Parse(msg)) if msg.contains("skills.toml")));
+    }
+}
@@ -3,10 +3,12 @@
 //! These tests verify that configuration management works correctly end-to-end
 //! using real implementations.
 
-use aiassisted::config::{GetCommand, PathCommand, ResetCommand, ShowCommand, TomlConfigStore};
+use aiassisted::config::{
+    GetCommand, PathCommand, ResetCommand, ShowCommand, TomlConfigStore, ValidateCommand,
+};
 use aiassisted::core::config::ConfigStore;
 use aiassisted::core::infra::{FileSystem, Logger};
-use aiassisted::core::types::{AppConfig, ToolType};
+use aiassisted::core::types::{AppConfig, ToolDirs, ToolType};
 use aiassisted::infra::StdFileSystem;
 use tempfile::TempDir;
 
@@ -225,6 +227,16 @@ async fn test_config_toml_roundtrip() {
         verbosity: 2,
         auto_update: false,
         prefer_project: false,
+        output_dirs: [(
+            "claude".to_string(),
+            ToolDirs {
+                skills: Some("tools/skills".into()),
+                agents: None,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        temp_dir: Some("/var/tmp/aiassisted".into()),
     };
 
     // Save
@@ -236,6 +248,7 @@ async fn test_config_toml_roundtrip() {
     assert!(content.contains("verbosity"));
     assert!(content.contains("auto_update"));
     assert!(content.contains("prefer_project"));
+    assert!(content.contains("[output_dirs.claude]"));
 
     // Load back and verify
     let loaded = store.load().await.unwrap();
@@ -243,6 +256,8 @@ async fn test_config_toml_roundtrip() {
     assert_eq!(loaded.verbosity, config.verbosity);
     assert_eq!(loaded.auto_update, config.auto_update);
     assert_eq!(loaded.prefer_project, config.prefer_project);
+    assert_eq!(loaded.output_dirs, config.output_dirs);
+    assert_eq!(loaded.temp_dir, config.temp_dir);
 }
 
 #[tokio::test]
@@ -318,3 +333,67 @@ async fn test_config_creates_parent_directory() {
     // Verify file exists
     assert!(fs.exists(&nested_path));
 }
+
+#[tokio::test]
+async fn test_validate_command_valid_config() {
+    let fs = StdFileSystem::new();
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    let store = TomlConfigStore::with_path(fs.clone(), config_path);
+    store.save(&AppConfig::default()).await.unwrap();
+
+    let cmd = ValidateCommand;
+    let result = cmd.execute(&fs, &store, &TestLogger).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_validate_command_missing_file() {
+    let fs = StdFileSystem::new();
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    let store = TomlConfigStore::with_path(fs.clone(), config_path);
+
+    let cmd = ValidateCommand;
+    let result = cmd.execute(&fs, &store, &TestLogger).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_validate_command_invalid_values() {
+    let fs = StdFileSystem::new();
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    fs.write(&config_path, "default_tool = \"vscode\"\nverbosity = 7\n")
+        .await
+        .unwrap();
+
+    let store = TomlConfigStore::with_path(fs.clone(), config_path);
+
+    let cmd = ValidateCommand;
+    let result = cmd.execute(&fs, &store, &TestLogger).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_config_override_path() {
+    let fs = StdFileSystem::new();
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("env/staging.toml");
+
+    let store = TomlConfigStore::with_override(fs, Some(config_path.clone())).unwrap();
+    assert_eq!(store.config_path(), config_path);
+
+    let config = AppConfig {
+        default_tool: ToolType::Claude,
+        ..AppConfig::default()
+    };
+    store.save(&config).await.unwrap();
+    assert!(config_path.exists());
+
+    let loaded = store.load().await.unwrap();
+    assert_eq!(loaded.default_tool, ToolType::Claude);
+}
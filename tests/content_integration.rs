@@ -3,15 +3,49 @@
 //! These tests verify that manifest loading, GitHub downloading, and sync
 //! operations work correctly together using real implementations.
 
-use aiassisted::core::infra::{Checksum, FileSystem, HttpClient};
-use aiassisted::core::types::ManifestEntry;
+use aiassisted::content::{
+    backup, CheckCommand, ContentFileStatus, ContentListCommand, ContentSource,
+    InstallCommand, UpdateCommand, WatchCommand,
+};
+use aiassisted::core::infra::{Checksum, FileSystem, HttpClient, Logger};
+use aiassisted::core::types::{ManifestEntry, OutputFormat};
 use aiassisted::infra::{ReqwestClient, Sha2Checksum, StdFileSystem};
 use aiassisted::Manifest;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tempfile::TempDir;
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+// Logger that records every message for assertions
+#[derive(Debug, Default)]
+struct RecordingLogger {
+    messages: Mutex<Vec<String>>,
+}
+
+impl RecordingLogger {
+    fn messages(&self) -> Vec<String> {
+        self.messages.lock().unwrap().clone()
+    }
+}
+
+impl Logger for RecordingLogger {
+    fn info(&self, msg: &str) {
+        self.messages.lock().unwrap().push(msg.to_string());
+    }
+    fn warn(&self, msg: &str) {
+        self.messages.lock().unwrap().push(msg.to_string());
+    }
+    fn error(&self, msg: &str) {
+        self.messages.lock().unwrap().push(msg.to_string());
+    }
+    fn debug(&self, msg: &str) {
+        self.messages.lock().unwrap().push(msg.to_string());
+    }
+    fn success(&self, msg: &str) {
+        self.messages.lock().unwrap().push(msg.to_string());
+    }
+}
 
 #[tokio::test]
 async fn test_manifest_load_save_roundtrip() {
@@ -32,6 +66,7 @@ async fn test_manifest_load_save_roundtrip() {
                 checksum: "def456".to_string(),
             },
         ],
+        self_checksum: None,
     };
 
     // Save it
@@ -47,6 +82,36 @@ async fn test_manifest_load_save_roundtrip() {
     assert_eq!(loaded.files[0].checksum, original.files[0].checksum);
 }
 
+#[tokio::test]
+async fn test_gzipped_manifest_at_plain_json_url() {
+    let mock_server = MockServer::start().await;
+
+    let manifest = Manifest {
+        version: "2.0.0".to_string(),
+        files: vec![ManifestEntry {
+            path: PathBuf::from("guide.md"),
+            checksum: "abc123".to_string(),
+        }],
+        self_checksum: None,
+    };
+
+    // Served compressed without a .gz suffix, as a CDN applying gzip would
+    Mock::given(method("GET"))
+        .and(path("/manifest.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Encoding", "gzip")
+                .set_body_bytes(manifest.to_gzip().unwrap()),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let url = format!("{}/manifest.json", mock_server.uri());
+    let loaded = Manifest::load_remote(&ReqwestClient::new(), &url).await.unwrap();
+
+    assert_eq!(loaded, manifest);
+}
+
 #[tokio::test]
 async fn test_manifest_diff_workflow() {
     let fs = StdFileSystem::new();
@@ -65,6 +130,7 @@ async fn test_manifest_diff_workflow() {
                 checksum: "old_hash".to_string(),
             },
         ],
+        self_checksum: None,
     };
 
     // Remote manifest (v2.0.0)
@@ -84,6 +150,7 @@ async fn test_manifest_diff_workflow() {
                 checksum: "new".to_string(),
             },
         ],
+        self_checksum: None,
     };
 
     // Save local manifest
@@ -198,6 +265,7 @@ async fn test_full_download_workflow_with_checksum_verification() {
                 checksum: hash2.clone(),
             },
         ],
+        self_checksum: None,
     };
 
     // Mock file downloads
@@ -276,6 +344,7 @@ async fn test_update_workflow_with_partial_changes() {
                 checksum: hash2_old.clone(),
             },
         ],
+        self_checksum: None,
     };
 
     // New version (v2.0.0) - only file1 changed
@@ -294,6 +363,7 @@ async fn test_update_workflow_with_partial_changes() {
                 checksum: hash2_old.clone(), // Unchanged
             },
         ],
+        self_checksum: None,
     };
 
     // Calculate diff
@@ -352,3 +422,778 @@ async fn test_large_file_download_and_checksum() {
     let actual_hash = checksum.sha256_file(&dest_path).unwrap();
     assert_eq!(actual_hash, expected_hash);
 }
+
+#[tokio::test]
+async fn test_binary_content_survives_manifest_and_backup() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    // PNG signature followed by bytes that are not valid UTF-8
+    let image = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0x00, 0xfe, 0x80];
+    fs.write_bytes(&content_dir.join("assets/logo.png"), &image)
+        .await
+        .unwrap();
+
+    let manifest = Manifest::generate(&fs, &checksum, &content_dir, "1.0.0")
+        .await
+        .unwrap();
+    assert_eq!(manifest.files.len(), 1);
+    assert_eq!(manifest.files[0].checksum, checksum.sha256(&image));
+    assert!(
+        manifest
+            .verify_checksums(&fs, &checksum, &content_dir)
+            .await
+            .is_empty()
+    );
+
+    let backup_dir = backup::create_backup(&fs, temp_dir.path()).await.unwrap();
+    assert_eq!(
+        fs.read_bytes(&backup_dir.join("assets/logo.png"))
+            .await
+            .unwrap(),
+        image
+    );
+}
+
+#[tokio::test]
+async fn test_check_verbose_reports_each_file() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    fs.write(&content_dir.join("good.md"), "good").await.unwrap();
+    fs.write(&content_dir.join("bad.md"), "original").await.unwrap();
+    let manifest = Manifest::generate(&fs, &checksum, &content_dir, "1.0.0")
+        .await
+        .unwrap();
+    manifest
+        .save(&fs, &content_dir.join("manifest.json"))
+        .await
+        .unwrap();
+
+    // Corrupt one file after the manifest was recorded
+    fs.write(&content_dir.join("bad.md"), "corrupted").await.unwrap();
+
+    let logger = RecordingLogger::default();
+    let cmd = CheckCommand {
+        path: temp_dir.path().to_path_buf(),
+        verbose: true,
+        format: OutputFormat::Text,
+        quiet: false,
+        source: ContentSource::default(),
+        offline: false,
+    };
+    // The client refuses the network, so the remote comparison fails after local verification ran
+    let result = cmd
+        .execute(&fs, &ReqwestClient::offline(), &checksum, &logger)
+        .await;
+    assert!(result.is_err());
+
+    let messages = logger.messages();
+    assert!(messages.iter().any(|m| m.contains("OK") && m.contains("good.md")));
+    let expected = checksum.sha256(b"original");
+    let actual = checksum.sha256(b"corrupted");
+    assert!(messages.iter().any(|m| m.contains("MISMATCH")
+        && m.contains("bad.md")
+        && m.contains(&expected)
+        && m.contains(&actual)));
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("1 of 2 local files failed verification"))
+    );
+}
+
+#[tokio::test]
+async fn test_check_offline_verifies_local_files_only() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    fs.write(&content_dir.join("guide.md"), "v1").await.unwrap();
+    Manifest::generate(&fs, &checksum, &content_dir, "1")
+        .await
+        .unwrap()
+        .save(&fs, &content_dir.join("manifest.json"))
+        .await
+        .unwrap();
+
+    let check = |quiet| CheckCommand {
+        path: temp_dir.path().to_path_buf(),
+        verbose: false,
+        format: OutputFormat::Text,
+        quiet,
+        source: ContentSource::default(),
+        offline: true,
+    };
+    let http = ReqwestClient::offline();
+
+    let logger = RecordingLogger::default();
+    let report = check(false).execute(&fs, &http, &checksum, &logger).await.unwrap();
+    assert_eq!(report.local_version, "1");
+    assert!(report.failed.is_empty());
+    assert!(logger.messages().iter().any(|m| m.contains("Remote not checked (offline)")));
+
+    let quiet = RecordingLogger::default();
+    assert!(check(true).execute(&fs, &http, &checksum, &quiet).await.is_ok());
+    assert!(quiet.messages().is_empty());
+
+    // Local checksums are still verified
+    fs.write(&content_dir.join("guide.md"), "edited").await.unwrap();
+    let logger = RecordingLogger::default();
+    let report = check(false).execute(&fs, &http, &checksum, &logger).await.unwrap();
+    assert_eq!(report.failed, vec![content_dir.join("guide.md")]);
+    assert!(logger.messages().iter().any(|m| m.contains("1 of 1 local files failed")));
+    let err = check(true).execute(&fs, &http, &checksum, &quiet).await.unwrap_err();
+    assert_eq!(err.exit_code(), 3);
+}
+
+#[tokio::test]
+async fn test_content_list_separates_managed_and_stray_files() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    fs.write(&content_dir.join("guidelines/good.md"), "good").await.unwrap();
+    fs.write(&content_dir.join("bad.md"), "original").await.unwrap();
+    fs.write(&content_dir.join("gone.md"), "gone").await.unwrap();
+    let manifest = Manifest::generate(&fs, &checksum, &content_dir, "1.0.0")
+        .await
+        .unwrap();
+    manifest
+        .save(&fs, &content_dir.join("manifest.json"))
+        .await
+        .unwrap();
+
+    fs.write(&content_dir.join("bad.md"), "corrupted").await.unwrap();
+    fs.remove_file(&content_dir.join("gone.md")).await.unwrap();
+    fs.write(&content_dir.join("leftover.md"), "old").await.unwrap();
+    fs.write(&content_dir.join(".tool-cache"), "{}").await.unwrap();
+
+    let logger = RecordingLogger::default();
+    let listing = ContentListCommand {
+        path: temp_dir.path().to_path_buf(),
+        verify: true,
+        verbose: false,
+        format: OutputFormat::Text,
+    }
+    .execute(&fs, &checksum, &logger)
+    .await
+    .unwrap();
+
+    let status = |name: &str| {
+        listing
+            .managed
+            .iter()
+            .find(|f| f.path == Path::new(name))
+            .and_then(|f| f.status)
+    };
+    assert_eq!(listing.managed.len(), 3);
+    assert_eq!(status("guidelines/good.md"), Some(ContentFileStatus::Ok));
+    assert_eq!(status("bad.md"), Some(ContentFileStatus::Mismatch));
+    assert_eq!(status("gone.md"), Some(ContentFileStatus::Missing));
+    // Bookkeeping files are neither managed nor stray
+    assert_eq!(listing.stray, vec![PathBuf::from("leftover.md")]);
+
+    let messages = logger.messages();
+    assert!(messages.iter().any(|m| m.contains("MISMATCH") && m.contains("bad.md")));
+    assert!(messages.iter().any(|m| m.contains("Stray files (1")));
+}
+
+// Serve `files` under `content_prefix` on a mirror, with their manifest at
+// /mirror/manifest.json
+async fn start_mirror(files: &[(&str, &str)], version: &str, content_prefix: &str) -> MockServer {
+    let server = MockServer::start().await;
+    let checksum = Sha2Checksum::new();
+
+    let mut manifest = Manifest {
+        version: version.to_string(),
+        files: Vec::new(),
+        self_checksum: None,
+    };
+    for (name, content) in files {
+        manifest.files.push(ManifestEntry {
+            path: PathBuf::from(name),
+            checksum: checksum.sha256(content.as_bytes()),
+        });
+        Mock::given(method("GET"))
+            .and(path(format!("{}/{}", content_prefix, name)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(*content))
+            .mount(&server)
+            .await;
+    }
+
+    Mock::given(method("GET"))
+        .and(path("/mirror/manifest.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&manifest))
+        .mount(&server)
+        .await;
+
+    server
+}
+
+#[tokio::test]
+async fn test_install_from_mirror_with_content_base() {
+    let files = [("guide.md", "v1"), ("rust/style.md", "style")];
+    let server = start_mirror(&files, "1", "/files").await;
+    let source = ContentSource::new(
+        Some(&format!("{}/mirror/manifest.json", server.uri())),
+        Some(&format!("{}/files/", server.uri())),
+    )
+    .unwrap();
+
+    let fs = StdFileSystem::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    let cmd = InstallCommand {
+        path: temp_dir.path().to_path_buf(),
+        dry_run: false,
+        concurrency: 2,
+        keep_going: false,
+        source,
+    };
+    let report = cmd
+        .execute(&fs, &ReqwestClient::new(), &Sha2Checksum::new(), &RecordingLogger::default())
+        .await
+        .unwrap();
+
+    assert_eq!(report.downloaded.len(), 2);
+    assert_eq!(report.manifest_version, "1");
+    assert_eq!(report.bytes, ("v1".len() + "style".len()) as u64);
+    assert_eq!(fs.read(&content_dir.join("rust/style.md")).await.unwrap(), "style");
+}
+
+#[tokio::test]
+async fn test_install_with_warm_cache_skips_content_downloads() {
+    let files = [("guide.md", "v1"), ("rust/style.md", "style")];
+    let server = start_mirror(&files, "1", "/files").await;
+    let cache_dir = TempDir::new().unwrap();
+    let source = ContentSource::new(
+        Some(&format!("{}/mirror/manifest.json", server.uri())),
+        Some(&format!("{}/files/", server.uri())),
+    )
+    .unwrap()
+    .with_cache(cache_dir.path().to_path_buf());
+
+    let fs = StdFileSystem::new();
+    let content_gets = || async {
+        let requests = server.received_requests().await.unwrap();
+        requests.iter().filter(|r| r.url.path().starts_with("/files/")).count()
+    };
+    let install = |project: PathBuf| InstallCommand {
+        path: project,
+        dry_run: false,
+        concurrency: 2,
+        keep_going: false,
+        source: source.clone(),
+    };
+
+    let first = TempDir::new().unwrap();
+    install(first.path().to_path_buf())
+        .execute(&fs, &ReqwestClient::new(), &Sha2Checksum::new(), &RecordingLogger::default())
+        .await
+        .unwrap();
+    assert_eq!(content_gets().await, 2);
+
+    // A tampered cache entry is re-downloaded rather than installed
+    let style_checksum = Sha2Checksum::new().sha256(b"style");
+    fs.write(&cache_dir.path().join("objects").join(&style_checksum), "tampered")
+        .await
+        .unwrap();
+
+    let second = TempDir::new().unwrap();
+    install(second.path().to_path_buf())
+        .execute(&fs, &ReqwestClient::new(), &Sha2Checksum::new(), &RecordingLogger::default())
+        .await
+        .unwrap();
+    assert_eq!(content_gets().await, 3);
+    let content_dir = second.path().join(".aiassisted");
+    assert_eq!(fs.read(&content_dir.join("rust/style.md")).await.unwrap(), "style");
+
+    // Fully warm: no content requests at all
+    let third = TempDir::new().unwrap();
+    install(third.path().to_path_buf())
+        .execute(&fs, &ReqwestClient::new(), &Sha2Checksum::new(), &RecordingLogger::default())
+        .await
+        .unwrap();
+    assert_eq!(content_gets().await, 3);
+    assert_eq!(fs.read(&third.path().join(".aiassisted/guide.md")).await.unwrap(), "v1");
+}
+
+#[tokio::test]
+async fn test_update_from_mirror_defaults_content_base_to_manifest_dir() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    // Existing installation at version 1
+    fs.write(&content_dir.join("guide.md"), "v1").await.unwrap();
+    fs.write(&content_dir.join("other.md"), "same").await.unwrap();
+    Manifest::generate(&fs, &checksum, &content_dir, "1")
+        .await
+        .unwrap()
+        .save(&fs, &content_dir.join("manifest.json"))
+        .await
+        .unwrap();
+
+    let server = start_mirror(&[("guide.md", "v2"), ("other.md", "same")], "2", "/mirror").await;
+    let source =
+        ContentSource::new(Some(&format!("{}/mirror/manifest.json", server.uri())), None).unwrap();
+
+    let cmd = UpdateCommand {
+        path: temp_dir.path().to_path_buf(),
+        force: false,
+        dry_run: false,
+        prune: false,
+        concurrency: 2,
+        keep_going: false,
+        backup: false,
+        keep_backups: 1,
+        rollback: false,
+        yes: false,
+        source,
+    };
+    let report = cmd
+        .execute(&fs, &ReqwestClient::new(), &checksum, &RecordingLogger::default())
+        .await
+        .unwrap();
+
+    assert_eq!(report.downloaded, vec![content_dir.join("guide.md")]);
+    assert_eq!(report.manifest_version, "2");
+    assert_eq!(fs.read(&content_dir.join("guide.md")).await.unwrap(), "v2");
+}
+
+/// Read an HTTP request head from `socket`, up to the blank line.
+async fn read_request_head(socket: &mut tokio::net::TcpStream) -> String {
+    use tokio::io::AsyncReadExt;
+
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") && socket.read(&mut byte).await.unwrap_or(0) == 1 {
+        head.push(byte[0]);
+    }
+    String::from_utf8_lossy(&head).into_owned()
+}
+
+/// Serve `body` at any path from a raw socket, hanging up halfway through
+/// every full response so only a resumed (ranged) request can complete it.
+///
+/// Returns the base URL and the `Range` header of each request received.
+async fn start_flaky_server(body: Vec<u8>) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+    use tokio::io::AsyncWriteExt;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}/", listener.local_addr().unwrap());
+    let ranges = Arc::new(Mutex::new(Vec::new()));
+    let seen = ranges.clone();
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let head = read_request_head(&mut socket).await;
+            let header = |name: &str| {
+                head.lines().find_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    key.eq_ignore_ascii_case(name).then(|| value.trim().to_string())
+                })
+            };
+            let range = header("range");
+            seen.lock().unwrap().push(range.clone());
+
+            let offset = range
+                .filter(|_| header("if-range").as_deref() == Some("\"v1\""))
+                .and_then(|range| range.strip_prefix("bytes=")?.strip_suffix('-')?.parse().ok());
+            let (head, rest) = match offset {
+                Some(offset) => (
+                    format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\n\
+                         Content-Range: bytes {}-{}/{}\r\n",
+                        body.len() - offset,
+                        offset,
+                        body.len() - 1,
+                        body.len()
+                    ),
+                    &body[offset..],
+                ),
+                None => (
+                    format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n", body.len()),
+                    &body[..body.len() / 2],
+                ),
+            };
+            let head = format!("{}ETag: \"v1\"\r\nConnection: close\r\n\r\n", head);
+            let _ = socket.write_all(head.as_bytes()).await;
+            let _ = socket.write_all(rest).await;
+            let _ = socket.shutdown().await;
+        }
+    });
+
+    (base, ranges)
+}
+
+#[tokio::test]
+async fn test_update_resumes_interrupted_download() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    fs.write(&content_dir.join("guide.md"), "v1").await.unwrap();
+    Manifest::generate(&fs, &checksum, &content_dir, "1")
+        .await
+        .unwrap()
+        .save(&fs, &content_dir.join("manifest.json"))
+        .await
+        .unwrap();
+
+    let body = "resumable guide\n".repeat(4096).into_bytes();
+    let (content_base, ranges) = start_flaky_server(body.clone()).await;
+    let server = MockServer::start().await;
+    let manifest = Manifest {
+        version: "2".to_string(),
+        files: vec![ManifestEntry {
+            path: PathBuf::from("guide.md"),
+            checksum: checksum.sha256(&body),
+        }],
+        self_checksum: None,
+    };
+    Mock::given(method("GET"))
+        .and(path("/manifest.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&manifest))
+        .mount(&server)
+        .await;
+
+    let update = UpdateCommand {
+        path: temp_dir.path().to_path_buf(),
+        force: false,
+        dry_run: false,
+        prune: false,
+        concurrency: 1,
+        keep_going: false,
+        backup: false,
+        keep_backups: 1,
+        rollback: false,
+        yes: false,
+        source: ContentSource::new(
+            Some(&format!("{}/manifest.json", server.uri())),
+            Some(&content_base),
+        )
+        .unwrap(),
+    };
+    let http = ReqwestClient::new();
+
+    // The connection drops mid-body: the installed file is untouched
+    let result = update
+        .execute(&fs, &http, &checksum, &RecordingLogger::default())
+        .await;
+    assert!(result.is_err());
+    assert_eq!(fs.read(&content_dir.join("guide.md")).await.unwrap(), "v1");
+    let partial = content_dir.join("guide.md.download.part");
+    let kept = std::fs::metadata(&partial).unwrap().len();
+    assert!(kept > 0 && kept < body.len() as u64);
+
+    // The next run asks for the rest instead of starting over
+    update
+        .execute(&fs, &http, &checksum, &RecordingLogger::default())
+        .await
+        .unwrap();
+    assert_eq!(fs.read_bytes(&content_dir.join("guide.md")).await.unwrap(), body);
+    assert!(!partial.exists());
+    assert!(!content_dir.join("guide.md.download").exists());
+    assert_eq!(
+        *ranges.lock().unwrap(),
+        vec![None, Some(format!("bytes={}-", kept))]
+    );
+}
+
+#[tokio::test]
+async fn test_update_backs_up_only_when_something_changes() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    fs.write(&content_dir.join("guide.md"), "v1").await.unwrap();
+    Manifest::generate(&fs, &checksum, &content_dir, "1")
+        .await
+        .unwrap()
+        .save(&fs, &content_dir.join("manifest.json"))
+        .await
+        .unwrap();
+
+    let update = |server: &MockServer| UpdateCommand {
+        path: temp_dir.path().to_path_buf(),
+        force: false,
+        dry_run: false,
+        prune: false,
+        concurrency: 2,
+        keep_going: false,
+        backup: true,
+        keep_backups: 1,
+        rollback: false,
+        yes: false,
+        source: ContentSource::new(Some(&format!("{}/mirror/manifest.json", server.uri())), None)
+            .unwrap(),
+    };
+    let http = ReqwestClient::new();
+
+    // Up to date: repeated runs leave no backups behind
+    let current = start_mirror(&[("guide.md", "v1")], "1", "/mirror").await;
+    for _ in 0..2 {
+        let report = update(&current)
+            .execute(&fs, &http, &checksum, &RecordingLogger::default())
+            .await
+            .unwrap();
+        assert_eq!(report.backup, None);
+    }
+    assert!(backup::list_backups(&fs, temp_dir.path()).await.unwrap().is_empty());
+
+    let newer = start_mirror(&[("guide.md", "v2")], "2", "/mirror").await;
+    let report = update(&newer)
+        .execute(&fs, &http, &checksum, &RecordingLogger::default())
+        .await
+        .unwrap();
+    let backup_dir = report.backup.unwrap();
+    assert_eq!(fs.read(&backup_dir.join("guide.md")).await.unwrap(), "v1");
+    assert_eq!(fs.read(&content_dir.join("guide.md")).await.unwrap(), "v2");
+    // The manifest was fetched once, not again by the update itself
+    let manifest_gets = newer
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == "/mirror/manifest.json")
+        .count();
+    assert_eq!(manifest_gets, 1);
+}
+
+#[tokio::test]
+async fn test_update_prune_removes_files_dropped_upstream() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    // Version 1 ships old.md and notes.md; version 2 drops both
+    fs.write(&content_dir.join("guide.md"), "v1").await.unwrap();
+    fs.write(&content_dir.join("old.md"), "old").await.unwrap();
+    fs.write(&content_dir.join("notes.md"), "notes").await.unwrap();
+    Manifest::generate(&fs, &checksum, &content_dir, "1")
+        .await
+        .unwrap()
+        .save(&fs, &content_dir.join("manifest.json"))
+        .await
+        .unwrap();
+
+    let server = start_mirror(&[("guide.md", "v1")], "2", "/mirror").await;
+    let source =
+        ContentSource::new(Some(&format!("{}/mirror/manifest.json", server.uri())), None).unwrap();
+    let cmd = UpdateCommand {
+        path: temp_dir.path().to_path_buf(),
+        force: false,
+        dry_run: false,
+        prune: false,
+        concurrency: 2,
+        keep_going: false,
+        backup: false,
+        keep_backups: 1,
+        rollback: false,
+        yes: false,
+        source,
+    };
+
+    // Without --prune the orphans stay, and the manifest keeps track of them
+    let logger = RecordingLogger::default();
+    let report = cmd
+        .execute(&fs, &ReqwestClient::new(), &checksum, &logger)
+        .await
+        .unwrap();
+    assert!(report.removed.is_empty());
+    assert!(content_dir.join("old.md").exists());
+    assert!(logger.messages().iter().any(|m| m.contains("Use --prune")));
+
+    // A later --prune deletes them, except the one edited in the meantime
+    fs.write(&content_dir.join("notes.md"), "my notes").await.unwrap();
+    let logger = RecordingLogger::default();
+    let report = UpdateCommand { prune: true, ..cmd }
+        .execute(&fs, &ReqwestClient::new(), &checksum, &logger)
+        .await
+        .unwrap();
+
+    assert_eq!(report.removed, vec![content_dir.join("old.md")]);
+    assert!(!content_dir.join("old.md").exists());
+    assert_eq!(fs.read(&content_dir.join("notes.md")).await.unwrap(), "my notes");
+    assert!(logger.messages().iter().any(|m| m.contains("Kept notes.md")));
+    assert_eq!(fs.read(&content_dir.join("guide.md")).await.unwrap(), "v1");
+}
+
+#[tokio::test]
+async fn test_update_keep_going_records_only_installed_files() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+    let manifest_path = content_dir.join("manifest.json");
+
+    fs.write(&content_dir.join("guide.md"), "v1").await.unwrap();
+    fs.write(&content_dir.join("style.md"), "s1").await.unwrap();
+    fs.write(&content_dir.join("old.md"), "old").await.unwrap();
+    Manifest::generate(&fs, &checksum, &content_dir, "1")
+        .await
+        .unwrap()
+        .save(&fs, &manifest_path)
+        .await
+        .unwrap();
+
+    // Version 2 changes style.md and adds new.md, but both fail to download
+    let files = [("guide.md", "v2"), ("style.md", "s2"), ("new.md", "new")];
+    let server = start_mirror(&files, "2", "/mirror").await;
+    for broken in ["/mirror/style.md", "/mirror/new.md"] {
+        Mock::given(method("GET"))
+            .and(path(broken))
+            .respond_with(ResponseTemplate::new(500))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+    }
+
+    let cmd = UpdateCommand {
+        path: temp_dir.path().to_path_buf(),
+        force: false,
+        dry_run: false,
+        prune: false,
+        concurrency: 2,
+        keep_going: true,
+        backup: false,
+        keep_backups: 1,
+        rollback: false,
+        yes: false,
+        source: ContentSource::new(Some(&format!("{}/mirror/manifest.json", server.uri())), None)
+            .unwrap(),
+    };
+    let result = cmd
+        .execute(&fs, &ReqwestClient::new(), &checksum, &RecordingLogger::default())
+        .await;
+    assert!(result.is_err());
+
+    let recorded = Manifest::load_local(&fs, &manifest_path).await.unwrap();
+    let recorded_checksum = |name: &str| {
+        recorded
+            .files
+            .iter()
+            .find(|e| e.path == Path::new(name))
+            .map(|e| e.checksum.clone())
+    };
+    assert_eq!(recorded_checksum("guide.md"), Some(checksum.sha256(b"v2")));
+    assert_eq!(recorded_checksum("style.md"), Some(checksum.sha256(b"s1")));
+    assert_eq!(recorded_checksum("new.md"), None);
+    assert_eq!(recorded_checksum("old.md"), Some(checksum.sha256(b"old")));
+}
+
+#[tokio::test]
+async fn test_check_quiet_exit_codes() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    fs.write(&content_dir.join("guide.md"), "v1").await.unwrap();
+    Manifest::generate(&fs, &checksum, &content_dir, "1")
+        .await
+        .unwrap()
+        .save(&fs, &content_dir.join("manifest.json"))
+        .await
+        .unwrap();
+
+    let check = |server: &MockServer| CheckCommand {
+        path: temp_dir.path().to_path_buf(),
+        verbose: false,
+        format: OutputFormat::Text,
+        quiet: true,
+        source: ContentSource::new(Some(&format!("{}/mirror/manifest.json", server.uri())), None)
+            .unwrap(),
+        offline: false,
+    };
+    let logger = RecordingLogger::default();
+    let http = ReqwestClient::new();
+
+    // Intact and up to date
+    let current = start_mirror(&[("guide.md", "v1")], "1", "/mirror").await;
+    assert!(check(&current).execute(&fs, &http, &checksum, &logger).await.is_ok());
+
+    // Upstream moved on
+    let newer = start_mirror(&[("guide.md", "v2")], "2", "/mirror").await;
+    let err = check(&newer).execute(&fs, &http, &checksum, &logger).await.unwrap_err();
+    assert_eq!(err.exit_code(), 7);
+
+    // Local drift is reported before the remote is consulted
+    fs.write(&content_dir.join("guide.md"), "edited").await.unwrap();
+    let err = check(&current).execute(&fs, &http, &checksum, &logger).await.unwrap_err();
+    assert_eq!(err.exit_code(), 3);
+
+    assert!(logger.messages().is_empty());
+}
+
+#[tokio::test]
+async fn test_watch_detects_mid_loop_change() {
+    let fs = StdFileSystem::new();
+    let checksum = Sha2Checksum::new();
+    let temp_dir = TempDir::new().unwrap();
+    let content_dir = temp_dir.path().join(".aiassisted");
+
+    fs.write(&content_dir.join("guide.md"), "v1").await.unwrap();
+    fs.write(&content_dir.join("other.md"), "other").await.unwrap();
+    Manifest::generate(&fs, &checksum, &content_dir, "1")
+        .await
+        .unwrap()
+        .save(&fs, &content_dir.join("manifest.json"))
+        .await
+        .unwrap();
+
+    let watch = |fail_on_change| WatchCommand {
+        path: temp_dir.path().to_path_buf(),
+        interval: std::time::Duration::from_millis(10),
+        fail_on_change,
+    };
+    let tamper = |contents: &'static str| {
+        let fs = fs.clone();
+        let path = content_dir.join("guide.md");
+        async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            fs.write(&path, contents).await.unwrap();
+        }
+    };
+
+    // Reported while running, then the watch stops cleanly on shutdown
+    let logger = RecordingLogger::default();
+    let shutdown = async {
+        tamper("edited").await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    };
+    watch(false).execute(&fs, &checksum, &logger, shutdown).await.unwrap();
+    let messages = logger.messages();
+    assert_eq!(messages.iter().filter(|m| m.starts_with("Changed:")).count(), 1);
+    assert!(messages.iter().any(|m| m.contains("guide.md (modified)")));
+    assert_eq!(messages.last().unwrap(), "Stopped watching");
+
+    // --fail-on-change stops at the first change and never needs a shutdown
+    fs.write(&content_dir.join("guide.md"), "v1").await.unwrap();
+    let logger = RecordingLogger::default();
+    let failing_watch = watch(true);
+    let (result, ()) = tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        tokio::join!(
+            failing_watch.execute(&fs, &checksum, &logger, std::future::pending()),
+            tamper("tampered")
+        )
+    })
+    .await
+    .expect("--fail-on-change watch never stopped");
+    let err = result.unwrap_err();
+    assert_eq!(err.exit_code(), 3);
+    assert!(logger.messages().iter().any(|m| m.contains("guide.md (modified)")));
+}